@@ -0,0 +1,247 @@
+//! Derive macro for the [cli-prompts](https://crates.io/crates/cli-prompts) crate.
+//!
+//! Use it through the `derive` feature of `cli-prompts`, which re-exports the macro as
+//! `cli_prompts::Promptable`. See the `cli_prompts::promptable` module for the details.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::{
+    parse_macro_input, spanned::Spanned, Data, DataEnum, DataStruct, DeriveInput, Error, Fields,
+    GenericArgument, Lit, LitStr, Path, PathArguments, Result, Type,
+};
+
+/// Implements `Promptable` for a struct with named fields, or `Choice` and `PromptField` for an
+/// enum without fields
+#[proc_macro_derive(Promptable, attributes(prompt))]
+pub fn derive_promptable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let result = match &input.data {
+        Data::Struct(data) => derive_struct(&input, data),
+        Data::Enum(data) => derive_enum(&input, data),
+        Data::Union(_) => Err(Error::new_spanned(
+            &input.ident,
+            "Promptable can't be derived for unions",
+        )),
+    };
+
+    result.unwrap_or_else(Error::into_compile_error).into()
+}
+
+#[derive(Default)]
+struct PromptAttributes {
+    label: Option<String>,
+    help: Option<String>,
+    default: Option<String>,
+    validator: Option<Path>,
+    skip: bool,
+}
+
+fn derive_struct(input: &DeriveInput, data: &DataStruct) -> Result<TokenStream2> {
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "Promptable can only be derived for structs with named fields",
+        ));
+    };
+
+    let mut steps = vec![];
+    let mut initializers = vec![];
+    for field in &fields.named {
+        let ident = field.ident.as_ref().expect("Named fields have identifiers");
+        let attributes = parse_attributes(&field.attrs)?;
+        if attributes.skip {
+            initializers.push(quote! { #ident: ::core::default::Default::default() });
+            continue;
+        }
+
+        let key = ident.to_string();
+        let label = attributes
+            .label
+            .clone()
+            .unwrap_or_else(|| label_from_field_name(&key));
+        let (value_type, is_optional) = match option_inner_type(&field.ty) {
+            Some(inner) => (inner, true),
+            None => (&field.ty, false),
+        };
+        let settings = field_settings(value_type, &label, &attributes);
+
+        if is_optional {
+            steps.push(quote! {
+                let form = <#value_type as ::cli_prompts::promptable::PromptField>::add_optional_steps(form, #key, #settings);
+            });
+            initializers.push(quote! {
+                #ident: <#value_type as ::cli_prompts::promptable::PromptField>::from_optional_answer(answers, #key)
+            });
+        } else {
+            steps.push(quote! {
+                let form = <#value_type as ::cli_prompts::promptable::PromptField>::add_steps(form, #key, #settings);
+            });
+            initializers.push(quote! {
+                #ident: <#value_type as ::cli_prompts::promptable::PromptField>::from_answer(answers, #key)?
+            });
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::cli_prompts::promptable::Promptable for #name #type_generics #where_clause {
+            fn form() -> ::cli_prompts::prompts::Form {
+                let form = ::cli_prompts::prompts::Form::new();
+                #(#steps)*
+                form
+            }
+
+            fn from_answers(answers: &mut ::cli_prompts::prompts::Answers) -> ::core::option::Option<Self> {
+                ::core::option::Option::Some(Self {
+                    #(#initializers,)*
+                })
+            }
+        }
+    })
+}
+
+fn derive_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream2> {
+    let name = &input.ident;
+    let mut variants = vec![];
+    let mut names = vec![];
+    let mut labels = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new_spanned(
+                variant,
+                "Promptable can only be derived for enums without fields",
+            ));
+        }
+
+        let attributes = parse_attributes(&variant.attrs)?;
+        let ident = &variant.ident;
+        variants.push(quote! { #name::#ident });
+        names.push(ident.to_string());
+        labels.push(attributes.label.unwrap_or_else(|| ident.to_string()));
+    }
+
+    if variants.is_empty() {
+        return Err(Error::new_spanned(
+            name,
+            "Promptable can't be derived for enums without variants",
+        ));
+    }
+
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::cli_prompts::promptable::Choice for #name #type_generics #where_clause {
+            fn choices() -> ::std::vec::Vec<Self> {
+                ::std::vec![#(#variants),*]
+            }
+
+            fn name(&self) -> &'static str {
+                match self {
+                    #(#variants => #names,)*
+                }
+            }
+
+            fn label(&self) -> ::std::string::String {
+                match self {
+                    #(#variants => ::std::string::String::from(#labels),)*
+                }
+            }
+        }
+
+        impl #impl_generics ::cli_prompts::promptable::PromptField for #name #type_generics #where_clause {
+            fn add_steps(
+                form: ::cli_prompts::prompts::Form,
+                key: &str,
+                settings: ::cli_prompts::promptable::FieldSettings<Self>,
+            ) -> ::cli_prompts::prompts::Form {
+                ::cli_prompts::promptable::add_choice_steps(form, key, settings)
+            }
+        }
+    })
+}
+
+fn field_settings(value_type: &Type, label: &str, attributes: &PromptAttributes) -> TokenStream2 {
+    let help = attributes.help.as_ref().map(|help| quote! { .help(#help) });
+    let default = attributes
+        .default
+        .as_ref()
+        .map(|default| quote! { .default_value(#default) });
+    let validator = attributes
+        .validator
+        .as_ref()
+        .map(|validator| quote_spanned! { validator.span()=> .validator(#validator) });
+
+    quote! {
+        ::cli_prompts::promptable::FieldSettings::<#value_type>::new(#label)
+            #help
+            #default
+            #validator
+    }
+}
+
+fn parse_attributes(attrs: &[syn::Attribute]) -> Result<PromptAttributes> {
+    let mut attributes = PromptAttributes::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("prompt")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("label") {
+                attributes.label = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("help") {
+                attributes.help = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("default") {
+                attributes.default = Some(literal_to_string(&meta.value()?.parse::<Lit>()?)?);
+            } else if meta.path.is_ident("validator") {
+                let value = meta.value()?;
+                attributes.validator = Some(if value.peek(LitStr) {
+                    value.parse::<LitStr>()?.parse::<Path>()?
+                } else {
+                    value.parse::<Path>()?
+                });
+            } else if meta.path.is_ident("skip") {
+                attributes.skip = true;
+            } else {
+                return Err(meta.error("unknown prompt attribute"));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(attributes)
+}
+
+fn literal_to_string(literal: &Lit) -> Result<String> {
+    match literal {
+        Lit::Str(s) => Ok(s.value()),
+        Lit::Char(c) => Ok(c.value().to_string()),
+        Lit::Int(i) => Ok(i.base10_digits().to_owned()),
+        Lit::Float(f) => Ok(f.base10_digits().to_owned()),
+        Lit::Bool(b) => Ok(b.value.to_string()),
+        _ => Err(Error::new_spanned(literal, "unsupported default value")),
+    }
+}
+
+fn option_inner_type(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return None;
+    };
+    match arguments.args.first()? {
+        GenericArgument::Type(inner) => Some(inner),
+        _ => None,
+    }
+}
+
+fn label_from_field_name(name: &str) -> String {
+    let words = name.trim_start_matches("r#").replace('_', " ");
+    let mut chars = words.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
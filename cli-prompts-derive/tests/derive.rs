@@ -0,0 +1,140 @@
+use cli_prompts::{
+    engine::RecordingBuffer,
+    input::Key,
+    prompts::{EventOutcome, Prompt},
+    Promptable,
+};
+
+#[derive(Promptable, Clone, Debug, PartialEq)]
+enum Engine {
+    Postgres,
+    #[prompt(label = "MySQL")]
+    Mysql,
+    Sqlite,
+}
+
+#[derive(Promptable, Debug, PartialEq)]
+struct DatabaseConfig {
+    #[prompt(label = "Database name", validator = not_empty)]
+    name: String,
+    #[prompt(default = "Sqlite")]
+    engine: Engine,
+    #[prompt(default = 5432, help = "Port of the database server")]
+    port: u16,
+    user: Option<String>,
+    replicas: Option<Engine>,
+    backups: Vec<Engine>,
+    create_if_missing: bool,
+    #[prompt(skip)]
+    retries: u32,
+}
+
+#[allow(clippy::ptr_arg)]
+fn not_empty(name: &String) -> Result<(), String> {
+    if name.is_empty() {
+        Err("Name must not be empty".into())
+    } else {
+        Ok(())
+    }
+}
+
+fn answer<T: Promptable>(keys: Vec<Vec<Key>>) -> T {
+    let mut form = T::form();
+    for key in keys.into_iter().flatten() {
+        if let EventOutcome::Done(mut answers) = form.on_key_pressed(key) {
+            return T::from_answers(&mut answers).expect("All the fields are answered");
+        }
+    }
+    panic!("The form is expected to be submitted");
+}
+
+fn text(s: &str) -> Vec<Key> {
+    s.chars().map(Key::Char).chain([Key::Enter]).collect()
+}
+
+#[test]
+fn prompts_for_all_fields() {
+    let keys = vec![
+        vec![Key::Enter],
+        text("app"),
+        vec![Key::Up, Key::Enter],
+        vec![Key::Enter],
+        vec![Key::Enter],
+        text("n"),
+        vec![
+            Key::Char(' '),
+            Key::Down,
+            Key::Down,
+            Key::Char(' '),
+            Key::Enter,
+        ],
+        text("y"),
+        vec![Key::End, Key::Enter],
+    ];
+
+    let config: DatabaseConfig = answer(keys);
+    assert_eq!(
+        config,
+        DatabaseConfig {
+            name: "app".into(),
+            engine: Engine::Mysql,
+            port: 5432,
+            user: None,
+            replicas: None,
+            backups: vec![Engine::Postgres, Engine::Sqlite],
+            create_if_missing: true,
+            retries: 0,
+        }
+    );
+}
+
+#[test]
+fn asks_optional_choice_after_confirmation() {
+    let keys = vec![
+        text("app"),
+        vec![Key::Enter, Key::Enter],
+        text("admin"),
+        text("y"),
+        vec![Key::Down, Key::Enter],
+        vec![Key::Enter],
+        text("n"),
+        vec![Key::End, Key::Enter],
+    ];
+
+    let config: DatabaseConfig = answer(keys);
+    assert_eq!(config.engine, Engine::Sqlite);
+    assert_eq!(config.user.as_deref(), Some("admin"));
+    assert_eq!(config.replicas, Some(Engine::Mysql));
+    assert!(config.backups.is_empty());
+}
+
+#[derive(Promptable)]
+struct DeploySettings {
+    #[prompt(help = "Target database engine")]
+    engine: Engine,
+    #[prompt(help = "Keep the old version running")]
+    canary: bool,
+}
+
+#[test]
+fn displays_help_of_selection_and_confirmation() {
+    let mut form = DeploySettings::form();
+    let mut commands = RecordingBuffer::default();
+    form.draw(&mut commands);
+    assert!(commands.text().contains("[Target database engine]"));
+
+    form.on_key_pressed(Key::Enter);
+    let mut commands = RecordingBuffer::default();
+    form.draw(&mut commands);
+    assert!(commands.text().contains("[Keep the old version running]"));
+
+    for key in [Key::Char('y'), Key::Enter, Key::End] {
+        form.on_key_pressed(key);
+    }
+    let EventOutcome::Done(mut answers) = form.on_key_pressed(Key::Enter) else {
+        panic!("The form is expected to be submitted");
+    };
+    let settings = DeploySettings::from_answers(&mut answers).unwrap();
+    assert_eq!(settings.engine, Engine::Postgres);
+    assert!(settings.canary);
+}
@@ -4,6 +4,7 @@ use cli_prompts::{
 };
 
 #[derive(Debug)]
+#[allow(clippy::upper_case_acronyms)]
 enum CarModel {
     Audi,
     BMW,
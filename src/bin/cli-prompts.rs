@@ -0,0 +1,511 @@
+//! Command line interface to the prompts for shell scripts.
+//!
+//! The prompt is drawn on the terminal and the answer is printed to the standard output,
+//! so it can be captured with `$(...)`:
+//!
+//! ```sh
+//! name=$(cli-prompts input "Project name" --default my-app)
+//! engine=$(printf "postgres\nmysql\nsqlite\n" | cli-prompts select "Database engine")
+//! if cli-prompts confirm "Deploy $name with $engine?"; then
+//!     ./deploy.sh "$name" "$engine"
+//! fi
+//! ```
+
+use std::{
+    fs::OpenOptions,
+    io::{self, BufRead, IsTerminal, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use cli_prompts::{
+    engine::CrosstermEngine,
+    prompts::{AbortReason, Confirmation, Input, Multiselect, Password, Selection},
+    questionnaire::{Questionnaire, QuestionnaireError},
+    style::{
+        Color, ConfirmationStyle, Formatting, InputStyle, LabelStyle, MultiselectionStyle,
+        SelectionStyle, Theme,
+    },
+    DisplayPrompt,
+};
+use regex::Regex;
+
+const EXIT_NO: u8 = 1;
+const EXIT_INVALID_INPUT: u8 = 2;
+const EXIT_IO_ERROR: u8 = 3;
+const EXIT_ABORTED: u8 = 130;
+
+const EXIT_CODES_HELP: &str = "Exit codes:
+  0    The prompt is completed. For `confirm` the answer is yes
+  1    The answer to `confirm` is no
+  2    Invalid arguments, options or questionnaire definition
+  3    I/O error
+  130  The prompt is aborted with Esc or Ctrl+C";
+
+/// Interactive prompts for shell scripts. The prompt is drawn on the terminal and the answer is
+/// printed to the standard output
+#[derive(Parser)]
+#[command(name = "cli-prompts", version, after_help = EXIT_CODES_HELP)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    #[command(flatten)]
+    style: StyleArgs,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Asks for a line of text
+    Input {
+        /// Text of the prompt
+        label: String,
+
+        /// Value that is used when the user just presses Enter
+        #[arg(long)]
+        default: Option<String>,
+
+        /// Help message displayed after the input
+        #[arg(long)]
+        help_message: Option<String>,
+
+        /// Don't accept an empty answer
+        #[arg(long)]
+        required: bool,
+
+        /// Regular expression that the answer must match
+        #[arg(long)]
+        regex: Option<Regex>,
+    },
+
+    /// Asks for a line of text without showing it
+    Password {
+        /// Text of the prompt
+        label: String,
+
+        /// Character that is displayed instead of the typed ones
+        #[arg(long, default_value_t = '*')]
+        mask: char,
+
+        /// Don't display anything while typing
+        #[arg(long)]
+        hidden: bool,
+
+        /// Don't accept an empty answer
+        #[arg(long)]
+        required: bool,
+    },
+
+    /// Asks a yes or no question. The answer is returned as the exit code
+    Confirm {
+        /// Text of the prompt
+        label: String,
+
+        /// Makes yes the default answer
+        #[arg(long)]
+        default_yes: bool,
+    },
+
+    /// Asks to select one of the options
+    Select {
+        /// Text of the prompt
+        label: String,
+
+        /// Options to select from. Read from the standard input, one per line, if omitted
+        options: Vec<String>,
+
+        /// Option that is highlighted initially
+        #[arg(long)]
+        default: Option<String>,
+
+        /// Print the index of the selected option instead of the option itself
+        #[arg(long)]
+        index: bool,
+
+        /// Number of options displayed at once
+        #[arg(long)]
+        height: Option<u16>,
+    },
+
+    /// Asks to select several options. The selected options are printed one per line
+    Multiselect {
+        /// Text of the prompt
+        label: String,
+
+        /// Options to select from. Read from the standard input, one per line, if omitted
+        options: Vec<String>,
+
+        /// Option that is selected initially. Can be repeated
+        #[arg(long = "selected")]
+        selected: Vec<String>,
+
+        /// Minimum number of the selected options
+        #[arg(long, default_value_t = 1)]
+        min: usize,
+
+        /// Maximum number of the selected options
+        #[arg(long)]
+        max: Option<usize>,
+
+        /// Number of options displayed at once
+        #[arg(long)]
+        height: Option<u16>,
+    },
+
+    /// Asks the questions from a JSON, TOML or YAML file and prints the answers as JSON
+    Form {
+        /// Path to the questionnaire definition
+        file: PathBuf,
+
+        /// Print the answers in a human readable JSON
+        #[arg(long)]
+        pretty: bool,
+    },
+}
+
+/// Options that change the appearance of the prompts
+#[derive(Args)]
+struct StyleArgs {
+    /// Theme that the other styling options are applied on top of.
+    /// Defaults to the one from `CLI_PROMPTS_THEME` or `~/.config/cli-prompts/theme.toml`
+    #[arg(long, global = true, value_enum)]
+    theme: Option<ThemePreset>,
+
+    /// String displayed before the prompt
+    #[arg(long, global = true)]
+    prefix: Option<String>,
+
+    /// Color of the prefix
+    #[arg(long, global = true, value_name = "COLOR")]
+    prefix_color: Option<Color>,
+
+    /// Color of the prompt text
+    #[arg(long, global = true, value_name = "COLOR")]
+    label_color: Option<Color>,
+
+    /// Color of the text being typed
+    #[arg(long, global = true, value_name = "COLOR")]
+    input_color: Option<Color>,
+
+    /// Color of the answer once the prompt is completed
+    #[arg(long, global = true, value_name = "COLOR")]
+    answer_color: Option<Color>,
+
+    /// Color of the highlighted option
+    #[arg(long, global = true, value_name = "COLOR")]
+    highlight_color: Option<Color>,
+
+    /// Color of the help messages
+    #[arg(long, global = true, value_name = "COLOR")]
+    help_color: Option<Color>,
+
+    /// Color of the error messages
+    #[arg(long, global = true, value_name = "COLOR")]
+    error_color: Option<Color>,
+
+    /// Marker of the highlighted option in `select`
+    #[arg(long, global = true)]
+    marker: Option<String>,
+
+    /// Erase the prompt from the screen once it is completed
+    #[arg(long, global = true)]
+    transient: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ThemePreset {
+    Default,
+    Minimal,
+    HighContrast,
+    ColorblindSafe,
+}
+
+enum Failure {
+    InvalidInput(String),
+    Io(io::Error),
+    Aborted,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(code) => ExitCode::from(code),
+        Err(Failure::Aborted) => ExitCode::from(EXIT_ABORTED),
+        Err(Failure::InvalidInput(message)) => {
+            eprintln!("error: {}", message);
+            ExitCode::from(EXIT_INVALID_INPUT)
+        }
+        Err(Failure::Io(error)) => {
+            eprintln!("error: {}", error);
+            ExitCode::from(EXIT_IO_ERROR)
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<u8, Failure> {
+    let style = &cli.style;
+    style.theme()?.install();
+    match cli.command {
+        Command::Input {
+            label,
+            default,
+            help_message,
+            required,
+            regex,
+        } => {
+            let mut prompt = Input::new(label, |text: &str| validate_text(text, required, &regex))
+                .style(style.input_style());
+            if let Some(default) = default {
+                prompt = prompt.default_value(default);
+            }
+            if let Some(help_message) = help_message {
+                prompt = prompt.help_message(help_message);
+            }
+            print_answer(prompt.display_with(style.engine())?)
+        }
+        Command::Password {
+            label,
+            mask,
+            hidden,
+            required,
+        } => {
+            let mut prompt =
+                Password::new(label, |text: &str| validate_text(text, required, &None))
+                    .mask(mask)
+                    .style(style.input_style());
+            if hidden {
+                prompt = prompt.hide_input();
+            }
+            print_answer(prompt.display_with(style.engine())?)
+        }
+        Command::Confirm { label, default_yes } => {
+            let prompt = Confirmation::new(label)
+                .default_positive(default_yes)
+                .style(style.confirmation_style());
+            match prompt.display_with(style.engine())? {
+                true => Ok(0),
+                false => Ok(EXIT_NO),
+            }
+        }
+        Command::Select {
+            label,
+            options,
+            default,
+            index,
+            height,
+        } => {
+            let options = options_or_stdin(options)?;
+            let mut prompt =
+                Selection::new(label, options.into_iter()).style(style.selection_style());
+            if let Some(default) = default {
+                prompt = prompt.default_option(|o| *o == default);
+            }
+            if let Some(height) = height {
+                prompt = prompt.displayed_options_count(height);
+            }
+
+            if index {
+                print_answer(
+                    prompt
+                        .return_index()
+                        .display_with(style.engine())?
+                        .to_string(),
+                )
+            } else {
+                print_answer(prompt.display_with(style.engine())?)
+            }
+        }
+        Command::Multiselect {
+            label,
+            options,
+            selected,
+            min,
+            max,
+            height,
+        } => {
+            let options = options_or_stdin(options)?;
+            let mut prompt = Multiselect::new(label, options.into_iter())
+                .min_selections(min)
+                .preselect(|o| selected.contains(o))
+                .style(style.multiselection_style());
+            if let Some(max) = max {
+                prompt = prompt.max_selections(max);
+            }
+            if let Some(height) = height {
+                prompt = prompt.max_displayed_options(height);
+            }
+            print_answer(prompt.display_with(style.engine())?.join("\n"))
+        }
+        Command::Form { file, pretty } => {
+            let questionnaire = Questionnaire::from_file(file)?;
+            let answers = questionnaire.form().display_with(style.engine())?;
+            let answers = questionnaire.collect_answers(answers);
+            let output = if pretty {
+                serde_json::to_string_pretty(&answers)
+            } else {
+                serde_json::to_string(&answers)
+            };
+            print_answer(output.map_err(|e| Failure::InvalidInput(e.to_string()))?)
+        }
+    }
+}
+
+impl StyleArgs {
+    fn engine(&self) -> CrosstermEngine<Box<dyn Write>> {
+        CrosstermEngine::new(terminal()).transient(self.transient)
+    }
+
+    fn theme(&self) -> Result<Theme, Failure> {
+        let theme = match self.theme {
+            Some(ThemePreset::Default) => Theme::default(),
+            Some(ThemePreset::Minimal) => Theme::minimal(),
+            Some(ThemePreset::HighContrast) => Theme::high_contrast(),
+            Some(ThemePreset::ColorblindSafe) => Theme::colorblind_safe(),
+            None => Theme::from_config()
+                .map_err(|e| Failure::InvalidInput(format!("theme: {}", e)))?
+                .unwrap_or_default(),
+        };
+        Ok(theme)
+    }
+
+    fn label_style(&self) -> LabelStyle {
+        let mut style = LabelStyle::default();
+        if let Some(prefix) = self.prefix.as_ref() {
+            style = style.prefix(prefix);
+        }
+        if let Some(color) = self.prefix_color {
+            style = style.prefix_formatting(Formatting::default().bold().foreground_color(color));
+        }
+        if let Some(color) = self.label_color {
+            style = style.prompt_formatting(Formatting::default().bold().foreground_color(color));
+        }
+        style
+    }
+
+    fn input_style(&self) -> InputStyle {
+        let mut style = InputStyle::default().label_style(self.label_style());
+        set_color(&mut style.input_formatting, self.input_color);
+        set_color(&mut style.submitted_formatting, self.answer_color);
+        set_color(&mut style.help_message_formatting, self.help_color);
+        set_color(&mut style.error_formatting, self.error_color);
+        style
+    }
+
+    fn confirmation_style(&self) -> ConfirmationStyle {
+        let mut style = ConfirmationStyle::default().label_style(self.label_style());
+        set_color(&mut style.input_formatting, self.input_color);
+        set_color(&mut style.submitted_formatting, self.answer_color);
+        style
+    }
+
+    fn selection_style(&self) -> SelectionStyle {
+        let mut style = SelectionStyle::default().label_style(self.label_style());
+        set_color(&mut style.filter_formatting, self.input_color);
+        set_color(&mut style.submitted_formatting, self.answer_color);
+        set_color(&mut style.selected_option_formatting, self.highlight_color);
+        set_color(&mut style.selected_marker.formatting, self.highlight_color);
+        set_color(&mut style.error_formatting, self.error_color);
+        if let Some(marker) = self.marker.as_ref() {
+            style.selected_marker.marker = format!("{} ", marker);
+            style.not_selected_marker.marker = " ".repeat(marker.chars().count() + 1);
+        }
+        style
+    }
+
+    fn multiselection_style(&self) -> MultiselectionStyle {
+        let mut style = MultiselectionStyle {
+            label_style: self.label_style(),
+            ..Default::default()
+        };
+        set_color(&mut style.filter_formatting, self.input_color);
+        set_color(&mut style.submitted_formatting, self.answer_color);
+        set_color(
+            &mut style.highlighted_option_formatting,
+            self.highlight_color,
+        );
+        set_color(&mut style.help_message_formatting, self.help_color);
+        set_color(&mut style.error_formatting, self.error_color);
+        style
+    }
+}
+
+fn set_color(formatting: &mut Formatting, color: Option<Color>) {
+    if color.is_some() {
+        formatting.foreground_color = color;
+    }
+}
+
+fn validate_text(text: &str, required: bool, regex: &Option<Regex>) -> Result<String, String> {
+    if required && text.is_empty() {
+        Err("The answer is required".into())
+    } else if regex.as_ref().is_some_and(|r| !r.is_match(text)) {
+        Err("The answer has invalid format".into())
+    } else {
+        Ok(text.to_string())
+    }
+}
+
+/// The prompts are drawn on the terminal even if the standard output is redirected
+fn terminal() -> Box<dyn Write> {
+    match OpenOptions::new().write(true).open("/dev/tty") {
+        Ok(tty) => Box::new(tty),
+        Err(_) => Box::new(io::stderr()),
+    }
+}
+
+fn options_or_stdin(options: Vec<String>) -> Result<Vec<String>, Failure> {
+    if !options.is_empty() {
+        return Ok(options);
+    }
+
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        return Err(Failure::InvalidInput(
+            "no options are given in the arguments or the standard input".into(),
+        ));
+    }
+
+    let options = stdin
+        .lock()
+        .lines()
+        .filter(|line| line.as_ref().map_or(true, |l| !l.trim().is_empty()))
+        .collect::<io::Result<Vec<String>>>()?;
+    if options.is_empty() {
+        return Err(Failure::InvalidInput("the list of options is empty".into()));
+    }
+
+    Ok(options)
+}
+
+fn print_answer(answer: String) -> Result<u8, Failure> {
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", answer)?;
+    stdout.flush()?;
+    Ok(0)
+}
+
+impl From<AbortReason> for Failure {
+    fn from(reason: AbortReason) -> Self {
+        match reason {
+            AbortReason::Interrupt => Failure::Aborted,
+            AbortReason::Error(error) => Failure::Io(error),
+        }
+    }
+}
+
+impl From<io::Error> for Failure {
+    fn from(error: io::Error) -> Self {
+        Failure::Io(error)
+    }
+}
+
+impl From<QuestionnaireError> for Failure {
+    fn from(error: QuestionnaireError) -> Self {
+        match error {
+            QuestionnaireError::Io(error) => Failure::Io(error),
+            QuestionnaireError::Aborted(reason) => reason.into(),
+            error => Failure::InvalidInput(error.to_string()),
+        }
+    }
+}
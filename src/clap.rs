@@ -0,0 +1,365 @@
+//! Integration with [clap](https://docs.rs/clap): missing required arguments are asked
+//! interactively instead of failing.
+//!
+//! The prompt for the argument is chosen by its definition:
+//! - arguments with possible values, e.g. value enums, use `Selection`, or `Multiselect` if
+//!   the argument accepts several values;
+//! - flags use `Confirmation`;
+//! - other arguments use `Input` that validates the text with the argument's value parser.
+//!
+//! The help text of the argument becomes the help message of the prompt. The prompts are only
+//! displayed when the standard input and error streams are terminals, otherwise the usual clap
+//! error is returned. The answers are added to the command line, which is then parsed again.
+//! If the user interrupts a prompt, `get_matches` and `parse` exit with code 130, like shells do
+//! for Ctrl+C.
+//!
+//! ```rust,no_run
+//! use clap::{Arg, Command};
+//!
+//! let command = Command::new("deploy")
+//!     .arg(Arg::new("environment").long("env").required(true).value_parser(["staging", "production"]))
+//!     .arg(Arg::new("version").required(true).help("Version to deploy"));
+//!
+//! let matches = cli_prompts::clap::get_matches(command);
+//! ```
+//!
+//! This module is available with the `clap` feature.
+
+use std::{
+    ffi::OsString,
+    io::{stderr, stdin, IsTerminal},
+};
+
+use ::clap::{error::ErrorKind, Arg, ArgMatches, Command, Parser};
+
+use crate::{
+    prompts::{AbortReason, Confirmation, Input, Multiselect, Selection},
+    DisplayPrompt,
+};
+
+const EXIT_INTERRUPTED: i32 = 130;
+const INTERRUPTED_MESSAGE: &str = "the prompt for the missing arguments was interrupted";
+
+/// Reasons why the arguments are not parsed
+enum Failure {
+    Invalid(::clap::Error),
+    Aborted(AbortReason),
+}
+
+/// Parses the arguments of the process, asking for the missing required ones.
+/// Exits the process on error, like `Command::get_matches`
+pub fn get_matches(command: Command) -> ArgMatches {
+    match matches_from(command.clone(), std::env::args_os()) {
+        Ok(matches) => matches,
+        Err(Failure::Aborted(AbortReason::Interrupt)) => std::process::exit(EXIT_INTERRUPTED),
+        Err(Failure::Aborted(AbortReason::Error(error))) => prompt_error(command, error).exit(),
+        Err(Failure::Invalid(error)) => error.exit(),
+    }
+}
+
+/// Parses the given arguments, asking for the missing required ones.
+///
+/// If the user interrupts a prompt, the error of the `MissingRequiredArgument` kind is returned.
+/// I/O errors of the prompts are returned as the errors of the `Io` kind
+pub fn try_get_matches_from<I, T>(command: Command, args: I) -> Result<ArgMatches, ::clap::Error>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    matches_from(command.clone(), args).map_err(|failure| match failure {
+        Failure::Invalid(error) => error,
+        Failure::Aborted(AbortReason::Interrupt) => interrupt_error(command),
+        Failure::Aborted(AbortReason::Error(error)) => prompt_error(command, error),
+    })
+}
+
+fn matches_from<I, T>(command: Command, args: I) -> Result<ArgMatches, Failure>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<OsString> + Clone,
+{
+    let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+    let error = match command.clone().try_get_matches_from(&args) {
+        Ok(matches) => return Ok(matches),
+        Err(error) => error,
+    };
+
+    let is_interactive = stdin().is_terminal() && stderr().is_terminal();
+    if error.kind() != ErrorKind::MissingRequiredArgument || !is_interactive {
+        return Err(Failure::Invalid(error));
+    }
+
+    let args = fill_missing_arguments(&command, args, ask_for_value).map_err(Failure::Aborted)?;
+    command.try_get_matches_from(args).map_err(Failure::Invalid)
+}
+
+fn interrupt_error(mut command: Command) -> ::clap::Error {
+    command.error(ErrorKind::MissingRequiredArgument, INTERRUPTED_MESSAGE)
+}
+
+fn prompt_error(mut command: Command, error: std::io::Error) -> ::clap::Error {
+    command.error(ErrorKind::Io, error)
+}
+
+/// Parses the arguments of the process into the type that derives `clap::Parser`, asking for
+/// the missing required arguments. Exits the process on error, like `Parser::parse`
+pub fn parse<P: Parser>() -> P {
+    let mut command = P::command();
+    let mut matches = get_matches(command.clone());
+    P::from_arg_matches_mut(&mut matches).unwrap_or_else(|e| e.format(&mut command).exit())
+}
+
+/// Adds the values of the missing required arguments to the command line.
+/// Each missing argument is passed to `ask` which returns its values
+fn fill_missing_arguments<F>(
+    command: &Command,
+    mut args: Vec<OsString>,
+    mut ask: F,
+) -> Result<Vec<OsString>, AbortReason>
+where
+    F: FnMut(&Arg) -> Result<Vec<String>, AbortReason>,
+{
+    let mut command = command.clone();
+    command.build();
+
+    let Ok(matches) = relaxed(command.clone()).try_get_matches_from(&args) else {
+        return Ok(args);
+    };
+
+    // Each matched subcommand is a level with the index of the argument it starts at
+    let mut levels = vec![(&command, &matches, 0)];
+    while let Some(&(level_command, level_matches, start)) = levels.last() {
+        let Some((name, subcommand_matches)) = level_matches.subcommand() else {
+            break;
+        };
+        let Some(subcommand) = level_command.find_subcommand(name) else {
+            break;
+        };
+        let index = args
+            .iter()
+            .skip(start + 1)
+            .position(|a| {
+                a.to_str()
+                    .is_some_and(|a| is_subcommand_name(subcommand, a))
+            })
+            .map_or(start, |i| start + 1 + i);
+        levels.push((subcommand, subcommand_matches, index));
+    }
+
+    let mut insertions = vec![];
+    for (depth, &(level_command, level_matches, start)) in levels.iter().enumerate() {
+        let mut named = vec![];
+        let mut positional = vec![];
+        let missing_arguments = level_command
+            .get_arguments()
+            .filter(|a| a.is_required_set() && !level_matches.contains_id(a.get_id().as_str()));
+        for arg in missing_arguments {
+            let values = ask(arg)?;
+            if arg.is_positional() {
+                positional.extend(values.into_iter().map(OsString::from));
+            } else {
+                named.extend(named_argument(arg, values));
+            }
+        }
+
+        let is_deepest = depth + 1 == levels.len();
+        if is_deepest && !positional.is_empty() {
+            if !args.iter().any(|a| a == "--") {
+                args.push("--".into());
+            }
+            args.append(&mut positional);
+        }
+        named.append(&mut positional);
+        insertions.push((start + 1, named));
+    }
+
+    for (index, values) in insertions.into_iter().rev() {
+        args.splice(index..index, values);
+    }
+
+    Ok(args)
+}
+
+/// Copy of the command that doesn't fail on the missing arguments
+fn relaxed(command: Command) -> Command {
+    command
+        .mut_args(|a| a.required(false))
+        .arg_required_else_help(false)
+        .mut_subcommands(relaxed)
+}
+
+fn is_subcommand_name(command: &Command, name: &str) -> bool {
+    command.get_name() == name || command.get_all_aliases().any(|alias| alias == name)
+}
+
+fn named_argument(arg: &Arg, values: Vec<String>) -> Vec<OsString> {
+    let takes_values = arg.get_action().takes_values();
+    let mut result = vec![];
+    for value in values {
+        let flag = match (arg.get_long(), arg.get_short()) {
+            (Some(long), _) if takes_values => format!("--{}={}", long, value),
+            (Some(long), _) => format!("--{}", long),
+            (None, Some(short)) => format!("-{}", short),
+            (None, None) => continue,
+        };
+        result.push(flag.into());
+        if takes_values && arg.get_long().is_none() {
+            result.push(value.into());
+        }
+    }
+    result
+}
+
+fn ask_for_value(arg: &Arg) -> Result<Vec<String>, AbortReason> {
+    let label = arg.get_id().as_str().replace(['_', '-'], " ");
+    let help = arg.get_help().map(|h| h.to_string());
+
+    if !arg.get_action().takes_values() {
+        let mut prompt = Confirmation::new(label);
+        if let Some(help) = help {
+            prompt = prompt.help_message(help);
+        }
+        let is_set = prompt.display_on(stderr())?;
+        return Ok(if is_set { vec![String::new()] } else { vec![] });
+    }
+
+    let accepts_many = arg.get_num_args().is_some_and(|n| n.max_values() > 1)
+        || matches!(arg.get_action(), ::clap::ArgAction::Append);
+    let possible_values: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|v| !v.is_hide_set())
+        .map(|v| v.get_name().to_owned())
+        .collect();
+
+    if !possible_values.is_empty() {
+        if accepts_many {
+            let mut prompt = Multiselect::new(label, possible_values.into_iter());
+            if let Some(help) = help {
+                prompt = prompt.help_message(help);
+            }
+            return prompt.display_on(stderr());
+        }
+        let mut prompt = Selection::new(label, possible_values.into_iter());
+        if let Some(help) = help {
+            prompt = prompt.help_message(help);
+        }
+        return prompt.display_on(stderr()).map(|value| vec![value]);
+    }
+
+    let delimiter = arg.get_value_delimiter();
+    let validator = value_validator(arg);
+    let mut prompt = Input::new(label, move |text: &str| {
+        let values: Vec<String> = match delimiter {
+            Some(delimiter) if accepts_many => text.split(delimiter).map(str::to_owned).collect(),
+            _ if accepts_many => text.split_whitespace().map(str::to_owned).collect(),
+            _ => vec![text.to_owned()],
+        };
+        values.iter().try_for_each(|v| validator(v)).map(|_| values)
+    });
+    if let Some(help) = help {
+        prompt = prompt.help_message(help);
+    }
+    prompt.display_on(stderr())
+}
+
+/// Checks the value with the argument's value parser
+fn value_validator(arg: &Arg) -> impl Fn(&str) -> Result<(), String> {
+    let validation_command = Command::new("value").no_binary_name(true).arg(
+        Arg::new(arg.get_id().clone())
+            .value_parser(arg.get_value_parser().clone())
+            .allow_hyphen_values(true),
+    );
+
+    move |value: &str| {
+        validation_command
+            .clone()
+            .try_get_matches_from([value])
+            .map(|_| ())
+            .map_err(|e| {
+                let message = e.to_string();
+                let first_line = message.lines().next().unwrap_or_default();
+                first_line.trim_start_matches("error: ").to_owned()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::clap::ArgAction;
+
+    fn command() -> Command {
+        Command::new("app")
+            .arg(
+                Arg::new("verbose")
+                    .long("verbose")
+                    .action(ArgAction::SetTrue),
+            )
+            .arg(Arg::new("config").long("config").required(true))
+            .subcommand(
+                Command::new("deploy")
+                    .arg(Arg::new("env").short('e').required(true))
+                    .arg(Arg::new("version").required(true))
+                    .arg(Arg::new("tags").num_args(1..)),
+            )
+    }
+
+    fn fill(args: &[&str]) -> ArgMatches {
+        let args = args.iter().map(OsString::from).collect();
+        let args = fill_missing_arguments(&command(), args, |arg| {
+            Ok(vec![format!("{}-value", arg.get_id())])
+        })
+        .unwrap();
+        command().try_get_matches_from(args).unwrap()
+    }
+
+    #[test]
+    fn fills_missing_arguments_of_all_levels() {
+        let matches = fill(&["app", "--verbose", "deploy"]);
+        assert_eq!(matches.get_one::<String>("config").unwrap(), "config-value");
+
+        let deploy = matches.subcommand_matches("deploy").unwrap();
+        assert_eq!(deploy.get_one::<String>("env").unwrap(), "env-value");
+        assert_eq!(
+            deploy.get_one::<String>("version").unwrap(),
+            "version-value"
+        );
+    }
+
+    #[test]
+    fn keeps_given_arguments() {
+        let matches = fill(&[
+            "app", "--config", "c.toml", "deploy", "-e", "prod", "--", "1.0",
+        ]);
+        assert_eq!(matches.get_one::<String>("config").unwrap(), "c.toml");
+
+        let deploy = matches.subcommand_matches("deploy").unwrap();
+        assert_eq!(deploy.get_one::<String>("env").unwrap(), "prod");
+        assert_eq!(deploy.get_one::<String>("version").unwrap(), "1.0");
+    }
+
+    #[test]
+    fn maps_prompt_errors_to_clap_errors() {
+        let error = interrupt_error(command());
+        assert_eq!(error.kind(), ErrorKind::MissingRequiredArgument);
+        assert!(error.to_string().contains(INTERRUPTED_MESSAGE));
+
+        let io_error = std::io::Error::new(std::io::ErrorKind::BrokenPipe, "terminal is closed");
+        let error = prompt_error(command(), io_error);
+        assert_eq!(error.kind(), ErrorKind::Io);
+        assert!(error.to_string().contains("terminal is closed"));
+    }
+
+    #[test]
+    fn validates_values_with_value_parser() {
+        let command =
+            Command::new("app").arg(Arg::new("port").value_parser(::clap::value_parser!(u16)));
+        let validator = value_validator(command.get_arguments().next().unwrap());
+
+        assert!(validator("8080").is_ok());
+        assert!(validator("port")
+            .unwrap_err()
+            .contains("invalid value 'port'"));
+    }
+}
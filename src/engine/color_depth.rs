@@ -0,0 +1,99 @@
+/// Number of colors the terminal can display. The colors of the prompts are converted to the
+/// nearest supported ones before they are rendered
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ColorDepth {
+    /// No colors at all. Only the text attributes, like bold and underline, are used
+    Monochrome,
+
+    /// The basic 16 colors
+    Ansi16,
+
+    /// The 256 colors of the xterm palette
+    Ansi256,
+
+    /// 24-bit RGB colors
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detects the color depth of the terminal from the environment variables:
+    /// - `NO_COLOR` disables the colors;
+    /// - `CLICOLOR_FORCE` enables the colors, `CLICOLOR=0` disables them;
+    /// - `COLORTERM` and `TERM` tell whether 24-bit or 256 colors are supported.
+    pub fn detect() -> Self {
+        Self::detect_from(|name| std::env::var(name).ok())
+    }
+
+    /// Same as `detect`, but reads the variables with the given function
+    pub fn detect_from(env: impl Fn(&str) -> Option<String>) -> Self {
+        let is_set = |name| env(name).is_some_and(|value| !value.is_empty());
+        let is_enabled = |name| env(name).is_some_and(|value| !value.is_empty() && value != "0");
+
+        if is_set("NO_COLOR") {
+            return ColorDepth::Monochrome;
+        }
+
+        let term = env("TERM").unwrap_or_default().to_lowercase();
+        let is_forced = is_enabled("CLICOLOR_FORCE");
+        if !is_forced && (env("CLICOLOR").as_deref() == Some("0") || term == "dumb") {
+            return ColorDepth::Monochrome;
+        }
+
+        let color_term = env("COLORTERM").unwrap_or_default().to_lowercase();
+        if color_term == "truecolor"
+            || color_term == "24bit"
+            || ["truecolor", "24bit", "direct"]
+                .iter()
+                .any(|name| term.contains(name))
+            || is_set("WT_SESSION")
+        {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(variables: &[(&str, &str)]) -> ColorDepth {
+        ColorDepth::detect_from(|name| {
+            variables
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn detects_depth_from_term_variables() {
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorDepth::Ansi256);
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+            ColorDepth::TrueColor
+        );
+        assert_eq!(detect(&[("TERM", "xterm")]), ColorDepth::Ansi16);
+        assert_eq!(detect(&[("TERM", "dumb")]), ColorDepth::Monochrome);
+    }
+
+    #[test]
+    fn honours_color_switches() {
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")]),
+            ColorDepth::Monochrome
+        );
+        assert_eq!(detect(&[("CLICOLOR", "0")]), ColorDepth::Monochrome);
+        assert_eq!(
+            detect(&[("TERM", "dumb"), ("CLICOLOR_FORCE", "1")]),
+            ColorDepth::Ansi16
+        );
+        assert_eq!(
+            detect(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")]),
+            ColorDepth::Monochrome
+        );
+    }
+}
@@ -1,71 +1,141 @@
-use std::io::{Result, Write};
+use std::{
+    io::{Result, Write},
+    marker::PhantomData,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
 
 use crossterm::{
-    cursor::{position, MoveTo, MoveToPreviousLine},
-    event::{read, Event, KeyEvent, KeyModifiers},
-    execute, queue,
+    cursor::MoveToPreviousLine,
+    event::{poll, read, Event, KeyEvent, KeyModifiers},
+    queue,
     style::{Attribute, Attributes, Color as Cc, Colors, Print, SetAttributes, SetColors},
-    terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, Clear, ClearType},
+    terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled, size, Clear, ClearType},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     input::Key,
     style::{Color, Formatting, FormattingOption}
 };
 
-use super::{CommandBuffer, Engine};
+use super::{ColorDepth, CommandBuffer, Engine, DEFAULT_TERMINAL_WIDTH};
 
 struct RawMode(bool);
 
+/// Rendered frame of the prompt
+type Frame = Vec<u8>;
+
 /// Terminal handing backend implemented with the [crossterm](https://docs.rs/crossterm/latest/crossterm/) crate
 pub struct CrosstermEngine<W: Write> {
-    buffer: W,
+    output: Arc<Mutex<Output<W>>>,
     raw_mode: RawMode,
-    previous_line_count: u16,
+    color_depth: ColorDepth,
+    is_transient: bool,
+}
+
+/// Handle that prints lines above the prompt displayed by the `CrosstermEngine` without
+/// corrupting it. It can be cloned and sent to other threads, e.g. to report the progress of
+/// the background work while the user is answering the prompt:
+///
+/// ```rust,no_run
+/// use std::{io::stdout, thread};
+/// use cli_prompts::{engine::CrosstermEngine, prompts::{DisplayPrompt, Selection}};
+///
+/// let engine = CrosstermEngine::new(stdout());
+/// let printer = engine.printer();
+/// thread::spawn(move || {
+///     for i in 1..=3 {
+///         printer.println(format!("Downloaded {} of 3", i)).unwrap();
+///     }
+/// });
+///
+/// let answer = Selection::new("Mirror", ["eu", "us"].into_iter()).display_with(engine);
+/// ```
+pub struct PromptPrinter<W: Write> {
+    output: Arc<Mutex<Output<W>>>,
+}
+
+/// Output of the engine that is shared with the printers
+struct Output<W: Write> {
+    buffer: W,
+    frame: Option<Frame>,
+    rows_count: u16,
 }
 
 /// Command buffer for the `CrosstermEngine`
 pub struct CrosstermCommandBuffer<W: Write> {
-    commands: Vec<Box<dyn Command<W>>>,
-    lines_count: u16,
+    commands: Vec<Box<dyn Command<Frame>>>,
+    finished_rows_count: u16,
+    line_width: usize,
+    terminal_width: u16,
+    color_depth: ColorDepth,
+    output: PhantomData<fn() -> W>,
 }
 
 impl<W: Write> CrosstermEngine<W> {
+    /// Creates the engine that draws to the given buffer. The color depth of the terminal is
+    /// detected from the environment variables
     pub fn new(buffer: W) -> Self {
         CrosstermEngine {
-            buffer,
+            output: Arc::new(Mutex::new(Output {
+                buffer,
+                frame: None,
+                rows_count: 1,
+            })),
             raw_mode: RawMode::ensure(),
-            previous_line_count: 1,
+            color_depth: ColorDepth::detect(),
+            is_transient: false,
         }
     }
+
+    /// Overrides the detected color depth of the terminal
+    pub fn color_depth(mut self, depth: ColorDepth) -> Self {
+        self.color_depth = depth;
+        self
+    }
+
+    /// Makes the engine erase the prompt when it is submitted or aborted instead of leaving its
+    /// final state on the screen. The cursor is put back where the prompt started, so a summary
+    /// can be printed in its place
+    pub fn transient(mut self, is_transient: bool) -> Self {
+        self.is_transient = is_transient;
+        self
+    }
+
+    /// Returns the handle that prints lines above the prompt displayed by this engine
+    pub fn printer(&self) -> PromptPrinter<W> {
+        PromptPrinter {
+            output: Arc::clone(&self.output),
+        }
+    }
+
+    fn output(&self) -> MutexGuard<'_, Output<W>> {
+        lock(&self.output)
+    }
 }
 
 impl<W: Write> Engine for CrosstermEngine<W> {
     type Buffer = CrosstermCommandBuffer<W>;
 
     fn get_command_buffer(&self) -> Self::Buffer {
-        CrosstermCommandBuffer::new()
+        CrosstermCommandBuffer::new(self.color_depth)
     }
 
     fn render(&mut self, render_commands: &Self::Buffer) -> Result<()> {
-        for _ in 0..self.previous_line_count - 1 {
-            queue!(self.buffer, MoveToPreviousLine(1))?;
-        }
-
-        queue!(self.buffer, MoveTo(0, position()?.1))?;
-
+        let mut frame = Frame::new();
         for cmd in &render_commands.commands {
-            cmd.execute(&mut self.buffer)?;
+            cmd.execute(&mut frame)?;
         }
 
-        queue!(self.buffer, Clear(ClearType::FromCursorDown))?;
+        queue!(frame, Clear(ClearType::FromCursorDown))?;
 
-        self.previous_line_count = render_commands.lines_count;
-        self.buffer.flush()
+        self.output().draw(frame, render_commands.rows_count())
     }
 
     fn finish_rendering(&mut self) -> Result<()> {
-        execute!(self.buffer, Print("\r\n"))
+        let is_transient = self.is_transient;
+        self.output().finish(is_transient)
     }
 
     fn read_key(&self) -> Result<Key> {
@@ -82,45 +152,161 @@ impl<W: Write> Engine for CrosstermEngine<W> {
             }
         }
     }
+
+    fn poll_key(&self, timeout: Duration) -> Result<Option<Key>> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if !poll(deadline.saturating_duration_since(Instant::now()))? {
+                return Ok(None);
+            }
+            if let Event::Key(key) = read()? {
+                return Ok(Some(key.into()));
+            }
+        }
+    }
+}
+
+impl<W: Write> PromptPrinter<W> {
+    /// Prints the line above the prompt. The prompt is redrawn below the line. If no prompt is
+    /// displayed at the moment, the line is just printed
+    pub fn println(&self, line: impl AsRef<str>) -> Result<()> {
+        lock(&self.output).print_line(line.as_ref())
+    }
+}
+
+impl<W: Write> Clone for PromptPrinter<W> {
+    fn clone(&self) -> Self {
+        PromptPrinter {
+            output: Arc::clone(&self.output),
+        }
+    }
+}
+
+impl<W: Write> Output<W> {
+    /// Replaces the frame on the screen with the new one
+    fn draw(&mut self, frame: Frame, rows_count: u16) -> Result<()> {
+        self.move_to_frame_start()?;
+        self.buffer.write_all(&frame)?;
+        self.frame = Some(frame);
+        self.rows_count = rows_count;
+        self.buffer.flush()
+    }
+
+    /// Leaves the last frame on the screen or erases it, so the following output is printed
+    /// below or in place of the frame
+    fn finish(&mut self, erase: bool) -> Result<()> {
+        if erase {
+            self.move_to_frame_start()?;
+            queue!(self.buffer, Clear(ClearType::FromCursorDown))?;
+        } else {
+            queue!(self.buffer, Print("\r\n"))?;
+        }
+
+        self.frame = None;
+        self.rows_count = 1;
+        self.buffer.flush()
+    }
+
+    fn print_line(&mut self, line: &str) -> Result<()> {
+        if self.frame.is_some() {
+            self.move_to_frame_start()?;
+            queue!(self.buffer, Clear(ClearType::FromCursorDown))?;
+        }
+
+        for line in line.split('\n') {
+            queue!(self.buffer, Print(line), Print("\r\n"))?;
+        }
+
+        if let Some(frame) = self.frame.as_ref() {
+            self.buffer.write_all(frame)?;
+        }
+        self.buffer.flush()
+    }
+
+    /// Moves the cursor to the first row of the frame on the screen
+    fn move_to_frame_start(&mut self) -> Result<()> {
+        for _ in 0..self.rows_count - 1 {
+            queue!(self.buffer, MoveToPreviousLine(1))?;
+        }
+
+        queue!(self.buffer, Print("\r"))
+    }
+}
+
+/// Locks the output even if another thread panicked while holding it
+fn lock<W: Write>(output: &Mutex<Output<W>>) -> MutexGuard<'_, Output<W>> {
+    output.lock().unwrap_or_else(|e| e.into_inner())
 }
 
 impl<W: Write> CrosstermCommandBuffer<W> {
-    fn new() -> Self {
+    fn new(color_depth: ColorDepth) -> Self {
         CrosstermCommandBuffer {
             commands: vec![],
-            lines_count: 1,
+            finished_rows_count: 0,
+            line_width: 0,
+            terminal_width: current_terminal_width(),
+            color_depth,
+            output: PhantomData,
         }
     }
+
+    /// Number of terminal rows the commands occupy, including the ones the long lines are
+    /// wrapped to
+    fn rows_count(&self) -> u16 {
+        self.finished_rows_count + self.rows_of_current_line()
+    }
+
+    fn rows_of_current_line(&self) -> u16 {
+        let width = usize::from(self.terminal_width.max(1));
+        self.line_width.div_ceil(width).max(1) as u16
+    }
 }
 
 impl<W: Write> CommandBuffer for CrosstermCommandBuffer<W> {
     fn new_line(&mut self) {
         self.commands.push(Box::new(NewLineCommand));
-        self.lines_count += 1;
+        self.finished_rows_count += self.rows_of_current_line();
+        self.line_width = 0;
     }
 
     fn print(&mut self, text: &str) {
         self.commands.push(Box::new(PrintCommand(text.to_owned())));
+        self.line_width += text.width();
     }
 
     fn set_formatting(&mut self, formatting: &Formatting) {
-        self.commands
-            .push(Box::new(SetFormattingCommand(formatting.to_owned())));
+        self.commands.push(Box::new(SetFormattingCommand(
+            formatting.downgrade(self.color_depth),
+        )));
     }
 
     fn reset_formatting(&mut self) {
         self.commands
             .push(Box::new(SetFormattingCommand(Formatting::reset())));
     }
+
+    fn terminal_width(&self) -> u16 {
+        self.terminal_width
+    }
 }
 
 impl<W: Write> super::Clear for CrosstermCommandBuffer<W> {
     fn clear(&mut self) {
         self.commands.clear();
-        self.lines_count = 1;
+        self.finished_rows_count = 0;
+        self.line_width = 0;
+        self.terminal_width = current_terminal_width();
     }
 }
 
+fn current_terminal_width() -> u16 {
+    size()
+        .map(|(columns, _)| columns)
+        .ok()
+        .filter(|&columns| columns > 0)
+        .unwrap_or(DEFAULT_TERMINAL_WIDTH)
+}
+
 impl RawMode {
     pub fn ensure() -> Self {
         let is_raw = is_raw_mode_enabled().unwrap_or(false);
@@ -172,7 +358,36 @@ impl<W: Write> Command<W> for SetFormattingCommand {
         let attributes_ref: &[Attribute] = &attributes_vec;
         let attributes: Attributes = attributes_ref.into();
 
-        queue!(buffer, SetColors(colors), SetAttributes(attributes))
+        queue!(buffer, SetColors(colors), SetAttributes(attributes))?;
+
+        // Crossterm doesn't support the underline styles and colors, so the escape sequences
+        // are written directly
+        for option in &self.0.text_formatting {
+            if let Some(style) = underline_style_code(*option) {
+                queue!(buffer, Print(format!("\x1b[4:{}m", style)))?;
+            }
+        }
+        if let Some(color) = self.0.underline_color {
+            let code = match color {
+                Color::Reset => "59".to_string(),
+                Color::Rgb { r, g, b } => format!("58;2;{};{};{}", r, g, b),
+                Color::AnsiValue(value) => format!("58;5;{}", value),
+                color => format!("58;5;{}", color.ansi_value().unwrap_or_default()),
+            };
+            queue!(buffer, Print(format!("\x1b[{}m", code)))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn underline_style_code(option: FormattingOption) -> Option<u8> {
+    match option {
+        FormattingOption::DoubleUnderline => Some(2),
+        FormattingOption::CurlyUnderline => Some(3),
+        FormattingOption::DottedUnderline => Some(4),
+        FormattingOption::DashedUnderline => Some(5),
+        _ => None,
     }
 }
 
@@ -184,6 +399,14 @@ impl From<FormattingOption> for crossterm::style::Attribute {
             FormattingOption::Italic => Attribute::Italic,
             FormattingOption::Underline => Attribute::Underlined,
             FormattingOption::CrossedOut => Attribute::CrossedOut,
+            FormattingOption::Dim => Attribute::Dim,
+            FormattingOption::Reverse => Attribute::Reverse,
+            FormattingOption::Blink => Attribute::SlowBlink,
+            FormattingOption::Hidden => Attribute::Hidden,
+            FormattingOption::DoubleUnderline
+            | FormattingOption::CurlyUnderline
+            | FormattingOption::DottedUnderline
+            | FormattingOption::DashedUnderline => Attribute::Underlined,
         }
     }
 }
@@ -215,31 +438,31 @@ impl From<crossterm::event::KeyCode> for Key {
 
 impl From<KeyEvent> for Key {
     fn from(key_event: KeyEvent) -> Self {
+        use crossterm::event::KeyCode;
+
         if key_event.modifiers.contains(KeyModifiers::CONTROL) {
-            if let crossterm::event::KeyCode::Char(c) = key_event.code {
+            if let KeyCode::Char(c) = key_event.code {
                 return Key::Ctrl(c);
             }
         }
 
-        key_event.code.into()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crossterm::event::KeyCode;
+        if key_event.modifiers.contains(KeyModifiers::ALT) {
+            if let KeyCode::Char(c) = key_event.code {
+                return Key::Alt(c);
+            }
+        }
 
-    #[test]
-    fn maps_ctrl_c_to_ctrl_key_variant() {
-        let key_event = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
-        assert_eq!(Key::from(key_event), Key::Ctrl('c'));
-    }
+        if key_event.modifiers.contains(KeyModifiers::SHIFT) {
+            match key_event.code {
+                KeyCode::Left => return Key::ShiftLeft,
+                KeyCode::Right => return Key::ShiftRight,
+                KeyCode::Up => return Key::ShiftUp,
+                KeyCode::Down => return Key::ShiftDown,
+                _ => {}
+            }
+        }
 
-    #[test]
-    fn keeps_esc_mapping_intact() {
-        let key_event = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
-        assert_eq!(Key::from(key_event), Key::Esc);
+        key_event.code.into()
     }
 }
 
@@ -268,3 +491,136 @@ impl From<Color> for Cc {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn maps_ctrl_c_to_ctrl_key_variant() {
+        let key_event = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(Key::from(key_event), Key::Ctrl('c'));
+    }
+
+    #[test]
+    fn maps_shift_arrows_and_alt_keys() {
+        let key_event = KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(Key::from(key_event), Key::ShiftUp);
+
+        let key_event = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT);
+        assert_eq!(Key::from(key_event), Key::Alt('x'));
+
+        let key_event = KeyEvent::new(KeyCode::Char('X'), KeyModifiers::SHIFT);
+        assert_eq!(Key::from(key_event), Key::Char('X'));
+    }
+
+    #[test]
+    fn writes_underline_styles_and_colors() {
+        let formatting = Formatting::default()
+            .curly_underline()
+            .underline_color(Color::Rgb { r: 255, g: 0, b: 0 });
+        let mut output = vec![];
+        SetFormattingCommand(formatting)
+            .execute(&mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.ends_with("\x1b[4:3m\x1b[58;2;255;0;0m"));
+    }
+
+    fn command_buffer(terminal_width: u16) -> CrosstermCommandBuffer<Vec<u8>> {
+        CrosstermCommandBuffer {
+            terminal_width,
+            ..CrosstermCommandBuffer::new(ColorDepth::TrueColor)
+        }
+    }
+
+    fn test_engine(is_transient: bool) -> CrosstermEngine<Vec<u8>> {
+        CrosstermEngine {
+            output: Arc::new(Mutex::new(Output {
+                buffer: vec![],
+                frame: None,
+                rows_count: 1,
+            })),
+            raw_mode: RawMode(true),
+            color_depth: ColorDepth::Monochrome,
+            is_transient,
+        }
+    }
+
+    fn take_output(engine: &CrosstermEngine<Vec<u8>>) -> String {
+        String::from_utf8(std::mem::take(&mut engine.output().buffer)).unwrap()
+    }
+
+    #[test]
+    fn counts_rows_of_wrapped_lines() {
+        let mut commands = command_buffer(10);
+        commands.print("Label: ");
+        commands.print("abc");
+        assert_eq!(commands.rows_count(), 1);
+
+        commands.print("d");
+        commands.new_line();
+        commands.print(&"x".repeat(25));
+        assert_eq!(commands.rows_count(), 5);
+
+        commands.new_line();
+        assert_eq!(commands.rows_count(), 6);
+    }
+
+    #[test]
+    fn counts_terminal_cells_of_wide_characters() {
+        let mut commands = command_buffer(10);
+        commands.print("日本語");
+        assert_eq!(commands.rows_count(), 1);
+
+        commands.print("テスト");
+        assert_eq!(commands.rows_count(), 2);
+    }
+
+    #[test]
+    fn erases_rendered_rows_when_transient() {
+        let mut engine = test_engine(true);
+        let mut commands = command_buffer(10);
+        commands.print(&"x".repeat(15));
+        commands.new_line();
+        commands.print("y");
+        engine.render(&commands).unwrap();
+        take_output(&engine);
+
+        engine.finish_rendering().unwrap();
+        assert_eq!(take_output(&engine), "\x1b[1F\x1b[1F\r\x1b[J");
+    }
+
+    #[test]
+    fn prints_lines_above_the_prompt() {
+        let mut engine = test_engine(false);
+        let printer = engine.printer();
+        let mut commands = command_buffer(10);
+        commands.print("a");
+        commands.new_line();
+        commands.print("b");
+        engine.render(&commands).unwrap();
+        let frame = take_output(&engine).replacen('\r', "", 1);
+
+        std::thread::spawn(move || printer.println("log").unwrap())
+            .join()
+            .unwrap();
+        assert_eq!(
+            take_output(&engine),
+            format!("\x1b[1F\r\x1b[Jlog\r\n{}", frame)
+        );
+
+        engine.finish_rendering().unwrap();
+        take_output(&engine);
+        engine.printer().println("done").unwrap();
+        assert_eq!(take_output(&engine), "done\r\n");
+    }
+
+    #[test]
+    fn keeps_esc_mapping_intact() {
+        let key_event = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        assert_eq!(Key::from(key_event), Key::Esc);
+    }
+}
@@ -2,18 +2,29 @@
 //!
 //! It consists of the multiple traits:
 //! - `Engine` trait, that represents the backend which draws content on the
-//! screen and handles the input;
+//!   screen and handles the input;
 //! - `CommandBuffer` trait that represents the set of rendering commands to display
-//! the given prompt.
+//!   the given prompt.
 //! - `Clear` trait that is complemetary to the `CommandBuffer` and allows to clear its contents
 //!
+//! `RecordingBuffer` is a `CommandBuffer` that stores the commands to replay them later.
+//! `ColorDepth` describes how many colors the terminal supports.
+//! `PromptPrinter` prints lines above the prompt that is being displayed
+//!
 //! Submodules are meant to implement the above traits using terminal manipulation libraries
+mod color_depth;
 mod crossterm;
+mod recording;
 
-pub use self::crossterm::CrosstermEngine;
+pub use self::color_depth::ColorDepth;
+pub use self::crossterm::{CrosstermEngine, PromptPrinter};
+pub use self::recording::RecordingBuffer;
 
 use crate::{input::Key, style::Formatting};
-use std::io::Result;
+use std::{io::Result, time::Duration};
+
+/// Terminal width that is assumed when the actual one is unknown
+pub const DEFAULT_TERMINAL_WIDTH: u16 = 80;
 
 /// Represents the backend to draw prompts on the screen and handle input
 pub trait Engine {
@@ -27,11 +38,19 @@ pub trait Engine {
     /// Renders content to the terminal using the specified rendering commands
     fn render(&mut self, render_commands: &Self::Buffer) -> Result<()>;
 
-    /// This is called when a prompt is submitted and needs to be rendered in its final state.
+    /// This is called when a prompt is submitted or aborted and needs to be rendered in its final
+    /// state.
     fn finish_rendering(&mut self) -> Result<()>;
 
     /// Reads a key that was pressed. This is a blocking call
     fn read_key(&self) -> Result<Key>;
+
+    /// Waits for a key to be pressed for at most the given time.
+    /// Returns `None` if no key was pressed in time. The default implementation doesn't support
+    /// ticking: it blocks until a key is pressed, so the animated prompts only redraw on keys
+    fn poll_key(&self, _timeout: Duration) -> Result<Option<Key>> {
+        self.read_key().map(Some)
+    }
 }
 
 /// Suplementary trait to the `CommandBuffer`
@@ -55,4 +74,9 @@ pub trait CommandBuffer {
 
     /// Resets the previously set formatting to default
     fn reset_formatting(&mut self);
+
+    /// Width of the terminal in columns. Prompts can use it to lay out their content
+    fn terminal_width(&self) -> u16 {
+        DEFAULT_TERMINAL_WIDTH
+    }
 }
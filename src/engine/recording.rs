@@ -0,0 +1,118 @@
+use crate::style::Formatting;
+
+use super::{Clear, CommandBuffer, DEFAULT_TERMINAL_WIDTH};
+
+/// Command buffer that records the rendering commands instead of executing them.
+/// The recorded commands can be replayed into any other command buffer later, which allows to
+/// compose several prompts into one.
+#[derive(Clone)]
+pub struct RecordingBuffer {
+    commands: Vec<RecordedCommand>,
+    terminal_width: u16,
+}
+
+#[derive(Clone)]
+enum RecordedCommand {
+    NewLine,
+    Print(String),
+    SetFormatting(Formatting),
+    ResetFormatting,
+}
+
+impl RecordingBuffer {
+    /// Creates an empty buffer that reports the given terminal width to the prompts
+    pub fn new(terminal_width: u16) -> Self {
+        RecordingBuffer {
+            commands: vec![],
+            terminal_width,
+        }
+    }
+
+    /// Executes the recorded commands on the given command buffer
+    pub fn replay(&self, commands: &mut impl CommandBuffer) {
+        for command in &self.commands {
+            match command {
+                RecordedCommand::NewLine => commands.new_line(),
+                RecordedCommand::Print(text) => commands.print(text),
+                RecordedCommand::SetFormatting(formatting) => commands.set_formatting(formatting),
+                RecordedCommand::ResetFormatting => commands.reset_formatting(),
+            }
+        }
+    }
+
+    /// Returns `true` if nothing has been recorded
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Returns the recorded text without the formatting
+    pub fn text(&self) -> String {
+        self.commands
+            .iter()
+            .map(|command| match command {
+                RecordedCommand::NewLine => "\n",
+                RecordedCommand::Print(text) => text.as_str(),
+                _ => "",
+            })
+            .collect()
+    }
+}
+
+impl Default for RecordingBuffer {
+    fn default() -> Self {
+        RecordingBuffer::new(DEFAULT_TERMINAL_WIDTH)
+    }
+}
+
+impl CommandBuffer for RecordingBuffer {
+    fn new_line(&mut self) {
+        self.commands.push(RecordedCommand::NewLine);
+    }
+
+    fn print(&mut self, text: &str) {
+        self.commands.push(RecordedCommand::Print(text.to_owned()));
+    }
+
+    fn set_formatting(&mut self, formatting: &Formatting) {
+        self.commands
+            .push(RecordedCommand::SetFormatting(formatting.clone()));
+    }
+
+    fn reset_formatting(&mut self) {
+        self.commands.push(RecordedCommand::ResetFormatting);
+    }
+
+    fn terminal_width(&self) -> u16 {
+        self.terminal_width
+    }
+}
+
+impl Clear for RecordingBuffer {
+    fn clear(&mut self) {
+        self.commands.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_recorded_commands() {
+        let mut recorded = RecordingBuffer::new(40);
+        recorded.set_formatting(&Formatting::default().bold());
+        recorded.print("first");
+        recorded.reset_formatting();
+        recorded.new_line();
+        recorded.print("second");
+
+        let mut target = RecordingBuffer::default();
+        recorded.replay(&mut target);
+
+        assert_eq!(target.text(), "first\nsecond");
+        assert_eq!(recorded.terminal_width(), 40);
+
+        recorded.clear();
+        assert!(recorded.is_empty());
+    }
+}
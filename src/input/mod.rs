@@ -1,7 +1,7 @@
 //! Module for handling input
 
 /// Represents different keyboard keys
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Key {
     /// Backspace key
     Backspace,
@@ -21,6 +21,18 @@ pub enum Key {
     /// Down arrow key
     Down,
 
+    /// Left arrow key pressed with Shift
+    ShiftLeft,
+
+    /// Right arrow key pressed with Shift
+    ShiftRight,
+
+    /// Up arrow key pressed with Shift
+    ShiftUp,
+
+    /// Down arrow key pressed with Shift
+    ShiftDown,
+
     /// Home key
     Home,
     
@@ -54,6 +66,9 @@ pub enum Key {
     /// A character key pressed with Ctrl
     Ctrl(char),
 
+    /// A character key pressed with Alt
+    Alt(char),
+
     /// Esc key
     Esc,
 }
@@ -7,13 +7,33 @@
 //! ## Features
 //! 
 //! - Cross-platform;
-//! - Only one dependency - [crossterm](https://github.com/crossterm-rs/crossterm);
-//! - 4 prompts out of the box:
+//! - Only one required dependency - [crossterm](https://github.com/crossterm-rs/crossterm);
+//! - Prompts out of the box:
 //!   - Normal input field. Supports input validation and default values;
+//!   - Password input that masks the typed text;
 //!   - Confirmation y/n;
 //!   - Selection from the list of options;
-//!   - Multiselection.
-//! - Customization of the colors and text style of the prompts;
+//!   - Multiselection;
+//!   - Expandable choice with a hotkey for each option;
+//!   - Selection and multiselection from a tree with collapsible nodes;
+//!   - Selection and multiselection from a table with sortable columns;
+//!   - Reordering of the list of options, optionally ranking only the top ones;
+//!   - List builder that asks the same prompt repeatedly and lets the user edit the collected items;
+//!   - Multi-step forms with back navigation, a review screen and steps that depend on the earlier answers.
+//! - Questionnaires loaded from JSON, TOML or YAML files with the `serde` feature;
+//! - Themes loaded from TOML or JSON files and the user's config directory with the `serde` feature;
+//! - `cli-prompts` binary that brings the prompts to shell scripts with the `cli` feature;
+//! - `#[derive(Promptable)]` that asks for a whole struct with one form, with the `derive` feature;
+//! - Interactive input of the missing required [clap](https://github.com/clap-rs/clap) arguments with the `clap` feature;
+//! - Customization of the colors and text style of the prompts, including dim, reverse video, blinking text and styled, colored underlines;
+//! - Inline markup like `[bold red]production[/]` in the labels and help messages, and optionally in the options;
+//! - Label prefixes that change when the prompt is submitted, aborted or has an invalid input, a custom label suffix and the input on a separate line;
+//! - Aborted prompts are left on the screen in the final "cancelled" state;
+//! - Transient prompts that are erased from the screen once they are completed;
+//! - Printing log lines above the active prompt from other threads;
+//! - Spinner and progress bar that are driven from a worker thread and finish with a success or failure line;
+//! - Themes that restyle all prompts at once, with the default, minimal ASCII, high-contrast and colorblind-safe presets;
+//! - Colors are converted to the ones the terminal supports, `NO_COLOR` and `CLICOLOR` are honoured;
 //! - Set of traits and helper structs that allows to implement custom prompts for your application;
 //! 
 //! ## Getting started
@@ -54,5 +74,14 @@ pub mod engine;
 pub mod prompts;
 pub mod style;
 pub mod input;
+#[cfg(feature = "clap")]
+pub mod clap;
+pub mod promptable;
+#[cfg(feature = "serde")]
+pub mod questionnaire;
 
 pub use prompts::DisplayPrompt;
+pub use promptable::Promptable;
+
+#[cfg(feature = "derive")]
+pub use cli_prompts_derive::Promptable;
@@ -0,0 +1,339 @@
+//! Traits that allow to ask for a whole struct with one form.
+//!
+//! With the `derive` feature the traits can be implemented with `#[derive(Promptable)]`:
+//! - for a struct it implements `Promptable`. Every field is asked with a prompt that depends
+//!   on its type: `String`, `char` and numbers use `Input`, `bool` uses `Confirmation`, enums use
+//!   `Selection` and `Vec` of enums uses `Multiselect`. `Option` fields can be skipped;
+//! - for an enum without fields it implements `Choice` and `PromptField`, so it can be used as a
+//!   field type.
+//!
+//! The fields are configured with the `#[prompt(...)]` attribute:
+//! - `label = "..."` - text of the prompt. Defaults to the field name;
+//! - `help = "..."` - help message of the prompt;
+//! - `default = ...` - default value: a literal for the input, `true` or `false` for the
+//!   confirmation, the variant name for the selection, comma separated names for the
+//!   multiselection;
+//! - `validator = path` - function `fn(&T) -> Result<(), String>` that validates the value.
+//!   Only the fields that are asked with `Input` can be validated, for other fields the
+//!   attribute doesn't compile;
+//! - `skip` - the field is not asked and is set to `Default::default()`.
+//!
+//! The enum variants accept `label = "..."` to change the displayed text.
+//!
+#![cfg_attr(feature = "derive", doc = "```rust,no_run")]
+#![cfg_attr(not(feature = "derive"), doc = "```rust,ignore")]
+//! use cli_prompts::Promptable;
+//!
+//! #[derive(Promptable, Clone)]
+//! enum Engine {
+//!     Postgres,
+//!     #[prompt(label = "MySQL")]
+//!     Mysql,
+//! }
+//!
+//! #[derive(Promptable)]
+//! struct DatabaseConfig {
+//!     #[prompt(label = "Database name", validator = not_empty)]
+//!     name: String,
+//!     engine: Engine,
+//!     #[prompt(default = 5432)]
+//!     port: u16,
+//!     #[prompt(help = "Leave empty to use the system user")]
+//!     user: Option<String>,
+//!     create_if_missing: bool,
+//! }
+//!
+//! #[allow(clippy::ptr_arg)]
+//! fn not_empty(name: &String) -> Result<(), String> {
+//!     if name.is_empty() { Err("Name must not be empty".into()) } else { Ok(()) }
+//! }
+//!
+//! let config = DatabaseConfig::prompt();
+//! ```
+//!
+//! The validator of a field that isn't asked with `Input` is rejected:
+//!
+#![cfg_attr(feature = "derive", doc = "```rust,compile_fail")]
+#![cfg_attr(not(feature = "derive"), doc = "```rust,ignore")]
+//! use cli_prompts::Promptable;
+//!
+//! #[derive(Promptable)]
+//! struct Settings {
+//!     #[prompt(validator = always_valid)]
+//!     verbose: bool,
+//! }
+//!
+//! fn always_valid(_: &bool) -> Result<(), String> {
+//!     Ok(())
+//! }
+//! ```
+
+use std::{rc::Rc, str::FromStr};
+
+use crate::{
+    prompts::{AbortReason, Answers, Confirmation, Form, Input, Multiselect, Selection},
+    DisplayPrompt,
+};
+
+/// Type that can be created by asking the user with a form
+pub trait Promptable: Sized {
+    /// Creates the form that asks for all the fields
+    fn form() -> Form;
+
+    /// Creates the value from the answers of the form returned by `form`.
+    /// Returns `None` if some of the answers are missing
+    fn from_answers(answers: &mut Answers) -> Option<Self>;
+
+    /// Displays the form and creates the value from the answers
+    fn prompt() -> Result<Self, AbortReason> {
+        let mut answers = Self::form().display()?;
+        Ok(Self::from_answers(&mut answers).expect("All the fields are answered by the form"))
+    }
+}
+
+/// Type that can be asked as a field of a `Promptable` struct
+pub trait PromptField: Sized + 'static {
+    /// Adds the steps that ask for the value to the form
+    fn add_steps(form: Form, key: &str, settings: FieldSettings<Self>) -> Form;
+
+    /// Takes the value from the answers
+    fn from_answer(answers: &mut Answers, key: &str) -> Option<Self> {
+        answers.remove::<Self>(key)
+    }
+
+    /// Adds the steps that ask for the optional value. By default the user is asked whether to
+    /// set the value before asking for the value itself
+    fn add_optional_steps(form: Form, key: &str, settings: FieldSettings<Self>) -> Form {
+        let set_key = optional_key(key);
+        let label = format!("Set {}?", settings.label);
+        let form = form.step(&set_key, move || Confirmation::new(&label));
+        Self::add_steps(form, key, settings)
+            .when(move |answers| answers.get::<bool>(&set_key) == Some(&true))
+    }
+
+    /// Takes the optional value from the answers
+    fn from_optional_answer(answers: &mut Answers, key: &str) -> Option<Self> {
+        answers.remove::<bool>(&optional_key(key));
+        Self::from_answer(answers, key)
+    }
+}
+
+/// Type of the fields that are asked with the `Input` prompt, which can validate the value
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` fields don't support the `validator` attribute",
+    label = "only the fields that are asked with `Input` can be validated"
+)]
+pub trait InputField: PromptField {}
+
+/// Enum which variants can be selected from a list
+pub trait Choice: Clone + 'static {
+    /// All the variants in the order of the declaration
+    fn choices() -> Vec<Self>;
+
+    /// Name of the variant in the code
+    fn name(&self) -> &'static str;
+
+    /// Text that is displayed for the variant
+    fn label(&self) -> String;
+}
+
+/// Function that validates the value of a field
+pub type Validator<T> = fn(&T) -> Result<(), String>;
+
+/// Settings of the field from its `#[prompt(...)]` attribute
+pub struct FieldSettings<T> {
+    /// Text of the prompt
+    pub label: String,
+
+    /// Help message of the prompt
+    pub help: Option<String>,
+
+    /// Default value as it is written in the attribute
+    pub default: Option<String>,
+
+    /// Function that validates the value
+    pub validator: Option<Validator<T>>,
+}
+
+impl<T> FieldSettings<T> {
+    /// Creates settings with the given label
+    pub fn new(label: impl Into<String>) -> Self {
+        FieldSettings {
+            label: label.into(),
+            help: None,
+            default: None,
+            validator: None,
+        }
+    }
+
+    /// Sets the help message
+    pub fn help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Sets the default value
+    pub fn default_value(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+
+    /// Sets the validation function. Only the fields that are asked with `Input` support it
+    pub fn validator(mut self, validator: Validator<T>) -> Self
+    where
+        T: InputField,
+    {
+        self.validator = Some(validator);
+        self
+    }
+
+    fn validate(&self, value: &T) -> Result<(), String> {
+        self.validator.map_or(Ok(()), |validator| validator(value))
+    }
+}
+
+/// Adds a step that asks for the value with the `Input` prompt and parses it with `FromStr`
+pub fn add_input_steps<T>(form: Form, key: &str, settings: FieldSettings<T>) -> Form
+where
+    T: FromStr + 'static,
+{
+    let settings = Rc::new(settings);
+    form.step(key, move || {
+        let validation_settings = settings.clone();
+        let prompt = Input::new(&settings.label, move |text: &str| {
+            let value = text
+                .parse::<T>()
+                .map_err(|_| format!("'{}' is not a valid value", text))?;
+            validation_settings.validate(&value).map(|_| value)
+        });
+        with_input_settings(prompt, &settings)
+    })
+}
+
+/// Adds a step that asks for the optional value with the `Input` prompt. Empty input skips it
+pub fn add_optional_input_steps<T>(form: Form, key: &str, settings: FieldSettings<T>) -> Form
+where
+    T: FromStr + 'static,
+{
+    let settings = Rc::new(settings);
+    form.step(key, move || {
+        let validation_settings = settings.clone();
+        let prompt = Input::new(&settings.label, move |text: &str| {
+            if text.is_empty() {
+                return Ok(None);
+            }
+            let value = text
+                .parse::<T>()
+                .map_err(|_| format!("'{}' is not a valid value", text))?;
+            validation_settings.validate(&value).map(|_| Some(value))
+        });
+        with_input_settings(prompt, &settings)
+    })
+}
+
+/// Adds a step that asks to select one of the variants with the `Selection` prompt
+pub fn add_choice_steps<T: Choice>(form: Form, key: &str, settings: FieldSettings<T>) -> Form {
+    form.step(key, move || {
+        let prompt = Selection::new_with_transformation(
+            &settings.label,
+            T::choices().into_iter(),
+            |choice: &T| choice.label(),
+        );
+        let prompt = match settings.default.as_ref() {
+            Some(default) => prompt.default_option(|choice| is_choice_named(choice, default)),
+            None => prompt,
+        };
+        match settings.help.as_ref() {
+            Some(help) => prompt.help_message(help),
+            None => prompt,
+        }
+    })
+}
+
+fn with_input_settings<F, T, O>(mut prompt: Input<F>, settings: &FieldSettings<T>) -> Input<F>
+where
+    F: Fn(&str) -> Result<O, String>,
+{
+    if let Some(default) = settings.default.as_ref() {
+        prompt = prompt.default_value(default);
+    }
+    if let Some(help) = settings.help.as_ref() {
+        prompt = prompt.help_message(help);
+    }
+    prompt
+}
+
+fn is_choice_named<T: Choice>(choice: &T, name: &str) -> bool {
+    let name = name.trim();
+    choice.name() == name || choice.label() == name
+}
+
+fn optional_key(key: &str) -> String {
+    format!("{}?", key)
+}
+
+macro_rules! impl_input_field {
+    ($($t:ty),*) => {
+        $(
+            impl PromptField for $t {
+                fn add_steps(form: Form, key: &str, settings: FieldSettings<Self>) -> Form {
+                    add_input_steps(form, key, settings)
+                }
+
+                fn add_optional_steps(form: Form, key: &str, settings: FieldSettings<Self>) -> Form {
+                    add_optional_input_steps(form, key, settings)
+                }
+
+                fn from_optional_answer(answers: &mut Answers, key: &str) -> Option<Self> {
+                    answers.remove::<Option<Self>>(key).flatten()
+                }
+            }
+
+            impl InputField for $t {}
+        )*
+    };
+}
+
+impl_input_field!(
+    String, char, u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+
+impl PromptField for bool {
+    fn add_steps(form: Form, key: &str, settings: FieldSettings<Self>) -> Form {
+        let default = settings
+            .default
+            .as_deref()
+            .is_some_and(|d| matches!(d.trim(), "true" | "yes" | "y"));
+        form.step(key, move || {
+            let prompt = Confirmation::new(&settings.label).default_positive(default);
+            match settings.help.as_ref() {
+                Some(help) => prompt.help_message(help),
+                None => prompt,
+            }
+        })
+    }
+}
+
+impl<T: Choice> PromptField for Vec<T> {
+    fn add_steps(form: Form, key: &str, settings: FieldSettings<Self>) -> Form {
+        let settings = Rc::new(settings);
+        form.step(key, move || {
+            let defaults: Vec<String> = settings
+                .default
+                .as_deref()
+                .map(|d| d.split(',').map(|name| name.trim().to_owned()).collect())
+                .unwrap_or_default();
+            let mut prompt = Multiselect::new_transformed(
+                &settings.label,
+                T::choices().into_iter(),
+                |choice: &T| choice.label(),
+            )
+            .min_selections(0)
+            .preselect(|choice| defaults.iter().any(|d| is_choice_named(choice, d)));
+            if let Some(help) = settings.help.as_ref() {
+                prompt = prompt.help_message(help);
+            }
+            prompt
+        })
+    }
+}
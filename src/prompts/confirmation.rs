@@ -1,4 +1,9 @@
-use crate::{engine::CommandBuffer, input::Key, prompts::EventOutcome, style::ConfirmationStyle};
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::EventOutcome,
+    style::{ConfirmationStyle, PromptState},
+};
 
 use super::Prompt;
 
@@ -28,7 +33,9 @@ use super::Prompt;
 pub struct Confirmation {
     label: String,
     default_positive: bool,
+    help_message: Option<String>,
     is_submitted: bool,
+    is_aborted: bool,
     selected_option: Option<bool>,
     style: ConfirmationStyle,
 }
@@ -40,7 +47,9 @@ impl Confirmation {
         Confirmation {
             label: label.into(),
             default_positive: true,
+            help_message: None,
             is_submitted: false,
+            is_aborted: false,
             selected_option: None,
             style: ConfirmationStyle::default(),
         }
@@ -52,6 +61,12 @@ impl Confirmation {
         self
     }
 
+    /// Sets a help message which will be displayed after the input
+    pub fn help_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.help_message = Some(message.into());
+        self
+    }
+
     /// Sets the style of the prompt
     pub fn style(mut self, s: ConfirmationStyle) -> Self {
         self.style = s;
@@ -61,13 +76,18 @@ impl Confirmation {
 
 impl Prompt<bool> for Confirmation {
     fn draw(&self, commands: &mut impl CommandBuffer) {
-        self.style.label_style.print(
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        self.style.label_style.print_with_state(
             format!(
                 "{} [{y}/{n}]",
                 self.label,
                 y = if self.default_positive { 'Y' } else { 'y' },
                 n = if !self.default_positive { 'N' } else { 'n' },
             ),
+            PromptState::new(self.is_submitted, false),
             commands,
         );
 
@@ -87,7 +107,19 @@ impl Prompt<bool> for Confirmation {
             &self.style.input_formatting
         };
 
+        let has_result = !result.is_empty();
         formatting.print(result, commands);
+
+        if let Some(help_message) = self.help_message.as_ref().filter(|_| !self.is_submitted) {
+            if has_result {
+                commands.print(" ");
+            }
+            self.style.label_style.print_help_message(
+                help_message,
+                &self.style.help_message_formatting,
+                commands,
+            );
+        }
     }
 
     fn on_key_pressed(&mut self, key: Key) -> EventOutcome<bool> {
@@ -120,4 +152,8 @@ impl Prompt<bool> for Confirmation {
             _ => EventOutcome::Continue,
         }
     }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
 }
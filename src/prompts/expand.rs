@@ -0,0 +1,372 @@
+use std::fmt::Display;
+
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{AbortReason, EventOutcome, Prompt},
+    style::{ExpandStyle, PromptState},
+};
+
+const HELP_KEY: char = 'h';
+
+/// Prompt where each choice is picked with its own key, like
+/// `[y]es / [n]o / [a]ll / [d]iff / [q]uit / [h]elp`.
+/// Pressing `h` expands the prompt to the full list of choices with their descriptions.
+/// The `h` key is reserved for that purpose and can not be assigned to a choice.
+///
+/// The keys are matched regardless of their case, because the key of the default choice is
+/// drawn in upper case. So the keys of the choices must differ not only in case.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{Expand, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// enum Action {
+///     Overwrite,
+///     Skip,
+///     Diff,
+/// }
+///
+/// fn main() {
+///     let prompt = Expand::new("Conflict on config.toml")
+///                     .choice('y', "Overwrite", Action::Overwrite)
+///                     .choice('n', "Skip", Action::Skip)
+///                     .choice('d', "Show the diff", Action::Diff)
+///                     .default_key('n');
+///     let action : Result<Action, AbortReason> = prompt.display();
+/// }
+/// ```
+pub struct Expand<T> {
+    label: String,
+    choices: Vec<Choice<T>>,
+    default_key: Option<char>,
+    current_choice: Option<usize>,
+    confirm_choice: bool,
+    is_expanded: bool,
+    is_submitted: bool,
+    is_aborted: bool,
+    style: ExpandStyle,
+}
+
+/// Errors of assigning a key to a choice of the `Expand` prompt
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ExpandError {
+    /// The key is reserved for the help
+    ReservedKey(char),
+
+    /// The key is already assigned to another choice, possibly in another case
+    DuplicateKey(char),
+}
+
+struct Choice<T> {
+    key: char,
+    label: String,
+    value: Option<T>,
+}
+
+impl<T> Expand<T> {
+    /// Constructs a new prompt with the given label and no choices
+    pub fn new<S: Into<String>>(label: S) -> Self {
+        Expand {
+            label: label.into(),
+            choices: vec![],
+            default_key: None,
+            current_choice: None,
+            confirm_choice: false,
+            is_expanded: false,
+            is_submitted: false,
+            is_aborted: false,
+            style: ExpandStyle::default(),
+        }
+    }
+
+    /// Adds a choice that is picked with the given key.
+    ///
+    /// # Panics
+    /// If the key can't be assigned to the choice, see `try_choice`
+    pub fn choice<S: Into<String>>(self, key: char, label: S, value: T) -> Self {
+        self.try_choice(key, label, value)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Adds a choice that is picked with the given key. Returns an error if the key is `h` or
+    /// it is already assigned to another choice in any case
+    pub fn try_choice<S: Into<String>>(
+        mut self,
+        key: char,
+        label: S,
+        value: T,
+    ) -> Result<Self, ExpandError> {
+        if key.eq_ignore_ascii_case(&HELP_KEY) {
+            return Err(ExpandError::ReservedKey(key));
+        }
+        if self.choice_index(key).is_some() {
+            return Err(ExpandError::DuplicateKey(key));
+        }
+
+        self.choices.push(Choice {
+            key,
+            label: label.into(),
+            value: Some(value),
+        });
+        Ok(self)
+    }
+
+    /// Sets the key of the choice that is picked when Enter is pressed without any other input
+    pub fn default_key(mut self, key: char) -> Self {
+        self.default_key = Some(key);
+        self
+    }
+
+    /// If set to `true`, pressing a key only highlights the choice and it has to be confirmed
+    /// with Enter
+    pub fn confirm_choice(mut self, confirm_choice: bool) -> Self {
+        self.confirm_choice = confirm_choice;
+        self
+    }
+
+    /// Sets the style of the prompt
+    pub fn style(mut self, style: ExpandStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Finds the choice by its key in any case
+    fn choice_index(&self, key: char) -> Option<usize> {
+        self.choices
+            .iter()
+            .position(|choice| choice.key.eq_ignore_ascii_case(&key))
+    }
+
+    fn draw_keys(&self, commands: &mut impl CommandBuffer) {
+        for choice in &self.choices {
+            let key = if Some(choice.key) == self.default_key {
+                choice.key.to_ascii_uppercase()
+            } else {
+                choice.key
+            };
+            self.draw_key(key, &choice.label, commands);
+            commands.print(" / ");
+        }
+
+        self.draw_key(HELP_KEY, "help", commands);
+        commands.print(" ");
+    }
+
+    /// Draws the key and the label in a short form like `[y]es` if the label starts with the
+    /// key, or `[y] Overwrite` otherwise
+    fn draw_key(&self, key: char, label: &str, commands: &mut impl CommandBuffer) {
+        let mut label_chars = label.chars();
+        let label_rest = match label_chars.next() {
+            Some(first) if first.eq_ignore_ascii_case(&key) => label_chars.as_str().to_owned(),
+            _ => format!(" {}", label),
+        };
+
+        self.style
+            .key_formatting
+            .print(format!("[{}]", key), commands);
+        self.style
+            .label_style
+            .print_text(&label_rest, &self.style.choice_formatting, commands);
+    }
+
+    fn draw_choices(&self, commands: &mut impl CommandBuffer) {
+        for (index, choice) in self.choices.iter().enumerate() {
+            commands.new_line();
+            self.style
+                .key_formatting
+                .print(format!("  {}) ", choice.key), commands);
+
+            let formatting = if self.current_choice == Some(index) {
+                &self.style.highlighted_choice_formatting
+            } else {
+                &self.style.choice_formatting
+            };
+            self.style
+                .label_style
+                .print_text(&choice.label, formatting, commands);
+        }
+
+        commands.new_line();
+        self.style
+            .key_formatting
+            .print(format!("  {}) ", HELP_KEY), commands);
+        self.style
+            .choice_formatting
+            .print("Help, list all options", commands);
+    }
+}
+
+impl<T> Prompt<T> for Expand<T> {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        let state = PromptState::new(self.is_submitted, false);
+        self.style
+            .label_style
+            .print_with_state(&self.label, state, commands);
+
+        if self.is_submitted {
+            if let Some(choice) = self.current_choice.map(|index| &self.choices[index]) {
+                self.style.label_style.print_text(
+                    &choice.label,
+                    &self.style.submitted_formatting,
+                    commands,
+                );
+            }
+            return;
+        }
+
+        self.draw_keys(commands);
+        if let Some(choice) = self.current_choice.map(|index| &self.choices[index]) {
+            let formatting = &self.style.highlighted_choice_formatting;
+            formatting.print("» ", commands);
+            self.style
+                .label_style
+                .print_text(&choice.label, formatting, commands);
+        }
+
+        if self.is_expanded {
+            self.draw_choices(commands);
+        }
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<T> {
+        match key {
+            Key::Char(c) if c.eq_ignore_ascii_case(&HELP_KEY) => {
+                self.is_expanded = true;
+                EventOutcome::Continue
+            }
+            Key::Char(c) => {
+                if let Some(index) = self.choice_index(c) {
+                    self.current_choice = Some(index);
+                    if !self.confirm_choice {
+                        return self.on_key_pressed(Key::Enter);
+                    }
+                }
+                EventOutcome::Continue
+            }
+            Key::Backspace => {
+                self.current_choice = None;
+                EventOutcome::Continue
+            }
+            Key::Enter => {
+                let choice_index = self
+                    .current_choice
+                    .or_else(|| self.default_key.and_then(|key| self.choice_index(key)));
+
+                match choice_index {
+                    Some(index) => {
+                        self.current_choice = Some(index);
+                        self.is_submitted = true;
+                        self.is_expanded = false;
+                        match self.choices[index].value.take() {
+                            Some(value) => EventOutcome::Done(value),
+                            None => EventOutcome::Continue,
+                        }
+                    }
+                    None => {
+                        self.is_expanded = true;
+                        EventOutcome::Continue
+                    }
+                }
+            }
+            Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
+
+impl Display for ExpandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExpandError::ReservedKey(key) => {
+                write!(f, "the '{}' key is reserved for the help", key)
+            }
+            ExpandError::DuplicateKey(key) => write!(f, "the '{}' key is already assigned", key),
+        }
+    }
+}
+
+impl std::error::Error for ExpandError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt() -> Expand<&'static str> {
+        Expand::new("Overwrite?")
+            .choice('y', "Yes", "yes")
+            .choice('n', "No", "no")
+            .choice('a', "All", "all")
+    }
+
+    #[test]
+    fn submits_choice_by_key() {
+        assert!(matches!(
+            prompt().on_key_pressed(Key::Char('a')),
+            EventOutcome::Done("all")
+        ));
+    }
+
+    #[test]
+    fn submits_default_choice_or_expands_on_enter() {
+        let mut without_default = prompt();
+        assert!(matches!(
+            without_default.on_key_pressed(Key::Enter),
+            EventOutcome::Continue
+        ));
+        assert!(without_default.is_expanded);
+
+        assert!(matches!(
+            prompt().default_key('n').on_key_pressed(Key::Enter),
+            EventOutcome::Done("no")
+        ));
+    }
+
+    #[test]
+    fn matches_keys_in_any_case() {
+        assert!(matches!(
+            prompt().default_key('n').on_key_pressed(Key::Char('N')),
+            EventOutcome::Done("no")
+        ));
+        assert!(matches!(
+            prompt().on_key_pressed(Key::Char('A')),
+            EventOutcome::Done("all")
+        ));
+    }
+
+    #[test]
+    fn rejects_reserved_and_duplicate_keys() {
+        assert_eq!(
+            prompt().try_choice('H', "Hide", "hide").err(),
+            Some(ExpandError::ReservedKey('H'))
+        );
+        assert_eq!(
+            prompt().try_choice('Y', "Yes to all", "yes to all").err(),
+            Some(ExpandError::DuplicateKey('Y'))
+        );
+        assert!(prompt().try_choice('s', "Skip", "skip").is_ok());
+    }
+
+    #[test]
+    fn waits_for_confirmation_when_required() {
+        let mut prompt = prompt().confirm_choice(true);
+        assert!(matches!(
+            prompt.on_key_pressed(Key::Char('y')),
+            EventOutcome::Continue
+        ));
+        assert!(matches!(
+            prompt.on_key_pressed(Key::Enter),
+            EventOutcome::Done("yes")
+        ));
+    }
+}
@@ -0,0 +1,73 @@
+use std::{any::Any, collections::HashMap};
+
+/// Typed answers collected by the `Form`, keyed by the step names.
+///
+/// Each answer is stored with the type that its prompt has returned, so it has to be requested
+/// with the same type
+#[derive(Default)]
+pub struct Answers {
+    values: HashMap<String, Box<dyn Any>>,
+}
+
+impl Answers {
+    /// Returns a reference to the answer of the given step if it exists and has the type `T`
+    pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+        self.values.get(key).and_then(|v| v.downcast_ref())
+    }
+
+    /// Removes the answer of the given step and returns it if it has the type `T`
+    pub fn remove<T: 'static>(&mut self, key: &str) -> Option<T> {
+        match self.values.remove(key)?.downcast() {
+            Ok(value) => Some(*value),
+            Err(value) => {
+                self.values.insert(key.to_owned(), value);
+                None
+            }
+        }
+    }
+
+    /// Stores the answer for the given step, replacing the previous one
+    pub fn insert<T: 'static>(&mut self, key: impl Into<String>, value: T) {
+        self.values.insert(key.into(), Box::new(value));
+    }
+
+    /// Returns `true` if there is an answer for the given step
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Number of the collected answers
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if no answers have been collected
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub(super) fn insert_boxed(&mut self, key: &str, value: Box<dyn Any>) {
+        self.values.insert(key.to_owned(), value);
+    }
+
+    pub(super) fn discard(&mut self, key: &str) {
+        self.values.remove(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_answers_of_matching_type_only() {
+        let mut answers = Answers::default();
+        answers.insert("port", 5432u16);
+
+        assert_eq!(answers.get::<u16>("port"), Some(&5432));
+        assert_eq!(answers.get::<String>("port"), None);
+        assert_eq!(answers.remove::<String>("port"), None);
+        assert_eq!(answers.remove::<u16>("port"), Some(5432));
+        assert!(answers.is_empty());
+    }
+}
@@ -0,0 +1,705 @@
+mod answers;
+
+pub use answers::Answers;
+
+use std::{any::Any, marker::PhantomData};
+
+use crate::{
+    engine::{CommandBuffer, RecordingBuffer},
+    input::Key,
+    prompts::{EventOutcome, Prompt},
+    style::FormStyle,
+};
+
+const DEFAULT_REVIEW_LABEL: &str = "Review your answers";
+const DEFAULT_SUBMIT_LABEL: &str = "Submit";
+const REVIEW_HELP_MESSAGE: &str = "Enter to edit an answer, Esc to go back";
+
+/// Multi-step prompt that asks a sequence of other prompts and collects their typed answers.
+///
+/// Already answered steps stay on the screen in their submitted state. Pressing Esc or Shift+Tab
+/// goes back to the previous step to change its answer. After the last step the review screen is
+/// displayed, where any answer can be picked for editing before the form is submitted. A step
+/// that is edited again shows its prompt as it was right before the answer was submitted.
+///
+/// Each step is created by a function, so that the prompt can be asked again when the user goes
+/// back to it. The function may use the answers collected so far, for example to pick the
+/// default value, and a step can be made conditional with `when`. The conditions are checked
+/// again every time an answer changes, so going back and changing an answer switches the
+/// branches that follow it.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{Answers, Confirmation, Form, Input, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// fn main() {
+///     let form = Form::new()
+///         .step("name", || Input::new("Project name", |s| Ok(s.to_string())))
+///         .step("license", || Input::new("License", |s| Ok(s.to_string())).default_value("MIT"))
+///         .step("git", || Confirmation::new("Initialize a git repository?").default_positive(true));
+///
+///     let answers : Result<Answers, AbortReason> = form.display();
+///     match answers {
+///         Ok(answers) => {
+///             let name = answers.get::<String>("name").unwrap();
+///             let git = answers.get::<bool>("git").unwrap();
+///             println!("Creating {} (git: {})", name, git);
+///         }
+///         Err(abort_reason) => println!("Form is aborted because of {:?}", abort_reason),
+///     }
+/// }
+/// ```
+pub struct Form {
+    steps: Vec<Step>,
+    answers: Answers,
+    position: Position,
+    current_prompt: Option<Box<dyn FormStep>>,
+    current_keys: Vec<Key>,
+    is_reviewing: bool,
+    is_aborted: bool,
+    has_review: bool,
+    review_label: String,
+    submit_label: String,
+    style: FormStyle,
+}
+
+type StepConstructor = Box<dyn Fn(&Answers) -> Box<dyn FormStep>>;
+type Condition = Box<dyn Fn(&Answers) -> bool>;
+
+struct Step {
+    key: String,
+    create: StepConstructor,
+    condition: Option<Condition>,
+    submitted_prompt: Option<Box<dyn FormStep>>,
+    /// Keys that were pressed in the prompt before its answer was submitted. They are replayed
+    /// to restore the prompt when the step is edited again
+    answer_keys: Vec<Key>,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Position {
+    Step(usize),
+    Review(usize),
+    Submitted,
+}
+
+/// Object safe counterpart of the `Prompt` trait that allows to keep the prompts of different
+/// types in one list
+trait FormStep {
+    fn draw(&self, commands: &mut RecordingBuffer);
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Box<dyn Any>>;
+    fn on_abort(&mut self);
+}
+
+struct PromptStep<T, P> {
+    prompt: P,
+    output: PhantomData<fn() -> T>,
+}
+
+impl Form {
+    /// Creates a form without any steps
+    pub fn new() -> Self {
+        Form {
+            steps: vec![],
+            answers: Answers::default(),
+            position: Position::Step(0),
+            current_prompt: None,
+            current_keys: vec![],
+            is_reviewing: false,
+            is_aborted: false,
+            has_review: true,
+            review_label: DEFAULT_REVIEW_LABEL.into(),
+            submit_label: DEFAULT_SUBMIT_LABEL.into(),
+            style: FormStyle::default(),
+        }
+    }
+
+    /// Adds a step to the form. The answer of the step is stored under the given key.
+    /// The function is called every time the step is displayed
+    pub fn step<T, P, F>(self, key: impl Into<String>, create: F) -> Self
+    where
+        T: 'static,
+        P: Prompt<T> + 'static,
+        F: Fn() -> P + 'static,
+    {
+        self.step_with(key, move |_| create())
+    }
+
+    /// Adds a step that is created from the answers to the previous steps.
+    /// The answer of the step is stored under the given key
+    ///
+    /// ```rust
+    /// use cli_prompts::prompts::{Form, Input, Selection};
+    ///
+    /// let form = Form::new()
+    ///     .step("engine", || Selection::new("Database engine", ["postgres", "mysql"].into_iter()))
+    ///     .step_with("port", |answers| {
+    ///         let default_port = match answers.get::<&str>("engine") {
+    ///             Some(&"mysql") => "3306",
+    ///             _ => "5432",
+    ///         };
+    ///         Input::new("Port", |s| s.parse::<u16>().map_err(|e| e.to_string()))
+    ///             .default_value(default_port)
+    ///     });
+    /// ```
+    pub fn step_with<T, P, F>(mut self, key: impl Into<String>, create: F) -> Self
+    where
+        T: 'static,
+        P: Prompt<T> + 'static,
+        F: Fn(&Answers) -> P + 'static,
+    {
+        self.steps.push(Step {
+            key: key.into(),
+            create: Box::new(move |answers| {
+                Box::new(PromptStep {
+                    prompt: create(answers),
+                    output: PhantomData,
+                })
+            }),
+            condition: None,
+            submitted_prompt: None,
+            answer_keys: vec![],
+        });
+        self
+    }
+
+    /// Makes the last added step run only if the condition on the previous answers holds.
+    /// The answer of a skipped step is removed
+    ///
+    /// ```rust
+    /// use cli_prompts::prompts::{Confirmation, Form, Selection};
+    ///
+    /// let form = Form::new()
+    ///     .step("use_database", || Confirmation::new("Use a database?"))
+    ///     .step("engine", || Selection::new("Database engine", ["postgres", "mysql"].into_iter()))
+    ///     .when(|answers| answers.get::<bool>("use_database") == Some(&true));
+    /// ```
+    pub fn when<F>(mut self, condition: F) -> Self
+    where
+        F: Fn(&Answers) -> bool + 'static,
+    {
+        let step = self
+            .steps
+            .last_mut()
+            .expect("The condition must follow a step");
+        step.condition = Some(Box::new(condition));
+        self
+    }
+
+    /// Sets the label of the review screen
+    pub fn review_label<S: Into<String>>(mut self, label: S) -> Self {
+        self.review_label = label.into();
+        self
+    }
+
+    /// Sets the label of the entry that submits the form on the review screen
+    pub fn submit_label<S: Into<String>>(mut self, label: S) -> Self {
+        self.submit_label = label.into();
+        self
+    }
+
+    /// Makes the form submit right after the last step without displaying the review screen
+    pub fn without_review(mut self) -> Self {
+        self.has_review = false;
+        self
+    }
+
+    /// Sets the style of the review screen
+    pub fn style(mut self, style: FormStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn current_position(&self) -> Position {
+        match self.position {
+            Position::Step(index) if !self.is_step_enabled(index) => self.position_from(index),
+            position => position,
+        }
+    }
+
+    fn answered_steps(&self) -> Vec<usize> {
+        (0..self.steps.len())
+            .filter(|&i| self.steps[i].submitted_prompt.is_some())
+            .collect()
+    }
+
+    fn is_step_enabled(&self, index: usize) -> bool {
+        self.steps.get(index).is_some_and(|step| {
+            step.condition
+                .as_ref()
+                .is_none_or(|condition| condition(&self.answers))
+        })
+    }
+
+    fn position_from(&self, step_index: usize) -> Position {
+        if let Some(next_index) = (step_index..self.steps.len()).find(|&i| self.is_step_enabled(i))
+        {
+            Position::Step(next_index)
+        } else if self.has_review {
+            Position::Review(self.answered_steps().len())
+        } else {
+            Position::Submitted
+        }
+    }
+
+    fn first_unanswered_step(&self) -> Option<usize> {
+        (0..self.steps.len())
+            .find(|&i| self.steps[i].submitted_prompt.is_none() && self.is_step_enabled(i))
+    }
+
+    fn previous_step(&self, step_index: usize) -> Option<usize> {
+        (0..step_index).rev().find(|&i| self.is_step_enabled(i))
+    }
+
+    /// Removes the answers of the steps which conditions don't hold anymore
+    fn skip_disabled_steps(&mut self) {
+        for index in 0..self.steps.len() {
+            if !self.is_step_enabled(index) {
+                self.answers.discard(&self.steps[index].key);
+                self.steps[index].submitted_prompt = None;
+                self.steps[index].answer_keys.clear();
+            }
+        }
+    }
+
+    fn review_position(&self, step_index: usize) -> Position {
+        let cursor = self
+            .answered_steps()
+            .iter()
+            .position(|&i| i == step_index)
+            .unwrap_or_default();
+        Position::Review(cursor)
+    }
+
+    /// Creates the prompt of the step and replays the keys that led to its previous answer, if
+    /// there is any. Falls back to the new prompt if the replayed keys complete it
+    fn create_prompt(&self, index: usize) -> Box<dyn FormStep> {
+        let step = &self.steps[index];
+        let mut prompt = (step.create)(&self.answers);
+        for key in &step.answer_keys {
+            if !matches!(prompt.on_key_pressed(key.clone()), EventOutcome::Continue) {
+                return (step.create)(&self.answers);
+            }
+        }
+        prompt
+    }
+
+    fn go_to(&mut self, position: Position) -> EventOutcome<Answers> {
+        self.position = position;
+        self.current_prompt = None;
+        self.current_keys.clear();
+        match position {
+            Position::Review(_) => {
+                self.is_reviewing = true;
+                EventOutcome::Continue
+            }
+            Position::Submitted => EventOutcome::Done(std::mem::take(&mut self.answers)),
+            Position::Step(_) => EventOutcome::Continue,
+        }
+    }
+
+    fn on_step_key_pressed(&mut self, index: usize, key: Key) -> EventOutcome<Answers> {
+        if matches!(key, Key::Esc | Key::BackTab) {
+            if self.is_reviewing {
+                return self.go_to(self.review_position(index));
+            } else if let Some(previous_index) = self.previous_step(index) {
+                return self.go_to(Position::Step(previous_index));
+            }
+        }
+
+        if self.current_prompt.is_none() {
+            self.current_prompt = Some(self.create_prompt(index));
+            self.current_keys = self.steps[index].answer_keys.clone();
+        }
+
+        let prompt = self
+            .current_prompt
+            .as_mut()
+            .expect("The prompt of the current step is created above");
+        match prompt.on_key_pressed(key.clone()) {
+            EventOutcome::Done(answer) => {
+                let step = &mut self.steps[index];
+                self.answers.insert_boxed(&step.key, answer);
+                step.submitted_prompt = self.current_prompt.take();
+                step.answer_keys = std::mem::take(&mut self.current_keys);
+                self.skip_disabled_steps();
+
+                let next_position = if !self.is_reviewing {
+                    self.position_from(index + 1)
+                } else if let Some(unanswered_index) = self.first_unanswered_step() {
+                    Position::Step(unanswered_index)
+                } else {
+                    self.review_position(index)
+                };
+                self.go_to(next_position)
+            }
+            EventOutcome::Continue => {
+                self.current_keys.push(key);
+                EventOutcome::Continue
+            }
+            EventOutcome::Abort(reason) => EventOutcome::Abort(reason),
+        }
+    }
+
+    fn on_review_key_pressed(&mut self, cursor: usize, key: Key) -> EventOutcome<Answers> {
+        let answered_steps = self.answered_steps();
+        let entries_count = answered_steps.len() + 1;
+        match key {
+            Key::Up => self.go_to(Position::Review(
+                (cursor + entries_count - 1) % entries_count,
+            )),
+            Key::Down => self.go_to(Position::Review((cursor + 1) % entries_count)),
+            Key::Home => self.go_to(Position::Review(0)),
+            Key::End => self.go_to(Position::Review(entries_count - 1)),
+            Key::Enter => match answered_steps.get(cursor) {
+                Some(&step_index) => self.go_to(Position::Step(step_index)),
+                None => match self.first_unanswered_step() {
+                    Some(step_index) => self.go_to(Position::Step(step_index)),
+                    None => self.go_to(Position::Submitted),
+                },
+            },
+            Key::Esc | Key::BackTab => match answered_steps.last() {
+                Some(&step_index) => self.go_to(Position::Step(step_index)),
+                None => EventOutcome::Continue,
+            },
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn draw_review(&self, cursor: usize, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self
+                .style
+                .label_style
+                .print_aborted(&self.review_label, commands);
+        }
+
+        self.style.label_style.print(&self.review_label, commands);
+        self.style
+            .help_message_formatting
+            .print(format!("[{}]", REVIEW_HELP_MESSAGE), commands);
+
+        let answered_steps = self.answered_steps();
+        for (entry, &step_index) in answered_steps.iter().enumerate() {
+            commands.new_line();
+            self.draw_marker(entry == cursor, commands);
+            self.draw_step(step_index, commands);
+        }
+
+        commands.new_line();
+        let is_highlighted = cursor == answered_steps.len();
+        self.draw_marker(is_highlighted, commands);
+        let formatting = if is_highlighted {
+            &self.style.highlighted_submit_formatting
+        } else {
+            &self.style.submit_formatting
+        };
+        formatting.print(&self.submit_label, commands);
+    }
+
+    fn draw_marker(&self, is_highlighted: bool, commands: &mut impl CommandBuffer) {
+        if is_highlighted {
+            self.style.highlighted_marker.print(commands);
+        } else {
+            self.style.not_highlighted_marker.print(commands);
+        }
+    }
+
+    fn draw_step(&self, index: usize, commands: &mut impl CommandBuffer) {
+        let mut recording = RecordingBuffer::new(commands.terminal_width());
+        if self.position == Position::Step(index) {
+            match self.current_prompt.as_ref() {
+                Some(prompt) => prompt.draw(&mut recording),
+                None => self.create_prompt(index).draw(&mut recording),
+            }
+        } else if let Some(prompt) = self.steps[index].submitted_prompt.as_ref() {
+            prompt.draw(&mut recording);
+        }
+
+        recording.replay(commands);
+    }
+}
+
+impl Default for Form {
+    fn default() -> Self {
+        Form::new()
+    }
+}
+
+impl Prompt<Answers> for Form {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        let visible_steps: Vec<usize> = match self.current_position() {
+            Position::Review(cursor) => return self.draw_review(cursor, commands),
+            Position::Step(index) if self.is_reviewing => {
+                let mut steps = self.answered_steps();
+                if !steps.contains(&index) {
+                    steps.push(index);
+                    steps.sort_unstable();
+                }
+                steps
+            }
+            Position::Step(index) => self
+                .answered_steps()
+                .into_iter()
+                .filter(|&i| i < index && self.is_step_enabled(i))
+                .chain(std::iter::once(index))
+                .collect(),
+            Position::Submitted => self.answered_steps(),
+        };
+
+        for (i, &step_index) in visible_steps.iter().enumerate() {
+            if i > 0 {
+                commands.new_line();
+            }
+            self.draw_step(step_index, commands);
+        }
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Answers> {
+        self.position = self.current_position();
+        match self.position {
+            Position::Step(index) => self.on_step_key_pressed(index, key),
+            Position::Review(cursor) => self.on_review_key_pressed(cursor, key),
+            Position::Submitted => EventOutcome::Done(std::mem::take(&mut self.answers)),
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.position = self.current_position();
+        match self.position {
+            Position::Step(index) => {
+                if self.current_prompt.is_none() {
+                    self.current_prompt = Some(self.create_prompt(index));
+                }
+                if let Some(prompt) = self.current_prompt.as_mut() {
+                    prompt.on_abort();
+                }
+            }
+            Position::Review(_) | Position::Submitted => self.is_aborted = true,
+        }
+    }
+}
+
+impl<T, P> FormStep for PromptStep<T, P>
+where
+    T: 'static,
+    P: Prompt<T>,
+{
+    fn draw(&self, commands: &mut RecordingBuffer) {
+        self.prompt.draw(commands);
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Box<dyn Any>> {
+        match self.prompt.on_key_pressed(key) {
+            EventOutcome::Done(answer) => EventOutcome::Done(Box::new(answer)),
+            EventOutcome::Continue => EventOutcome::Continue,
+            EventOutcome::Abort(reason) => EventOutcome::Abort(reason),
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.prompt.on_abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompts::{Confirmation, Input};
+
+    fn test_form() -> Form {
+        Form::new()
+            .step("name", || Input::new("Name", |s| Ok(s.to_string())))
+            .step("age", || {
+                Input::new("Age", |s| s.parse::<u32>().map_err(|e| e.to_string()))
+            })
+            .step("subscribe", || Confirmation::new("Subscribe?"))
+    }
+
+    fn type_text(form: &mut Form, text: &str) {
+        for c in text.chars() {
+            form.on_key_pressed(Key::Char(c));
+        }
+        form.on_key_pressed(Key::Enter);
+    }
+
+    fn erase_text(form: &mut Form, count: usize) {
+        for _ in 0..count {
+            form.on_key_pressed(Key::Backspace);
+        }
+    }
+
+    fn rendered(form: &Form) -> String {
+        let mut commands = RecordingBuffer::default();
+        form.draw(&mut commands);
+        commands.text()
+    }
+
+    #[test]
+    fn draws_current_step_as_aborted() {
+        let mut form = test_form();
+        type_text(&mut form, "Ann");
+        form.on_abort();
+        assert_eq!(rendered(&form), "✔ Name: Ann\n✖ Age: cancelled");
+    }
+
+    #[test]
+    fn collects_typed_answers_after_review() {
+        let mut form = test_form();
+        type_text(&mut form, "Ann");
+        type_text(&mut form, "42");
+        type_text(&mut form, "y");
+
+        assert!(rendered(&form).contains("Review your answers"));
+        form.on_key_pressed(Key::End);
+        let EventOutcome::Done(answers) = form.on_key_pressed(Key::Enter) else {
+            panic!("The form is expected to be submitted");
+        };
+
+        assert_eq!(
+            answers.get::<String>("name").map(String::as_str),
+            Some("Ann")
+        );
+        assert_eq!(answers.get::<u32>("age"), Some(&42));
+        assert_eq!(answers.get::<bool>("subscribe"), Some(&true));
+    }
+
+    #[test]
+    fn goes_back_to_previous_step() {
+        let mut form = test_form();
+        type_text(&mut form, "Ann");
+        form.on_key_pressed(Key::Esc);
+        assert_eq!(form.position, Position::Step(0));
+        assert!(rendered(&form).starts_with("? Name: Ann"));
+
+        erase_text(&mut form, 3);
+        type_text(&mut form, "Bob");
+        assert_eq!(form.position, Position::Step(1));
+        assert_eq!(
+            form.answers.get::<String>("name").map(String::as_str),
+            Some("Bob")
+        );
+        assert!(rendered(&form).starts_with("✔ Name: Bob"));
+    }
+
+    #[test]
+    fn restores_previous_answer_when_going_back() {
+        let mut form = test_form();
+        type_text(&mut form, "Ann");
+        form.on_key_pressed(Key::Esc);
+
+        form.on_key_pressed(Key::Backspace);
+        type_text(&mut form, "a");
+        assert_eq!(form.position, Position::Step(1));
+        assert_eq!(
+            form.answers.get::<String>("name").map(String::as_str),
+            Some("Ana")
+        );
+    }
+
+    #[test]
+    fn edits_answer_from_review() {
+        let mut form = test_form();
+        type_text(&mut form, "Ann");
+        type_text(&mut form, "42");
+        type_text(&mut form, "n");
+
+        form.on_key_pressed(Key::Home);
+        form.on_key_pressed(Key::Down);
+        form.on_key_pressed(Key::Enter);
+        assert_eq!(form.position, Position::Step(1));
+        assert!(rendered(&form).contains("Subscribe?"));
+
+        erase_text(&mut form, 2);
+        type_text(&mut form, "7");
+        assert_eq!(form.position, Position::Review(1));
+        assert_eq!(form.answers.get::<u32>("age"), Some(&7));
+        assert_eq!(
+            form.answers.get::<String>("name").map(String::as_str),
+            Some("Ann")
+        );
+    }
+
+    #[test]
+    fn submits_after_last_step_without_review() {
+        let mut form = test_form().without_review();
+        type_text(&mut form, "Ann");
+        type_text(&mut form, "42");
+
+        form.on_key_pressed(Key::Char('n'));
+        assert!(matches!(
+            form.on_key_pressed(Key::Enter),
+            EventOutcome::Done(_)
+        ));
+        assert_eq!(rendered(&form).lines().count(), 3);
+    }
+
+    fn database_form() -> Form {
+        Form::new()
+            .step("use_database", || Confirmation::new("Use a database?"))
+            .step("engine", || Input::new("Engine", |s| Ok(s.to_string())))
+            .when(|answers| answers.get::<bool>("use_database") == Some(&true))
+            .step_with("port", |answers| {
+                let default_port = match answers.get::<String>("engine").map(String::as_str) {
+                    Some("mysql") => "3306",
+                    _ => "5432",
+                };
+                Input::new("Port", |s| s.parse::<u16>().map_err(|e| e.to_string()))
+                    .default_value(default_port)
+            })
+            .when(|answers| answers.contains("engine"))
+            .step("name", || Input::new("Name", |s| Ok(s.to_string())))
+    }
+
+    #[test]
+    fn skips_steps_which_conditions_fail() {
+        let mut form = database_form();
+        type_text(&mut form, "n");
+        assert_eq!(form.position, Position::Step(3));
+        assert_eq!(rendered(&form), "✔ Use a database? [Y/n]: No\n? Name: ");
+
+        form.on_key_pressed(Key::Esc);
+        assert_eq!(form.position, Position::Step(0));
+    }
+
+    #[test]
+    fn creates_steps_from_previous_answers() {
+        let mut form = database_form();
+        type_text(&mut form, "y");
+        type_text(&mut form, "mysql");
+        assert!(rendered(&form).ends_with("? Port: [3306]"));
+
+        type_text(&mut form, "");
+        assert_eq!(form.answers.get::<u16>("port"), Some(&3306));
+    }
+
+    #[test]
+    fn reevaluates_branches_after_changing_answer() {
+        let mut form = database_form();
+        type_text(&mut form, "y");
+        type_text(&mut form, "mysql");
+        type_text(&mut form, "");
+        type_text(&mut form, "app");
+
+        form.on_key_pressed(Key::Home);
+        form.on_key_pressed(Key::Enter);
+        erase_text(&mut form, 1);
+        type_text(&mut form, "n");
+        assert_eq!(form.position, Position::Review(0));
+        assert!(!form.answers.contains("engine"));
+        assert!(!form.answers.contains("port"));
+
+        form.on_key_pressed(Key::Enter);
+        erase_text(&mut form, 1);
+        type_text(&mut form, "y");
+        assert_eq!(form.position, Position::Step(1));
+
+        type_text(&mut form, "postgres");
+        assert_eq!(form.position, Position::Step(2));
+        type_text(&mut form, "");
+        assert_eq!(form.position, Position::Review(2));
+        assert_eq!(form.answers.get::<u16>("port"), Some(&5432));
+    }
+}
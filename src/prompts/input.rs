@@ -3,7 +3,7 @@ use crate::{
     engine::CommandBuffer,
     input::Key,
     prompts::{AbortReason, EventOutcome},
-    style::InputStyle,
+    style::{InputStyle, PromptState},
 };
 
 /// This is a normal text input prompt with the following features:
@@ -53,6 +53,7 @@ pub struct Input<F> {
     help_message: Option<String>,
     is_first_input: bool,
     is_submitted: bool,
+    is_aborted: bool,
     error: Option<String>,
     validation: F,
     style: InputStyle,
@@ -74,6 +75,7 @@ where
             help_message: None,
             is_first_input: true,
             is_submitted: false,
+            is_aborted: false,
             error: None,
             validation,
             style: InputStyle::default(),
@@ -106,7 +108,14 @@ where
     F: Fn(&str) -> Result<T, String>,
 {
     fn draw(&self, commands: &mut impl CommandBuffer) {
-        self.style.label_style.print(&self.label, commands);
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        let state = PromptState::new(self.is_submitted, self.error.is_some());
+        self.style
+            .label_style
+            .print_with_state(&self.label, state, commands);
 
         if let Some(error) = self.error.as_ref() {
             self.style
@@ -114,7 +123,7 @@ where
                 .print(format!("[{}]", error), commands);
         } else if self.is_submitted {
             self.style.submitted_formatting.print(&self.input, commands);
-        } else if self.is_first_input && self.input.len() > 0 {
+        } else if self.is_first_input && !self.input.is_empty() {
             self.style
                 .default_value_formatting
                 .print(format!("[{}]", self.input), commands);
@@ -123,12 +132,18 @@ where
         }
 
         if let Some(help_message) = self.help_message.as_ref() {
-            self.style
-                .help_message_formatting
-                .print(format!("[{}]", help_message), commands);
+            self.style.label_style.print_help_message(
+                help_message,
+                &self.style.help_message_formatting,
+                commands,
+            );
         }
     }
 
+    fn has_default_answer(&self) -> bool {
+        self.is_first_input && !self.input.is_empty()
+    }
+
     fn on_key_pressed(&mut self, key: Key) -> EventOutcome<T> {
         let is_first_input = self.is_first_input;
         self.is_first_input = false;
@@ -166,4 +181,8 @@ where
             _ => EventOutcome::Continue,
         }
     }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
 }
@@ -0,0 +1,452 @@
+use crate::{
+    engine::{CommandBuffer, RecordingBuffer},
+    input::Key,
+    prompts::{EventOutcome, Prompt},
+    style::{ListBuilderStyle, PromptState},
+};
+
+const ADD_HELP_MESSAGE: &str = "Enter on empty to finish, Tab to edit the list";
+const LIST_HELP_MESSAGE: &str = "Enter to edit, Del to remove, Tab to add, Ctrl+D to finish";
+const MAX_ITEMS_HELP_MESSAGE: &str = "Enter to edit, Del to remove, Ctrl+D to finish";
+
+type PromptConstructor<T, P> = Box<dyn Fn(Option<&T>) -> P>;
+
+/// Prompt that asks another prompt repeatedly and collects its answers into a list.
+///
+/// The collected items stay on the screen above the prompt for the next item. Pressing Enter
+/// without typing anything, or Ctrl+D, finishes the list. If the prompt for the next item has a
+/// default answer, Enter adds it and only Ctrl+D finishes the list. Tab moves the focus to the collected
+/// items, where an item can be edited with Enter or removed with Delete.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{Input, ListBuilder, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// fn main() {
+///     let hosts = ListBuilder::new("Hosts", || Input::new("Host", |s| Ok(s.to_string())))
+///         .min_items(1)
+///         .max_items(5);
+///
+///     let hosts : Result<Vec<String>, AbortReason> = hosts.display();
+///     match hosts {
+///         Ok(hosts) => println!("Deploying to {}", hosts.join(", ")),
+///         Err(abort_reason) => println!("Prompt is aborted because of {:?}", abort_reason),
+///     }
+/// }
+/// ```
+pub struct ListBuilder<T, P> {
+    label: String,
+    create: PromptConstructor<T, P>,
+    items: Vec<T>,
+    submitted_prompts: Vec<P>,
+    current_prompt: P,
+    is_prompt_touched: bool,
+    focus: Focus,
+    min_items: usize,
+    max_items: Option<usize>,
+    finish_on_empty_submit: bool,
+    help_message: Option<String>,
+    error: Option<String>,
+    is_submitted: bool,
+    is_aborted: bool,
+    style: ListBuilderStyle,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Focus {
+    NewItem,
+    Item(usize),
+    Editing(usize),
+}
+
+impl<T, P> ListBuilder<T, P>
+where
+    P: Prompt<T>,
+{
+    /// Constructs a list builder with a given label and a function that creates
+    /// the prompt for each item
+    pub fn new<F>(label: impl Into<String>, create: F) -> Self
+    where
+        F: Fn() -> P + 'static,
+    {
+        Self::new_with_editing(label, move |_| create())
+    }
+
+    /// Constructs a list builder which prompt function also receives the item that is edited.
+    /// This allows to show the current value of the item, e.g. as the default value of the input
+    ///
+    /// ```rust
+    /// use cli_prompts::prompts::{Input, ListBuilder};
+    ///
+    /// let tags = ListBuilder::new_with_editing("Tags", |tag: Option<&String>| {
+    ///     Input::new("Tag", |s| Ok(s.to_string()))
+    ///         .default_value(tag.map(String::as_str).unwrap_or_default())
+    /// });
+    /// ```
+    pub fn new_with_editing<F>(label: impl Into<String>, create: F) -> Self
+    where
+        F: Fn(Option<&T>) -> P + 'static,
+    {
+        let current_prompt = create(None);
+        ListBuilder {
+            label: label.into(),
+            create: Box::new(create),
+            items: vec![],
+            submitted_prompts: vec![],
+            current_prompt,
+            is_prompt_touched: false,
+            focus: Focus::NewItem,
+            min_items: 0,
+            max_items: None,
+            finish_on_empty_submit: true,
+            help_message: None,
+            error: None,
+            is_submitted: false,
+            is_aborted: false,
+            style: ListBuilderStyle::default(),
+        }
+    }
+
+    /// Sets the minimal number of items. The list can't be finished with less items
+    pub fn min_items(mut self, count: usize) -> Self {
+        self.min_items = count;
+        self
+    }
+
+    /// Sets the maximal number of items. No more items can be added after it is reached
+    pub fn max_items(mut self, count: usize) -> Self {
+        self.max_items = Some(count);
+        self
+    }
+
+    /// Makes Enter always go to the item prompt, so that the list can only be finished with
+    /// Ctrl+D. This is useful for the item prompts which accept an empty answer, like `Selection`
+    pub fn dont_finish_on_empty_submit(mut self) -> Self {
+        self.finish_on_empty_submit = false;
+        self
+    }
+
+    /// Sets a help message which will be displayed after the label
+    /// instead of the default one
+    pub fn help_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.help_message = Some(message.into());
+        self
+    }
+
+    /// Sets the style for the prompt
+    pub fn style(mut self, style: ListBuilderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn can_add_items(&self) -> bool {
+        self.max_items.is_none_or(|max| self.items.len() < max)
+    }
+
+    fn reset_prompt(&mut self, item_index: Option<usize>) {
+        self.current_prompt = (self.create)(item_index.map(|i| &self.items[i]));
+        self.is_prompt_touched = false;
+    }
+
+    fn focus_list(&mut self, index: usize) {
+        self.focus = if self.items.is_empty() {
+            Focus::NewItem
+        } else {
+            Focus::Item(index.min(self.items.len() - 1))
+        };
+    }
+
+    fn finish(&mut self) -> EventOutcome<Vec<T>> {
+        if self.items.len() < self.min_items {
+            self.error = Some(match self.min_items {
+                1 => "At least 1 item is required".into(),
+                count => format!("At least {} items are required", count),
+            });
+            return EventOutcome::Continue;
+        }
+
+        self.is_submitted = true;
+        EventOutcome::Done(std::mem::take(&mut self.items))
+    }
+
+    fn on_prompt_key_pressed(&mut self, key: Key) -> EventOutcome<Vec<T>> {
+        let is_empty_submit = key == Key::Enter
+            && !self.is_prompt_touched
+            && self.focus == Focus::NewItem
+            && !self.current_prompt.has_default_answer();
+        if is_empty_submit {
+            if self.finish_on_empty_submit {
+                return self.finish();
+            }
+        } else {
+            self.is_prompt_touched = true;
+        }
+
+        match self.current_prompt.on_key_pressed(key) {
+            EventOutcome::Done(item) => {
+                let prompt = std::mem::replace(&mut self.current_prompt, (self.create)(None));
+                self.is_prompt_touched = false;
+                match self.focus {
+                    Focus::Editing(index) => {
+                        self.items[index] = item;
+                        self.submitted_prompts[index] = prompt;
+                        self.focus = Focus::Item(index);
+                    }
+                    _ => {
+                        self.items.push(item);
+                        self.submitted_prompts.push(prompt);
+                        if !self.can_add_items() {
+                            self.focus_list(self.items.len() - 1);
+                        }
+                    }
+                }
+                EventOutcome::Continue
+            }
+            EventOutcome::Continue => EventOutcome::Continue,
+            EventOutcome::Abort(reason) => EventOutcome::Abort(reason),
+        }
+    }
+
+    fn on_list_key_pressed(&mut self, index: usize, key: Key) -> EventOutcome<Vec<T>> {
+        match key {
+            Key::Up => self.focus_list((index + self.items.len() - 1) % self.items.len()),
+            Key::Down => self.focus_list((index + 1) % self.items.len()),
+            Key::Home => self.focus_list(0),
+            Key::End => self.focus_list(self.items.len() - 1),
+            Key::Enter | Key::Char('e') => {
+                self.reset_prompt(Some(index));
+                self.focus = Focus::Editing(index);
+            }
+            Key::Delete | Key::Backspace | Key::Char('d') => {
+                self.items.remove(index);
+                self.submitted_prompts.remove(index);
+                self.focus_list(index);
+            }
+            Key::Tab | Key::Esc if self.can_add_items() => self.focus = Focus::NewItem,
+            _ => {}
+        }
+        EventOutcome::Continue
+    }
+
+    fn draw_marker(&self, is_highlighted: bool, commands: &mut impl CommandBuffer) {
+        if is_highlighted {
+            self.style.highlighted_marker.print(commands);
+        } else {
+            self.style.not_highlighted_marker.print(commands);
+        }
+    }
+
+    fn draw_prompt(prompt: &P, commands: &mut impl CommandBuffer) {
+        let mut recording = RecordingBuffer::new(commands.terminal_width());
+        prompt.draw(&mut recording);
+        recording.replay(commands);
+    }
+
+    fn current_help_message(&self) -> &str {
+        if let Some(help_message) = self.help_message.as_ref() {
+            help_message
+        } else if self.focus == Focus::NewItem {
+            ADD_HELP_MESSAGE
+        } else if self.can_add_items() {
+            LIST_HELP_MESSAGE
+        } else {
+            MAX_ITEMS_HELP_MESSAGE
+        }
+    }
+}
+
+impl<T, P> Prompt<Vec<T>> for ListBuilder<T, P>
+where
+    P: Prompt<T>,
+{
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        let state = PromptState::new(self.is_submitted, self.error.is_some());
+        self.style
+            .label_style
+            .print_with_state(&self.label, state, commands);
+
+        if self.is_submitted {
+            return self
+                .style
+                .submitted_formatting
+                .print(items_count(self.submitted_prompts.len()), commands);
+        }
+
+        if let Some(error) = self.error.as_ref() {
+            self.style
+                .error_formatting
+                .print(format!("[{}]", error), commands);
+        } else if !matches!(self.focus, Focus::Editing(_)) {
+            self.style.label_style.print_help_message(
+                self.current_help_message(),
+                &self.style.help_message_formatting,
+                commands,
+            );
+        }
+
+        for (index, prompt) in self.submitted_prompts.iter().enumerate() {
+            commands.new_line();
+            match self.focus {
+                Focus::Editing(i) if i == index => {
+                    self.draw_marker(true, commands);
+                    Self::draw_prompt(&self.current_prompt, commands);
+                }
+                focus => {
+                    self.draw_marker(focus == Focus::Item(index), commands);
+                    Self::draw_prompt(prompt, commands);
+                }
+            }
+        }
+
+        if self.focus == Focus::NewItem {
+            commands.new_line();
+            self.style.new_item_marker.print(commands);
+            Self::draw_prompt(&self.current_prompt, commands);
+        }
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Vec<T>> {
+        self.error = None;
+        match (self.focus, &key) {
+            (Focus::Editing(index), Key::Esc) => {
+                self.reset_prompt(None);
+                self.focus = Focus::Item(index);
+                EventOutcome::Continue
+            }
+            (Focus::NewItem | Focus::Item(_), Key::Ctrl('d')) => self.finish(),
+            (Focus::NewItem, Key::Tab) => {
+                if !self.items.is_empty() {
+                    self.focus_list(self.items.len() - 1);
+                }
+                EventOutcome::Continue
+            }
+            (Focus::Item(index), _) => self.on_list_key_pressed(index, key),
+            _ => self.on_prompt_key_pressed(key),
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
+
+fn items_count(count: usize) -> String {
+    match count {
+        1 => "1 item".into(),
+        count => format!("{} items", count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prompts::Input;
+
+    fn test_list() -> ListBuilder<String, impl Prompt<String>> {
+        ListBuilder::new_with_editing("Hosts", |host: Option<&String>| {
+            Input::new("Host", |s| Ok(s.to_string()))
+                .default_value(host.map(String::as_str).unwrap_or_default())
+        })
+    }
+
+    fn type_text<P: Prompt<Vec<String>>>(prompt: &mut P, text: &str) -> EventOutcome<Vec<String>> {
+        for c in text.chars() {
+            prompt.on_key_pressed(Key::Char(c));
+        }
+        prompt.on_key_pressed(Key::Enter)
+    }
+
+    fn rendered<P: Prompt<Vec<String>>>(prompt: &P) -> String {
+        let mut commands = RecordingBuffer::default();
+        prompt.draw(&mut commands);
+        commands.text()
+    }
+
+    #[test]
+    fn collects_items_until_empty_submit() {
+        let mut list = test_list();
+        type_text(&mut list, "alpha");
+        type_text(&mut list, "beta");
+        assert!(rendered(&list).contains("✔ Host: alpha"));
+
+        let EventOutcome::Done(items) = type_text(&mut list, "") else {
+            panic!("The list is expected to be finished");
+        };
+        assert_eq!(items, vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn edits_and_removes_items() {
+        let mut list = test_list();
+        type_text(&mut list, "alpha");
+        type_text(&mut list, "beta");
+        type_text(&mut list, "gamma");
+
+        list.on_key_pressed(Key::Tab);
+        list.on_key_pressed(Key::Home);
+        list.on_key_pressed(Key::Delete);
+        list.on_key_pressed(Key::Enter);
+        type_text(&mut list, "delta");
+        assert_eq!(list.focus, Focus::Item(0));
+
+        list.on_key_pressed(Key::Tab);
+        let EventOutcome::Done(items) = list.on_key_pressed(Key::Ctrl('d')) else {
+            panic!("The list is expected to be finished");
+        };
+        assert_eq!(items, vec!["delta", "gamma"]);
+    }
+
+    #[test]
+    fn enforces_item_count() {
+        let mut list = test_list().min_items(2).max_items(2);
+        type_text(&mut list, "alpha");
+        assert!(matches!(
+            list.on_key_pressed(Key::Ctrl('d')),
+            EventOutcome::Continue
+        ));
+        assert!(rendered(&list).contains("At least 2 items are required"));
+
+        type_text(&mut list, "beta");
+        assert_eq!(list.focus, Focus::Item(1));
+        list.on_key_pressed(Key::Tab);
+        assert_eq!(list.focus, Focus::Item(1));
+        assert!(matches!(
+            list.on_key_pressed(Key::Ctrl('d')),
+            EventOutcome::Done(_)
+        ));
+    }
+
+    #[test]
+    fn requires_single_item_in_singular() {
+        let mut list = test_list().min_items(1);
+        list.on_key_pressed(Key::Ctrl('d'));
+        assert!(rendered(&list).contains("[At least 1 item is required]"));
+
+        type_text(&mut list, "alpha");
+        list.on_key_pressed(Key::Ctrl('d'));
+        assert!(rendered(&list).ends_with("✔ Hosts: 1 item"));
+    }
+
+    #[test]
+    fn adds_default_answer_on_untouched_enter() {
+        let mut list = ListBuilder::new("Ports", || {
+            Input::new("Port", |s| Ok(s.to_string())).default_value("8080")
+        });
+        assert!(matches!(
+            list.on_key_pressed(Key::Enter),
+            EventOutcome::Continue
+        ));
+        type_text(&mut list, "9090");
+
+        let EventOutcome::Done(items) = list.on_key_pressed(Key::Ctrl('d')) else {
+            panic!("The list is expected to be finished");
+        };
+        assert_eq!(items, vec!["8080", "9090"]);
+    }
+}
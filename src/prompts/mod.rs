@@ -6,16 +6,40 @@
 //! handle the rest
 
 mod confirmation;
+mod expand;
+mod form;
 mod input;
+mod list_builder;
 mod options;
+mod password;
+mod progress;
+mod table;
+mod tree;
 
 pub use confirmation::Confirmation;
+pub use expand::{Expand, ExpandError};
+pub use form::{Answers, Form};
 pub use input::Input;
+pub use list_builder::ListBuilder;
 pub use options::multiselect::Multiselect;
-pub use options::selection::Selection;
+pub use options::reorder::Reorder;
+pub use options::selection::{
+    ReturnCreatable, ReturnIndex, ReturnIndexAndItem, ReturnItem, ReturnMode, Selection, Shortcuts,
+};
 pub use options::{Options, multioption_prompt::MultiOptionPrompt};
-
-use std::io::stdout;
+pub use password::Password;
+pub use progress::{progress_bar::ProgressBar, spinner::Spinner, TaskHandle};
+pub use table::table_multiselect::TableMultiselect;
+pub use table::table_select::TableSelect;
+pub use table::{Alignment, Table};
+pub use tree::tree_multiselect::TreeMultiselect;
+pub use tree::tree_select::TreeSelect;
+pub use tree::{Tree, TreeItem, TreeNode};
+
+use std::{
+    io::{stdout, Write},
+    time::{Duration, Instant},
+};
 
 use crate::{
     engine::{Clear, CommandBuffer, CrosstermEngine, Engine},
@@ -98,10 +122,34 @@ pub trait Prompt<TOut> {
     /// outcome of the keypress:
     /// - EventOutcome::Continue - the input was handled and the prompt should continue displaying
     /// - EventOutcome::Done(TOut) - the prompt has successfully completed. Pass the result as the
-    /// enum's field
+    ///   enum's field
     /// - EventOutcome::Abort(AbortReason) - the prompt has finished abruptly. Specify a reason in
-    /// the enum's field
+    ///   the enum's field
     fn on_key_pressed(&mut self, key: Key) -> EventOutcome<TOut>;
+
+    /// This is called when the prompt is aborted, right before it is drawn for the last time.
+    /// The built-in prompts switch to the aborted state here, e.g. cross out their label.
+    /// Does nothing by default, so the last frame stays as it is
+    fn on_abort(&mut self) {}
+
+    /// Whether pressing Enter right away submits a default answer, like the default value of the
+    /// `Input`. Lets the `ListBuilder` tell an empty answer from the default one. Returns `false`
+    /// by default
+    fn has_default_answer(&self) -> bool {
+        false
+    }
+
+    /// Interval between the `on_tick` calls. Returns `None` by default, so the prompt is only
+    /// redrawn when a key is pressed
+    fn tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// This is called every `tick_interval` if no key is pressed in the meantime. Allows the
+    /// prompt to animate itself or to complete without the user's input
+    fn on_tick(&mut self) -> EventOutcome<TOut> {
+        EventOutcome::Continue
+    }
 }
 
 /// A trait that is implemented for every type that implements `Prompt`. Provides a convenient way
@@ -112,29 +160,67 @@ pub trait DisplayPrompt<T> {
     /// - Returns `Ok(T)` if the prompt is completed successfully.
     /// - Returns `Err(AbortReason)` if it failed. Check the `AbortReason` to find out why
     fn display(self) -> Result<T, AbortReason>;
+
+    /// Same as `display`, but draws the prompt to the given buffer instead of the standard
+    /// output. This allows to keep the standard output for the results, e.g. by drawing to
+    /// `stderr`
+    fn display_on<W: Write>(self, buffer: W) -> Result<T, AbortReason>;
+
+    /// Same as `display`, but draws the prompt with the given engine. This allows to configure
+    /// the engine, e.g. to erase the prompt after it is completed:
+    ///
+    /// ```rust,no_run
+    /// use std::io::stderr;
+    /// use cli_prompts::{engine::CrosstermEngine, prompts::{DisplayPrompt, Selection}};
+    ///
+    /// let engine = CrosstermEngine::new(stderr()).transient(true);
+    /// let branch = Selection::new("Branch", ["main", "develop"].into_iter()).display_with(engine);
+    /// if let Ok(branch) = branch {
+    ///     eprintln!("Switched to {}", branch);
+    /// }
+    /// ```
+    fn display_with<E: Engine>(self, engine: E) -> Result<T, AbortReason>;
 }
 
 impl<T, P> DisplayPrompt<T> for P
 where
     P: Prompt<T> + Sized,
 {
-    fn display(mut self) -> Result<T, AbortReason> {
-        let buffer = stdout();
-        let mut engine = CrosstermEngine::new(buffer);
+    fn display(self) -> Result<T, AbortReason> {
+        self.display_on(stdout())
+    }
+
+    fn display_on<W: Write>(self, buffer: W) -> Result<T, AbortReason> {
+        self.display_with(CrosstermEngine::new(buffer))
+    }
+
+    fn display_with<E: Engine>(mut self, mut engine: E) -> Result<T, AbortReason> {
         let mut commands = engine.get_command_buffer();
+        let mut next_tick = None;
 
         loop {
             self.draw(&mut commands);
             engine.render(&commands)?;
 
-            let key_pressed = engine.read_key()?;
-            if matches!(
-                key_pressed,
-                Key::Ctrl('c') | Key::Ctrl('C')
-            ) {
-                return Err(AbortReason::Interrupt);
-            }
-            match self.on_key_pressed(key_pressed) {
+            let key_pressed = match self.tick_interval() {
+                Some(interval) => {
+                    let tick = *next_tick.get_or_insert_with(|| Instant::now() + interval);
+                    engine.poll_key(tick.saturating_duration_since(Instant::now()))?
+                }
+                None => Some(engine.read_key()?),
+            };
+            let outcome = match key_pressed {
+                Some(Key::Ctrl('c') | Key::Ctrl('C')) => {
+                    EventOutcome::Abort(AbortReason::Interrupt)
+                }
+                Some(key) => self.on_key_pressed(key),
+                None => {
+                    next_tick = None;
+                    self.on_tick()
+                }
+            };
+
+            match outcome {
                 EventOutcome::Done(result) => {
                     commands.clear();
                     self.draw(&mut commands);
@@ -147,7 +233,15 @@ where
                     commands.clear();
                     continue;
                 }
-                EventOutcome::Abort(reason) => return Err(reason),
+                EventOutcome::Abort(reason) => {
+                    self.on_abort();
+                    commands.clear();
+                    self.draw(&mut commands);
+                    engine.render(&commands)?;
+                    engine.finish_rendering()?;
+
+                    return Err(reason);
+                }
             }
         }
     }
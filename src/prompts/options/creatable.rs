@@ -0,0 +1,63 @@
+use crate::prompts::options::Options;
+
+type Constructor<T> = Box<dyn Fn(&str) -> Result<T, String>>;
+
+/// Helper for the multi-option prompts that allow creating a new option from the filter string
+pub struct Creator<T> {
+    constructor: Constructor<T>,
+    error: Option<String>,
+}
+
+impl<T> Creator<T> {
+    /// Wraps the function that validates the filter string and constructs a new option from it
+    pub fn new<F>(constructor: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        Creator {
+            constructor: Box::new(constructor),
+            error: None,
+        }
+    }
+
+    /// Label of the synthetic option that creates a new one from the filter string
+    pub fn label(filter: &str) -> String {
+        format!("Create \"{}\"", filter)
+    }
+
+    /// Whether the synthetic option should be displayed in place of the filtered options
+    pub fn is_shown(filter: &str, options: &Options<T>) -> bool {
+        !filter.is_empty() && options.filtered_options().is_empty()
+    }
+
+    /// The error returned by the constructor on the last attempt to create an option
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Clears the last creation error
+    pub fn clear_error(&mut self) {
+        self.error = None;
+    }
+
+    /// Constructs a new option from the filter string and appends it to the options.
+    /// Returns the index of the new option or `None` if the constructor has failed or an option
+    /// with the same text already exists
+    pub fn create(&mut self, filter: &str, options: &mut Options<T>) -> Option<usize> {
+        if options.transformed_options().iter().any(|o| o == filter) {
+            self.error = Some(format!("\"{}\" already exists", filter));
+            return None;
+        }
+
+        match (self.constructor)(filter) {
+            Ok(option) => {
+                self.error = None;
+                Some(options.push(option, filter.to_owned()))
+            }
+            Err(error) => {
+                self.error = Some(error);
+                None
+            }
+        }
+    }
+}
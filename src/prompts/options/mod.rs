@@ -1,12 +1,18 @@
 pub mod multiselect;
 pub mod selection;
 pub mod multioption_prompt;
+pub mod reorder;
+mod creatable;
+
+use crate::style::{markup, MarkupMode};
 
 /// A helper struct for the multi-option prompts
 pub struct Options<T> {
     all_options: Vec<T>,
     transformed_options: Vec<String>,
     filtered_options: Vec<usize>,
+    markup: MarkupMode,
+    plain_options: Vec<String>,
 }
 
 impl<T> Options<T>
@@ -14,6 +20,7 @@ where
     T: Into<String> + Clone,
 {
     /// Create `Options` from an iterator over a type that is convertable to `String`
+    #[allow(clippy::should_implement_trait)]
     pub fn from_iter<I>(iter: I) -> Self
     where
         I: Iterator<Item = T>,
@@ -24,6 +31,8 @@ where
             all_options: options.clone(),
             transformed_options: options.into_iter().map(|s| s.into()).collect(),
             filtered_options: (0..options_count).collect(),
+            markup: MarkupMode::Plain,
+            plain_options: vec![],
         }
     }
 }
@@ -43,29 +52,119 @@ impl<T> Options<T> {
             all_options,
             transformed_options,
             filtered_options: (0..options_count).collect(),
+            markup: MarkupMode::Plain,
+            plain_options: vec![],
+        }
+    }
+
+    /// Sets how the markup in the string representations of the options is handled. The options
+    /// are filtered by their text without the markup
+    pub fn set_markup(&mut self, mode: MarkupMode) {
+        self.markup = mode;
+        self.plain_options = match mode {
+            MarkupMode::Plain => vec![],
+            _ => self
+                .transformed_options
+                .iter()
+                .map(|option| markup::plain_text(option, mode))
+                .collect(),
+        };
+    }
+
+    /// Get a reference to all options in their string representation without the markup
+    pub fn plain_options(&self) -> &[String] {
+        match self.markup {
+            MarkupMode::Plain => &self.transformed_options,
+            _ => &self.plain_options,
         }
     }
 
     /// Filter options using provided string slice
     pub fn filter(&mut self, filter: &str) {
         self.filtered_options.clear();
-        for (index, option) in self.transformed_options.iter().enumerate() {
-            if option.contains(filter) {
+        for index in 0..self.transformed_options.len() {
+            if self.plain_options()[index].contains(filter) {
                 self.filtered_options.push(index);
             }
         }
     }
 
+    /// Filter options using provided predicate on their indices
+    pub fn filter_by<P>(&mut self, mut predicate: P)
+    where
+        P: FnMut(usize) -> bool,
+    {
+        self.filtered_options.clear();
+        for index in 0..self.transformed_options.len() {
+            if predicate(index) {
+                self.filtered_options.push(index);
+            }
+        }
+    }
+
+    /// Reorder the options that satisfy the last applied filter using provided comparison
+    /// function on their indices
+    pub fn sort_filtered_options<F>(&mut self, compare: F)
+    where
+        F: FnMut(&usize, &usize) -> std::cmp::Ordering,
+    {
+        self.filtered_options.sort_by(compare);
+    }
+
+    /// Move the filtered option from one position among the filtered options to another one.
+    /// Does nothing if either of the positions is out of bounds
+    pub fn move_filtered_option(&mut self, from: usize, to: usize) {
+        if from >= self.filtered_options.len() || to >= self.filtered_options.len() {
+            return;
+        }
+
+        let option = self.filtered_options.remove(from);
+        self.filtered_options.insert(to, option);
+    }
+
     /// Retrieve the indices of all options that satisfy the last applied filter
     pub fn filtered_options(&self) -> &[usize] {
         &self.filtered_options
     }
 
+    /// Get a reference to all available options
+    pub fn all_options(&self) -> &[T] {
+        &self.all_options
+    }
+
     /// Get a mutable reference to the vector all available options
     pub fn all_options_mut(&mut self) -> &mut Vec<T> {
         &mut self.all_options
     }
 
+    /// Add a new option with the given string representation to the end of the list.
+    /// Returns the index of the new option. The filter has to be applied again for the option to
+    /// appear among the filtered ones
+    pub fn push(&mut self, option: T, transformed_option: String) -> usize {
+        if self.markup != MarkupMode::Plain {
+            self.plain_options
+                .push(markup::plain_text(&transformed_option, self.markup));
+        }
+        self.all_options.push(option);
+        self.transformed_options.push(transformed_option);
+        self.transformed_options.len() - 1
+    }
+
+    /// Take the options with the given indices out of the list in the order the indices are
+    /// listed. All the other options are dropped, while their string representations remain
+    /// available for drawing
+    pub fn take_options(&mut self, indices: &[usize]) -> Vec<T> {
+        let mut all_options: Vec<Option<T>> = std::mem::take(&mut self.all_options)
+            .into_iter()
+            .map(Some)
+            .collect();
+
+        indices
+            .iter()
+            .filter_map(|&index| all_options.get_mut(index).and_then(Option::take))
+            .collect()
+    }
+
     /// Get a reference to all options in their string representation
     pub fn transformed_options(&self) -> &[String] {
         &self.transformed_options
@@ -1,7 +1,12 @@
-use crate::{engine::CommandBuffer, prompts::options::Options, style::LabelStyle};
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::options::Options,
+    style::{LabelStyle, PromptState, Theme},
+};
 
 /// Helper trait that simplifies the implementation of the prompts that have multiple options to
-/// choose from. It handles filtering, pagination, drawing prompt header and options.
+/// choose from. It handles filtering, pagination, navigation, drawing prompt header and options.
 pub trait MultiOptionPrompt<T> {
 
     /// Maximum number of options that can be displayed on the screen
@@ -25,6 +30,41 @@ pub trait MultiOptionPrompt<T> {
     /// Draws the prompt header
     fn draw_header(&self, cmd_buffer: &mut impl CommandBuffer, is_submitted: bool);
 
+    /// Whether moving up from the first option or down from the last one wraps around to the
+    /// other end of the list
+    fn wraps_around(&self) -> bool {
+        false
+    }
+
+    /// Draws the scroll indicators ("↑ 12 more", "↓ 40 more") and the position counter
+    fn draw_scroll_indicator(&self, text: &str, cmd_buffer: &mut impl CommandBuffer) {
+        Theme::current().muted.print(text, cmd_buffer);
+    }
+
+    /// Calculates the new value of `currently_selected_index` after the given key is pressed.
+    /// Handles Up, Down, PageUp, PageDown, Home and End keys.
+    /// Returns `None` if the key is not a navigation key
+    fn navigate(&self, key: &Key) -> Option<usize> {
+        let current = self.currently_selected_index();
+        let options_count = self.options().filtered_options().len();
+        let page_size = usize::from(self.max_options_count()).max(1);
+        let last = options_count.saturating_sub(1);
+
+        let new_index = match key {
+            Key::Up if current == 0 && self.wraps_around() => last,
+            Key::Up => current.saturating_sub(1),
+            Key::Down if current >= last && self.wraps_around() => 0,
+            Key::Down => (current + 1).min(last),
+            Key::PageUp => current.saturating_sub(page_size),
+            Key::PageDown => (current + page_size).min(last),
+            Key::Home => 0,
+            Key::End => last,
+            _ => return None,
+        };
+
+        Some(new_index)
+    }
+
     /// Draws the entire prompt with all the options. Call this from within the `Prompt::draw()`
     /// method
     fn draw_multioption(
@@ -34,7 +74,25 @@ pub trait MultiOptionPrompt<T> {
         label_style: &LabelStyle,
         cmd_buffer: &mut impl CommandBuffer,
     ) {
-        label_style.print(label, cmd_buffer);
+        self.draw_multioption_with_state(
+            label,
+            PromptState::new(is_submitted, false),
+            label_style,
+            cmd_buffer,
+        );
+    }
+
+    /// Same as `draw_multioption`, but draws the label in the given state, e.g. with the error
+    /// prefix when the input is invalid
+    fn draw_multioption_with_state(
+        &self,
+        label: &str,
+        state: PromptState,
+        label_style: &LabelStyle,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        let is_submitted = state == PromptState::Submitted;
+        label_style.print_with_state(label, state, cmd_buffer);
         self.draw_header(cmd_buffer, is_submitted);
 
         if is_submitted {
@@ -43,17 +101,17 @@ pub trait MultiOptionPrompt<T> {
 
         cmd_buffer.new_line();
         let max_options_count: usize = self.max_options_count().into();
-        let mut start_from = self
+        let options_count = self.options().filtered_options().len();
+        let start_from = self
             .currently_selected_index()
-            .checked_sub(max_options_count / 2)
-            .unwrap_or(0);
-        start_from = start_from.min(
-            self.options()
-                .filtered_options()
-                .len()
-                .checked_sub(max_options_count)
-                .unwrap_or(0),
-        );
+            .saturating_sub(max_options_count / 2)
+            .min(options_count.saturating_sub(max_options_count));
+        let hidden_below = options_count.saturating_sub(start_from + max_options_count);
+
+        if start_from > 0 {
+            self.draw_scroll_indicator(&format!("↑ {} more", start_from), cmd_buffer);
+            cmd_buffer.new_line();
+        }
 
         let displayed_option_indices = self
             .options()
@@ -61,7 +119,7 @@ pub trait MultiOptionPrompt<T> {
             .iter()
             .enumerate()
             .skip(start_from)
-            .take(self.max_options_count().into());
+            .take(max_options_count);
 
         for (selection_index, option_index) in displayed_option_indices {
             let is_selected = selection_index == self.currently_selected_index();
@@ -70,5 +128,107 @@ pub trait MultiOptionPrompt<T> {
             self.draw_option(*option_index, option_label, is_selected, cmd_buffer);
             cmd_buffer.new_line();
         }
+
+        if options_count > max_options_count {
+            let position = format!("{}/{}", self.currently_selected_index() + 1, options_count);
+            let status = if hidden_below > 0 {
+                format!("↓ {} more  {}", hidden_below, position)
+            } else {
+                position
+            };
+
+            self.draw_scroll_indicator(&status, cmd_buffer);
+            cmd_buffer.new_line();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestPrompt {
+        options: Options<String>,
+        current: usize,
+        wrap: bool,
+    }
+
+    impl TestPrompt {
+        fn new(count: usize, current: usize, wrap: bool) -> Self {
+            TestPrompt {
+                options: Options::from_iter((0..count).map(|i| i.to_string())),
+                current,
+                wrap,
+            }
+        }
+    }
+
+    impl MultiOptionPrompt<String> for TestPrompt {
+        fn max_options_count(&self) -> u16 {
+            5
+        }
+
+        fn options(&self) -> &Options<String> {
+            &self.options
+        }
+
+        fn currently_selected_index(&self) -> usize {
+            self.current
+        }
+
+        fn draw_option(&self, _: usize, _: &str, _: bool, _: &mut impl CommandBuffer) {}
+
+        fn draw_header(&self, _: &mut impl CommandBuffer, _: bool) {}
+
+        fn wraps_around(&self) -> bool {
+            self.wrap
+        }
+    }
+
+    #[test]
+    fn stops_at_list_bounds_without_wrap_around() {
+        assert_eq!(TestPrompt::new(10, 0, false).navigate(&Key::Up), Some(0));
+        assert_eq!(TestPrompt::new(10, 9, false).navigate(&Key::Down), Some(9));
+    }
+
+    #[test]
+    fn wraps_around_list_bounds() {
+        assert_eq!(TestPrompt::new(10, 0, true).navigate(&Key::Up), Some(9));
+        assert_eq!(TestPrompt::new(10, 9, true).navigate(&Key::Down), Some(0));
+    }
+
+    #[test]
+    fn jumps_by_pages_and_to_list_ends() {
+        let prompt = TestPrompt::new(12, 3, false);
+        assert_eq!(prompt.navigate(&Key::PageDown), Some(8));
+        assert_eq!(prompt.navigate(&Key::PageUp), Some(0));
+        assert_eq!(prompt.navigate(&Key::Home), Some(0));
+        assert_eq!(prompt.navigate(&Key::End), Some(11));
+        assert_eq!(prompt.navigate(&Key::Enter), None);
+    }
+
+    #[test]
+    fn draws_label_in_state_given_by_submitted_flag() {
+        let label_style = LabelStyle::default();
+        let mut submitted = crate::engine::RecordingBuffer::default();
+        let mut with_error = crate::engine::RecordingBuffer::default();
+        let prompt = TestPrompt::new(0, 0, false);
+        prompt.draw_multioption("Label", true, &label_style, &mut submitted);
+        prompt.draw_multioption_with_state(
+            "Label",
+            PromptState::Error,
+            &label_style,
+            &mut with_error,
+        );
+
+        assert!(submitted.text().starts_with("✔ Label"));
+        assert!(with_error.text().starts_with("! Label"));
+    }
+
+    #[test]
+    fn navigates_empty_list_without_underflow() {
+        let prompt = TestPrompt::new(0, 0, true);
+        assert_eq!(prompt.navigate(&Key::Down), Some(0));
+        assert_eq!(prompt.navigate(&Key::End), Some(0));
     }
 }
@@ -2,10 +2,10 @@ use crate::{
     engine::CommandBuffer,
     input::Key,
     prompts::{options::Options, AbortReason, EventOutcome, Prompt},
-    style::MultiselectionStyle,
+    style::{MultiselectionStyle, PromptState},
 };
 
-use super::multioption_prompt::MultiOptionPrompt;
+use super::{creatable::Creator, multioption_prompt::MultiOptionPrompt};
 
 const DEFAUTL_MAX_OPTIONS: u16 = 5;
 const DEFAULT_HELP_MESSAGE: &str = "Space to select, enter to submit";
@@ -13,6 +13,18 @@ const DEFAULT_HELP_MESSAGE: &str = "Space to select, enter to submit";
 /// Prompt that allows to select multiple options from the given list.
 /// Supports filtering and moving the selection with arrow keys.
 ///
+/// Besides toggling options one by one with Space, the following keys affect all the options
+/// that satisfy the current filter:
+/// - `Ctrl+A` selects all of them;
+/// - `Ctrl+D` deselects all of them;
+/// - `Ctrl+R` inverts the selection.
+///
+/// If the prompt is made `creatable`, the user can also add new options. When the filter matches
+/// nothing, a synthetic "Create" option appears in the list and Space adds the new option and
+/// selects it. Pressing Tab does the same for any non-empty filter string. An option is not
+/// created if one with the same text already exists or if selecting it would exceed
+/// `max_selections`.
+///
 /// ```rust
 /// use cli_prompts::{
 ///     prompts::{Multiselect, AbortReason},
@@ -29,7 +41,9 @@ const DEFAULT_HELP_MESSAGE: &str = "Space to select, enter to submit";
 ///
 ///     let prompt = Multiselect::new("Select files to copy", files.into_iter())
 ///                     .dont_display_help_message()
-///                     .max_displayed_options(3);
+///                     .max_displayed_options(3)
+///                     .max_selections(2)
+///                     .preselect(|file| file.ends_with(".txt"));
 ///     let selection : Result<Vec<&str>, AbortReason> = prompt.display();
 ///     match selection {
 ///         Ok(selected_files) => {
@@ -45,12 +59,19 @@ pub struct Multiselect<T> {
     label: String,
     options: Options<T>,
     selected_options: Vec<usize>,
+    min_selections: usize,
+    max_selections: Option<usize>,
+    keep_selection_order: bool,
+    error: Option<String>,
     help_message: Option<String>,
     max_displayed_options: u16,
+    wrap_around: bool,
     currently_selected_index: usize,
     is_submitted: bool,
+    is_aborted: bool,
     filter: String,
     style: MultiselectionStyle,
+    creator: Option<Creator<T>>,
 }
 
 impl<T> Multiselect<T>
@@ -101,6 +122,88 @@ impl<T> Multiselect<T> {
         self.max_displayed_options = max_options;
         self
     }
+
+    /// Sets the minimum number of options that must be selected to submit the prompt.
+    /// Defaults to 1. It is clamped to `max_selections`
+    pub fn min_selections(mut self, min_selections: usize) -> Self {
+        self.min_selections = min_selections;
+        self.clamp_to_max_selections();
+        self
+    }
+
+    /// Sets the maximum number of options that can be selected. `min_selections` is lowered to
+    /// it if needed and only the first preselected options within the limit stay selected
+    pub fn max_selections(mut self, max_selections: usize) -> Self {
+        self.max_selections = Some(max_selections);
+        self.clamp_to_max_selections();
+        self
+    }
+
+    /// Selects all the options that satisfy the predicate when the prompt is displayed.
+    /// If there are more of them than `max_selections`, only the first ones are selected
+    pub fn preselect<P>(mut self, predicate: P) -> Self
+    where
+        P: Fn(&T) -> bool,
+    {
+        let indices: Vec<usize> = self
+            .options
+            .all_options()
+            .iter()
+            .enumerate()
+            .filter(|(_, option)| predicate(option))
+            .map(|(index, _)| index)
+            .collect();
+        self.add_preselected(indices);
+        self
+    }
+
+    /// Selects the options with the given indices when the prompt is displayed.
+    /// If there are more of them than `max_selections`, only the first ones are selected
+    pub fn preselect_indices<I>(mut self, indices: I) -> Self
+    where
+        I: IntoIterator<Item = usize>,
+    {
+        let options_count = self.options.all_options().len();
+        let indices: Vec<usize> = indices
+            .into_iter()
+            .filter(|&index| index < options_count)
+            .collect();
+        self.add_preselected(indices);
+        self
+    }
+
+    /// If set to `true`, the selected options are returned in the order they were picked.
+    /// Otherwise they keep the order of the original list
+    pub fn keep_selection_order(mut self, keep_selection_order: bool) -> Self {
+        self.keep_selection_order = keep_selection_order;
+        self
+    }
+
+    /// Allows the user to add new options that are not in the list. The function validates the
+    /// filter string and constructs the option from it. It should return `Err(String)` with an
+    /// error message if the option cannot be created.
+    pub fn creatable<F>(mut self, constructor: F) -> Self
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        self.creator = Some(Creator::new(constructor));
+        self
+    }
+
+    /// Sets the prompt style
+    pub fn style(mut self, style: MultiselectionStyle) -> Self {
+        self.options
+            .set_markup(style.label_style.data_markup_mode());
+        self.style = style;
+        self
+    }
+
+    /// Makes the highlight jump to the other end of the list when moving past the first or the
+    /// last option
+    pub fn wrap_around(mut self, wrap_around: bool) -> Self {
+        self.wrap_around = wrap_around;
+        self
+    }
 }
 
 impl<T> MultiOptionPrompt<T> for Multiselect<T> {
@@ -116,6 +219,14 @@ impl<T> MultiOptionPrompt<T> for Multiselect<T> {
         self.currently_selected_index
     }
 
+    fn wraps_around(&self) -> bool {
+        self.wrap_around
+    }
+
+    fn draw_scroll_indicator(&self, text: &str, commands: &mut impl CommandBuffer) {
+        self.style.scroll_indicator_formatting.print(text, commands);
+    }
+
     fn draw_option(
         &self,
         option_index: usize,
@@ -130,25 +241,34 @@ impl<T> MultiOptionPrompt<T> for Multiselect<T> {
 
     fn draw_header(&self, commands: &mut impl CommandBuffer, is_submitted: bool) {
         if is_submitted {
-            commands.set_formatting(&self.style.submitted_formatting);
+            let formatting = &self.style.submitted_formatting;
             for (i, selected_index) in self.selected_options.iter().enumerate() {
                 let selected_option = &self.options.transformed_options()[*selected_index];
-                commands.print(selected_option);
+                self.style
+                    .label_style
+                    .print_data_text(selected_option, formatting, commands);
 
                 if i < self.selected_options.len() - 1 {
-                    commands.print(", ");
+                    formatting.print(", ", commands);
                 }
             }
-            commands.reset_formatting();
         } else {
-            commands.print(&self.filter);
+            self.style.filter_formatting.print(&self.filter, commands);
             commands.print(" ");
-            if let Some(help_message) = self.help_message.as_ref() {
-                commands.set_formatting(&self.style.help_message_formatting);
-                commands.print("[");
-                commands.print(help_message);
-                commands.print("]");
-                commands.reset_formatting();
+            let error = self
+                .error
+                .as_deref()
+                .or_else(|| self.creator.as_ref().and_then(Creator::error));
+            if let Some(error) = error {
+                self.style
+                    .error_formatting
+                    .print(format!("[{}]", error), commands);
+            } else if let Some(help_message) = self.help_message.as_ref() {
+                self.style.label_style.print_help_message(
+                    help_message,
+                    &self.style.help_message_formatting,
+                    commands,
+                );
             }
         }
     }
@@ -156,44 +276,69 @@ impl<T> MultiOptionPrompt<T> for Multiselect<T> {
 
 impl<T> Prompt<Vec<T>> for Multiselect<T> {
     fn draw(&self, commands: &mut impl CommandBuffer) {
-        self.draw_multioption(
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        let has_error =
+            self.error.is_some() || self.creator.as_ref().and_then(Creator::error).is_some();
+        self.draw_multioption_with_state(
             &self.label,
-            self.is_submitted,
+            PromptState::new(self.is_submitted, has_error),
             &self.style.label_style,
             commands,
         );
+
+        if !self.is_submitted && self.is_create_option_shown() {
+            let filter = self.style.label_style.escape(&self.filter);
+            self.style
+                .print_option(&Creator::<T>::label(&filter), false, true, commands);
+            commands.new_line();
+        }
     }
 
     fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Vec<T>> {
+        self.error = None;
+        if let Some(creator) = self.creator.as_mut() {
+            creator.clear_error();
+        }
+
+        if let Some(new_index) = self.navigate(&key) {
+            self.currently_selected_index = new_index;
+            return EventOutcome::Continue;
+        }
+
         match key {
-            Key::Up if self.currently_selected_index > 0 => {
-                self.currently_selected_index -= 1;
+            Key::Char(' ') if self.is_create_option_shown() => {
+                self.create_option();
                 EventOutcome::Continue
             }
-            Key::Down
-                if self.currently_selected_index < self.options.filtered_options().len() - 1 =>
-            {
-                self.currently_selected_index += 1;
+            Key::Tab if self.creator.is_some() && !self.filter.is_empty() => {
+                self.create_option();
                 EventOutcome::Continue
             }
             Key::Char(c) => {
                 if c == ' ' {
-                    let selected_option_index =
-                        self.options.filtered_options()[self.currently_selected_index];
+                    let selected_option_index = match self
+                        .options
+                        .filtered_options()
+                        .get(self.currently_selected_index)
+                    {
+                        Some(&index) => index,
+                        None => return EventOutcome::Continue,
+                    };
                     let existing_value_index = self
                         .selected_options
                         .iter()
-                        .enumerate()
-                        .find(|&x| *x.1 == selected_option_index)
-                        .map(|x| x.0);
+                        .position(|&x| x == selected_option_index);
 
                     if let Some(i) = existing_value_index {
                         self.selected_options.remove(i);
-                    } else {
-                        self.selected_options.push(selected_option_index);
+                    } else if !self.add_to_selection(vec![selected_option_index]) {
+                        return EventOutcome::Continue;
                     }
 
-                    if self.filter.len() > 0 {
+                    if !self.filter.is_empty() {
                         self.filter.clear();
                         self.options.filter(&self.filter);
                         self.currently_selected_index = 0;
@@ -206,45 +351,278 @@ impl<T> Prompt<Vec<T>> for Multiselect<T> {
                     EventOutcome::Continue
                 }
             }
-            Key::Backspace if self.filter.len() > 0 => {
+            Key::Ctrl('a') => {
+                let filtered_options = self.options.filtered_options().to_vec();
+                self.add_to_selection(filtered_options);
+                EventOutcome::Continue
+            }
+            Key::Ctrl('d') => {
+                let filtered_options = self.options.filtered_options();
+                self.selected_options
+                    .retain(|index| !filtered_options.contains(index));
+                EventOutcome::Continue
+            }
+            Key::Ctrl('r') => {
+                let filtered_options = self.options.filtered_options().to_vec();
+                let (to_deselect, to_select): (Vec<usize>, Vec<usize>) = filtered_options
+                    .into_iter()
+                    .partition(|index| self.selected_options.contains(index));
+                let selected_count =
+                    self.selected_options.len() - to_deselect.len() + to_select.len();
+
+                if self.check_max_selections(selected_count) {
+                    self.selected_options
+                        .retain(|index| !to_deselect.contains(index));
+                    self.selected_options.extend(to_select);
+                }
+                EventOutcome::Continue
+            }
+            Key::Backspace if !self.filter.is_empty() => {
                 self.filter.pop();
                 self.options.filter(&self.filter);
                 self.currently_selected_index = 0;
                 EventOutcome::Continue
             }
-            Key::Enter if self.selected_options.len() > 0 => {
+            Key::Enter if self.selected_options.len() < self.min_selections => {
+                self.error = Some(match self.min_selections {
+                    1 => "Select at least one option".into(),
+                    n => format!("Select at least {} options", n),
+                });
+                EventOutcome::Continue
+            }
+            Key::Enter => {
                 self.is_submitted = true;
-                self.selected_options.sort();
-
-                let mut result = vec![];
-                for selected_option_index in self.selected_options.iter().rev() {
-                    let selected_option = self
-                        .options
-                        .all_options_mut()
-                        .remove(*selected_option_index);
-                    result.push(selected_option);
+                if !self.keep_selection_order {
+                    self.selected_options.sort();
                 }
 
-                EventOutcome::Done(result)
+                EventOutcome::Done(self.options.take_options(&self.selected_options))
             }
             Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
             _ => EventOutcome::Continue,
         }
     }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
 }
 
 impl<T> Multiselect<T> {
-    fn new_internal(label: String, options: Options<T>) -> Self {
+    fn new_internal(label: String, mut options: Options<T>) -> Self {
+        let style = MultiselectionStyle::default();
+        options.set_markup(style.label_style.data_markup_mode());
+
         Multiselect {
             label,
             options,
             selected_options: vec![],
+            min_selections: 1,
+            max_selections: None,
+            keep_selection_order: false,
+            error: None,
             help_message: Some(DEFAULT_HELP_MESSAGE.into()),
             max_displayed_options: DEFAUTL_MAX_OPTIONS,
+            wrap_around: false,
             currently_selected_index: 0,
             is_submitted: false,
+            is_aborted: false,
             filter: String::new(),
-            style: MultiselectionStyle::default(),
+            style,
+            creator: None,
         }
     }
+
+    /// Adds the given options to the selection, skipping those already selected. Does nothing
+    /// and sets the error if that would exceed the maximum number of selections.
+    /// Returns whether the options were added
+    fn add_to_selection(&mut self, indices: Vec<usize>) -> bool {
+        let mut new_indices: Vec<usize> = vec![];
+        for index in indices {
+            if !self.selected_options.contains(&index) && !new_indices.contains(&index) {
+                new_indices.push(index);
+            }
+        }
+
+        if !self.check_max_selections(self.selected_options.len() + new_indices.len()) {
+            return false;
+        }
+
+        self.selected_options.extend(new_indices);
+        true
+    }
+
+    fn add_preselected(&mut self, indices: Vec<usize>) {
+        for index in indices {
+            if !self.selected_options.contains(&index) {
+                self.selected_options.push(index);
+            }
+        }
+        self.clamp_to_max_selections();
+    }
+
+    fn clamp_to_max_selections(&mut self) {
+        if let Some(max_selections) = self.max_selections {
+            self.min_selections = self.min_selections.min(max_selections);
+            self.selected_options.truncate(max_selections);
+        }
+    }
+
+    fn is_create_option_shown(&self) -> bool {
+        self.creator.is_some() && Creator::is_shown(&self.filter, &self.options)
+    }
+
+    fn create_option(&mut self) {
+        if !self.check_max_selections(self.selected_options.len() + 1) {
+            return;
+        }
+
+        let created_option_index = match self.creator.as_mut() {
+            Some(creator) => creator.create(&self.filter, &mut self.options),
+            None => None,
+        };
+
+        if let Some(option_index) = created_option_index {
+            self.add_to_selection(vec![option_index]);
+            self.filter.clear();
+            self.options.filter(&self.filter);
+            self.currently_selected_index = option_index;
+        }
+    }
+
+    fn check_max_selections(&mut self, selected_count: usize) -> bool {
+        match self.max_selections {
+            Some(max_selections) if selected_count > max_selections => {
+                self.error = Some(match max_selections {
+                    1 => "Only one option can be selected".into(),
+                    n => format!("At most {} options can be selected", n),
+                });
+                false
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt() -> Multiselect<&'static str> {
+        Multiselect::new("Fruits", ["apple", "banana", "cherry", "date"].into_iter())
+    }
+
+    fn submit(mut prompt: Multiselect<&'static str>) -> Vec<&'static str> {
+        match prompt.on_key_pressed(Key::Enter) {
+            EventOutcome::Done(result) => result,
+            _ => panic!("The prompt is expected to be submitted"),
+        }
+    }
+
+    #[test]
+    fn returns_options_in_original_order() {
+        let mut prompt = prompt();
+        prompt.on_key_pressed(Key::End);
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Home);
+        prompt.on_key_pressed(Key::Char(' '));
+
+        assert_eq!(submit(prompt), vec!["apple", "date"]);
+    }
+
+    #[test]
+    fn returns_options_in_selection_order() {
+        let mut prompt = prompt().keep_selection_order(true);
+        prompt.on_key_pressed(Key::End);
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Home);
+        prompt.on_key_pressed(Key::Char(' '));
+
+        assert_eq!(submit(prompt), vec!["date", "apple"]);
+    }
+
+    #[test]
+    fn refuses_to_exceed_max_selections() {
+        let mut prompt = prompt().max_selections(2).preselect_indices([0, 1]);
+        prompt.on_key_pressed(Key::End);
+        prompt.on_key_pressed(Key::Char(' '));
+
+        assert!(prompt.error.is_some());
+        assert_eq!(submit(prompt), vec!["apple", "banana"]);
+    }
+
+    #[test]
+    fn clamps_preselection_and_min_selections_to_max_selections() {
+        let clamped = prompt()
+            .preselect(|f| f.contains('a'))
+            .max_selections(2)
+            .min_selections(3);
+
+        assert!(clamped.error.is_none());
+        assert_eq!(clamped.min_selections, 2);
+        assert_eq!(submit(clamped), vec!["apple", "banana"]);
+
+        let clamped = prompt().max_selections(1).preselect_indices([3, 1]);
+        assert_eq!(submit(clamped), vec!["date"]);
+    }
+
+    #[test]
+    fn refuses_to_submit_below_min_selections() {
+        let mut prompt = prompt().min_selections(2).preselect(|f| f.starts_with('c'));
+
+        assert!(matches!(
+            prompt.on_key_pressed(Key::Enter),
+            EventOutcome::Continue
+        ));
+        assert!(prompt.error.is_some());
+    }
+
+    #[test]
+    fn selects_clears_and_inverts_filtered_options() {
+        let mut prompt = prompt().preselect_indices([0]);
+        prompt.on_key_pressed(Key::Char('a'));
+        prompt.on_key_pressed(Key::Char('n'));
+        prompt.on_key_pressed(Key::Ctrl('r'));
+        assert_eq!(prompt.selected_options, vec![0, 1]);
+
+        prompt.on_key_pressed(Key::Ctrl('d'));
+        assert_eq!(prompt.selected_options, vec![0]);
+
+        prompt.on_key_pressed(Key::Ctrl('a'));
+        assert_eq!(submit(prompt), vec!["apple", "banana"]);
+    }
+
+    #[test]
+    fn creates_and_selects_new_option() {
+        let mut prompt = Multiselect::new("Tags", ["rust".to_string()].into_iter())
+            .creatable(|s| Ok(s.to_owned()));
+        prompt.on_key_pressed(Key::Char('c'));
+        prompt.on_key_pressed(Key::Char('l'));
+        prompt.on_key_pressed(Key::Char('i'));
+        prompt.on_key_pressed(Key::Char(' '));
+
+        assert!(matches!(
+            prompt.on_key_pressed(Key::Enter),
+            EventOutcome::Done(tags) if tags == vec!["cli".to_string()]
+        ));
+    }
+
+    #[test]
+    fn rejects_duplicate_and_excess_created_options() {
+        let mut prompt = Multiselect::new("Tags", ["rust".to_string()].into_iter())
+            .creatable(|s| Ok(s.to_owned()))
+            .max_selections(1);
+        for c in "rust".chars() {
+            prompt.on_key_pressed(Key::Char(c));
+        }
+        prompt.on_key_pressed(Key::Tab);
+        assert!(prompt.creator.as_ref().unwrap().error().is_some());
+        assert_eq!(prompt.options.all_options().len(), 1);
+
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Char('x'));
+        prompt.on_key_pressed(Key::Tab);
+        assert!(prompt.error.is_some());
+        assert_eq!(prompt.options.all_options().len(), 1);
+    }
 }
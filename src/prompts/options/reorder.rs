@@ -0,0 +1,370 @@
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{options::Options, AbortReason, EventOutcome, Prompt},
+    style::ReorderStyle,
+};
+
+use super::multioption_prompt::MultiOptionPrompt;
+
+const DEFAULT_OPTIONS_COUNT: u16 = 10;
+const DEFAULT_HELP_MESSAGE: &str = "Space to grab, arrows to move, enter to submit";
+
+/// Prompt that allows to put the options in the desired order.
+/// Space grabs the highlighted option, after which the arrow keys move it around the list.
+/// Pressing Space again drops it. Shift+Up and Shift+Down move the highlighted option without
+/// grabbing it. The options are returned in their new order.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{Reorder, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// fn main() {
+///     let services = ["database", "cache", "api", "web"];
+///
+///     let prompt = Reorder::new("Set the deploy order", services.into_iter());
+///     let order : Result<Vec<&str>, AbortReason> = prompt.display();
+///     match order {
+///         Ok(services) => {
+///             for service in services {
+///                 // Deploy the service
+///             }
+///         }
+///         Err(abort_reason) => println!("Prompt is aborted because of {:?}", abort_reason),
+///     }
+/// }
+/// ```
+pub struct Reorder<T> {
+    label: String,
+    options: Options<T>,
+    positions: Vec<usize>,
+    current_position: usize,
+    is_grabbed: bool,
+    top_count: Option<usize>,
+    max_options: u16,
+    help_message: Option<String>,
+    is_submitted: bool,
+    is_aborted: bool,
+    style: ReorderStyle,
+}
+
+impl<T> Reorder<T>
+where
+    T: Into<String> + Clone,
+{
+    /// Create new prompt with the given label and the iterator over a type that is convertable to
+    /// `String`
+    pub fn new<S, I>(label: S, options: I) -> Self
+    where
+        S: Into<String>,
+        I: Iterator<Item = T>,
+    {
+        let options = Options::from_iter(options);
+        Self::new_internal(label.into(), options)
+    }
+}
+
+impl<T> Reorder<T> {
+    /// Create new prompt with the given label and a transformation function that will convert the
+    /// iterator items to strings
+    pub fn new_transformed<S, I, F>(label: S, options: I, transformation: F) -> Self
+    where
+        S: Into<String>,
+        I: Iterator<Item = T>,
+        F: Fn(&T) -> String,
+    {
+        let options = Options::from_iter_transformed(options, transformation);
+        Self::new_internal(label.into(), options)
+    }
+
+    /// Makes the prompt return only the first `count` options, which is useful for ranking the
+    /// top choices. The rest of the options are displayed as unranked
+    pub fn top(mut self, count: usize) -> Self {
+        self.top_count = Some(count);
+        self
+    }
+
+    /// Set help message to be displayed after the label
+    pub fn help_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.help_message = Some(message.into());
+        self
+    }
+
+    /// Makes prompt not to display the help message
+    pub fn dont_display_help_message(mut self) -> Self {
+        self.help_message = None;
+        self
+    }
+
+    /// Sets the maximum number of options that can be displayed on the screen
+    pub fn max_displayed_options(mut self, max_options: u16) -> Self {
+        self.max_options = max_options;
+        self
+    }
+
+    /// Sets the prompt style
+    pub fn style(mut self, style: ReorderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn new_internal(label: String, options: Options<T>) -> Self {
+        let mut positions = vec![0; options.all_options().len()];
+        for (position, &index) in options.filtered_options().iter().enumerate() {
+            positions[index] = position;
+        }
+
+        Reorder {
+            label,
+            options,
+            positions,
+            current_position: 0,
+            is_grabbed: false,
+            top_count: None,
+            max_options: DEFAULT_OPTIONS_COUNT,
+            help_message: Some(DEFAULT_HELP_MESSAGE.into()),
+            is_submitted: false,
+            is_aborted: false,
+            style: ReorderStyle::default(),
+        }
+    }
+
+    fn returned_count(&self) -> usize {
+        let options_count = self.options.filtered_options().len();
+        self.top_count
+            .map_or(options_count, |c| c.min(options_count))
+    }
+
+    fn move_current_option(&mut self, new_position: usize) {
+        let options_count = self.options.filtered_options().len();
+        if self.current_position >= options_count || new_position >= options_count {
+            return;
+        }
+
+        self.options
+            .move_filtered_option(self.current_position, new_position);
+
+        let start = self.current_position.min(new_position);
+        let end = self.current_position.max(new_position);
+        for position in start..=end {
+            let index = self.options.filtered_options()[position];
+            self.positions[index] = position;
+        }
+        self.current_position = new_position;
+    }
+}
+
+impl<T> MultiOptionPrompt<T> for Reorder<T> {
+    fn max_options_count(&self) -> u16 {
+        self.max_options
+    }
+
+    fn options(&self) -> &Options<T> {
+        &self.options
+    }
+
+    fn currently_selected_index(&self) -> usize {
+        self.current_position
+    }
+
+    fn draw_scroll_indicator(&self, text: &str, cmd_buffer: &mut impl CommandBuffer) {
+        self.style
+            .scroll_indicator_formatting
+            .print(text, cmd_buffer);
+    }
+
+    fn draw_option(
+        &self,
+        option_index: usize,
+        option_label: &str,
+        is_selected: bool,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        let position = self.positions[option_index];
+        let is_ranked = position < self.returned_count();
+
+        let (marker, formatting) = match (is_selected, self.is_grabbed) {
+            (true, true) => (
+                &self.style.grabbed_marker,
+                &self.style.grabbed_option_formatting,
+            ),
+            (true, false) => (
+                &self.style.highlighted_marker,
+                &self.style.highlighted_option_formatting,
+            ),
+            (false, _) if is_ranked => (
+                &self.style.not_highlighted_marker,
+                &self.style.option_formatting,
+            ),
+            (false, _) => (
+                &self.style.not_highlighted_marker,
+                &self.style.unranked_option_formatting,
+            ),
+        };
+
+        marker.print(cmd_buffer);
+        let rank = if is_ranked {
+            format!("{}. ", position + 1)
+        } else {
+            String::new()
+        };
+        formatting.print(rank, cmd_buffer);
+        self.style
+            .label_style
+            .print_data_text(option_label, formatting, cmd_buffer);
+    }
+
+    fn draw_header(&self, commands: &mut impl CommandBuffer, is_submitted: bool) {
+        if is_submitted {
+            let labels: Vec<&str> = self
+                .options
+                .filtered_options()
+                .iter()
+                .take(self.returned_count())
+                .map(|&i| self.options.transformed_options()[i].as_str())
+                .collect();
+            self.style.label_style.print_data_text(
+                &labels.join(", "),
+                &self.style.submitted_formatting,
+                commands,
+            );
+        } else if let Some(help_message) = self.help_message.as_ref() {
+            self.style.label_style.print_help_message(
+                help_message,
+                &self.style.help_message_formatting,
+                commands,
+            );
+        }
+    }
+}
+
+impl<T> Prompt<Vec<T>> for Reorder<T> {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        self.draw_multioption(
+            &self.label,
+            self.is_submitted,
+            &self.style.label_style,
+            commands,
+        );
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Vec<T>> {
+        if let Some(new_position) = self.navigate(&key) {
+            if self.is_grabbed {
+                self.move_current_option(new_position);
+            } else {
+                self.current_position = new_position;
+            }
+            return EventOutcome::Continue;
+        }
+
+        match key {
+            Key::ShiftUp if self.current_position > 0 => {
+                self.move_current_option(self.current_position - 1);
+                EventOutcome::Continue
+            }
+            Key::ShiftDown if self.current_position + 1 < self.options.filtered_options().len() => {
+                self.move_current_option(self.current_position + 1);
+                EventOutcome::Continue
+            }
+            Key::Char(' ') if !self.options.filtered_options().is_empty() => {
+                self.is_grabbed = !self.is_grabbed;
+                EventOutcome::Continue
+            }
+            Key::Enter => {
+                self.is_submitted = true;
+                self.is_grabbed = false;
+
+                let order: Vec<usize> = self
+                    .options
+                    .filtered_options()
+                    .iter()
+                    .take(self.returned_count())
+                    .copied()
+                    .collect();
+                EventOutcome::Done(self.options.take_options(&order))
+            }
+            Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submit(mut prompt: Reorder<&'static str>) -> Vec<&'static str> {
+        match prompt.on_key_pressed(Key::Enter) {
+            EventOutcome::Done(result) => result,
+            _ => panic!("The prompt is expected to be submitted"),
+        }
+    }
+
+    #[test]
+    fn moves_grabbed_option() {
+        let mut prompt = Reorder::new("Order", ["a", "b", "c", "d"].into_iter());
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Down);
+        prompt.on_key_pressed(Key::Down);
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Down);
+
+        assert_eq!(prompt.current_position, 3);
+        assert_eq!(submit(prompt), vec!["b", "c", "a", "d"]);
+    }
+
+    #[test]
+    fn moves_option_with_shift_arrows() {
+        let mut prompt = Reorder::new("Order", ["a", "b", "c"].into_iter());
+        prompt.on_key_pressed(Key::End);
+        prompt.on_key_pressed(Key::ShiftUp);
+        prompt.on_key_pressed(Key::ShiftUp);
+        prompt.on_key_pressed(Key::ShiftUp);
+
+        assert_eq!(submit(prompt), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn returns_only_top_options() {
+        let mut prompt = Reorder::new("Order", ["a", "b", "c"].into_iter()).top(2);
+        prompt.on_key_pressed(Key::End);
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Home);
+
+        assert_eq!(submit(prompt), vec!["c", "a"]);
+    }
+
+    #[test]
+    fn ignores_grab_and_move_keys_without_options() {
+        let mut prompt = Reorder::new("Order", std::iter::empty::<&str>());
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Down);
+        prompt.on_key_pressed(Key::ShiftDown);
+
+        assert!(!prompt.is_grabbed);
+        assert!(submit(prompt).is_empty());
+    }
+
+    #[test]
+    fn draws_ranks_of_moved_options() {
+        let mut prompt = Reorder::new("Order", ["a", "b", "c"].into_iter());
+        prompt.on_key_pressed(Key::End);
+        prompt.on_key_pressed(Key::Char(' '));
+        prompt.on_key_pressed(Key::Home);
+
+        let mut commands = crate::engine::RecordingBuffer::default();
+        prompt.draw(&mut commands);
+        assert!(commands.text().contains("1. c\n  2. a\n  3. b"));
+    }
+}
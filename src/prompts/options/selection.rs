@@ -1,17 +1,29 @@
+use std::marker::PhantomData;
+
 use crate::{
     engine::CommandBuffer,
     input::Key,
     prompts::{options::Options, AbortReason, EventOutcome, Prompt},
-    style::SelectionStyle,
+    style::{PromptState, SelectionStyle},
 };
 
-use super::multioption_prompt::MultiOptionPrompt;
+use super::{creatable::Creator, multioption_prompt::MultiOptionPrompt};
 
 const DEFAULT_OPTIONS_COUNT: u16 = 5;
 
 /// Prompt that allows to select one option from the given list.
 /// Supports filtering and moving the selection with arrow keys.
 ///
+/// If the prompt is made `creatable`, the user can also type in a new value. When the filter
+/// matches nothing, a synthetic "Create" option appears in the list. Pressing Tab creates the
+/// value from the filter string right away.
+///
+/// With `shortcuts` enabled, the options are prefixed with numbers or letters and pressing one
+/// of them picks the corresponding option instantly.
+///
+/// By default the prompt returns the selected option itself. Use `return_index` or
+/// `return_index_and_item` to get its index in the original list instead or along with it.
+///
 /// ```rust
 /// use cli_prompts::{
 ///      prompts::{Selection, AbortReason},
@@ -35,14 +47,135 @@ const DEFAULT_OPTIONS_COUNT: u16 = 5;
 ///     }
 /// }
 /// ```
-pub struct Selection<T> {
+///
+/// Options can also be borrowed from a slice, in which case they don't need to be `Clone`:
+///
+/// ```rust
+/// use cli_prompts::{prompts::Selection, DisplayPrompt};
+///
+/// struct Branch {
+///     name: String,
+/// }
+///
+/// let branches = vec![
+///     Branch { name: "main".into() },
+///     Branch { name: "develop".into() },
+/// ];
+///
+/// let prompt = Selection::from_slice_transformed("Checkout branch", &branches, |b| b.name.clone())
+///                 .default_option(|b| b.name == "develop")
+///                 .return_index();
+/// if let Ok(index) = prompt.display() {
+///     println!("Checking out {}", branches[index].name);
+/// }
+/// ```
+pub struct Selection<T, M = ReturnItem> {
     label: String,
     options: Options<T>,
     current_selection: usize,
     max_options: u16,
+    wrap_around: bool,
     current_filter: String,
+    help_message: Option<String>,
     is_submitted: bool,
+    is_aborted: bool,
     style: SelectionStyle,
+    creator: Option<Creator<T>>,
+    shortcuts: Option<Shortcuts>,
+    confirm_shortcut: bool,
+    return_mode: PhantomData<M>,
+}
+
+/// Kind of the keys that are assigned to the options of the `Selection` prompt to pick them
+/// quickly
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shortcuts {
+    /// The first nine options are picked with the keys from `1` to `9`. Other keys still filter
+    /// the options
+    Numbers,
+
+    /// The first 26 options are picked with the keys from `a` to `z`. Filtering is disabled
+    Letters,
+}
+
+impl Shortcuts {
+    /// Returns the key assigned to the option with the given index, if there is any
+    pub fn key(&self, option_index: usize) -> Option<char> {
+        let (first_key, keys_count) = match self {
+            Shortcuts::Numbers => (b'1', 9),
+            Shortcuts::Letters => (b'a', 26),
+        };
+
+        if option_index < keys_count {
+            Some((first_key + option_index as u8) as char)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of the option the given key is assigned to, if there is any
+    pub fn option_index(&self, key: char) -> Option<usize> {
+        match (self, key) {
+            (Shortcuts::Numbers, '1'..='9') => Some(key as usize - '1' as usize),
+            (Shortcuts::Letters, 'a'..='z') => Some(key as usize - 'a' as usize),
+            _ => None,
+        }
+    }
+}
+
+/// Defines what the `Selection` prompt returns once it is submitted
+pub trait ReturnMode<T> {
+    /// Type of the prompt's result
+    type Output;
+
+    /// Produces the result from the option with the given index in the original list
+    fn output(options: &mut Options<T>, option_index: usize) -> Self::Output;
+}
+
+/// Makes `Selection` return the selected option
+pub struct ReturnItem;
+
+/// Makes `Selection` return the index of the selected option in the original list
+pub struct ReturnIndex;
+
+/// Makes `Selection` return both the index of the selected option in the original list and the
+/// option itself
+pub struct ReturnIndexAndItem;
+
+/// Makes the `creatable` `Selection` return the selected or the created option. The created
+/// options are not in the original list, so this mode can't be switched to returning indices
+pub struct ReturnCreatable;
+
+impl<T> ReturnMode<T> for ReturnItem {
+    type Output = T;
+
+    fn output(options: &mut Options<T>, option_index: usize) -> T {
+        options.all_options_mut().swap_remove(option_index)
+    }
+}
+
+impl<T> ReturnMode<T> for ReturnIndex {
+    type Output = usize;
+
+    fn output(_: &mut Options<T>, option_index: usize) -> usize {
+        option_index
+    }
+}
+
+impl<T> ReturnMode<T> for ReturnCreatable {
+    type Output = T;
+
+    fn output(options: &mut Options<T>, option_index: usize) -> T {
+        ReturnItem::output(options, option_index)
+    }
+}
+
+impl<T> ReturnMode<T> for ReturnIndexAndItem {
+    type Output = (usize, T);
+
+    fn output(options: &mut Options<T>, option_index: usize) -> (usize, T) {
+        (option_index, ReturnItem::output(options, option_index))
+    }
 }
 
 impl<T> Selection<T> {
@@ -60,6 +193,30 @@ impl<T> Selection<T> {
     }
 }
 
+impl<'a, T> Selection<&'a T> {
+
+    /// Create new prompt with the given label that borrows its options from a slice
+    pub fn from_slice<S>(label: S, options: &'a [T]) -> Self
+    where
+        T: AsRef<str>,
+        S: Into<String>,
+    {
+        Self::from_slice_transformed(label, options, |option| option.as_ref().to_owned())
+    }
+
+    /// Create new prompt with the given label that borrows its options from a slice and a
+    /// transformation function that will convert them to strings
+    pub fn from_slice_transformed<S, F>(label: S, options: &'a [T], transformation: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&T) -> String,
+    {
+        let options =
+            Options::from_iter_transformed(options.iter(), |option| transformation(option));
+        Self::new_internal(label.into(), options)
+    }
+}
+
 impl<T> Selection<T> {
 
     /// Create new prompt with the given label and a transformation function that will convert the
@@ -74,32 +231,157 @@ impl<T> Selection<T> {
         Self::new_internal(label.into(), options)
     }
 
+    /// Allow the user to enter a new value that is not in the list. The function validates the
+    /// filter string and constructs the value from it. It should return `Err(String)` with an
+    /// error message if the value cannot be created.
+    ///
+    /// The created value is not in the original list, so the returned prompt has no
+    /// `return_index` or `return_index_and_item`:
+    ///
+    /// ```rust,compile_fail
+    /// use cli_prompts::prompts::Selection;
+    ///
+    /// let prompt = Selection::new("Tag", ["v1"].into_iter())
+    ///     .creatable(|s| Err(s.to_owned()))
+    ///     .return_index();
+    /// ```
+    pub fn creatable<F>(self, constructor: F) -> Selection<T, ReturnCreatable>
+    where
+        F: Fn(&str) -> Result<T, String> + 'static,
+    {
+        let mut prompt = self.with_return_mode();
+        prompt.creator = Some(Creator::new(constructor));
+        prompt
+    }
+
+    /// Make the prompt return the index of the selected option in the original list
+    pub fn return_index(self) -> Selection<T, ReturnIndex> {
+        self.with_return_mode()
+    }
+
+    /// Make the prompt return the index of the selected option in the original list along with
+    /// the option itself
+    pub fn return_index_and_item(self) -> Selection<T, ReturnIndexAndItem> {
+        self.with_return_mode()
+    }
+
+    fn new_internal(label: String, mut options: Options<T>) -> Self {
+        let style = SelectionStyle::default();
+        options.set_markup(style.label_style.data_markup_mode());
+
+        Selection {
+            label,
+            options,
+            current_selection: 0_usize,
+            max_options: DEFAULT_OPTIONS_COUNT,
+            wrap_around: false,
+            current_filter: String::new(),
+            help_message: None,
+            is_submitted: false,
+            is_aborted: false,
+            style,
+            creator: None,
+            shortcuts: None,
+            confirm_shortcut: false,
+            return_mode: PhantomData,
+        }
+    }
+}
+
+impl<T, M> Selection<T, M> {
+
     /// Set maximum number of options that can be displayed on the screen
     pub fn displayed_options_count(mut self, options_count: u16) -> Self {
         self.max_options = options_count;
         self
     }
 
+    /// Makes the selection jump to the other end of the list when moving past the first or the
+    /// last option
+    pub fn wrap_around(mut self, wrap_around: bool) -> Self {
+        self.wrap_around = wrap_around;
+        self
+    }
+
+    /// Set help message to be displayed after the filter string
+    pub fn help_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.help_message = Some(message.into());
+        self
+    }
+
     /// Set the prompt style
     pub fn style(mut self, style: SelectionStyle) -> Self {
+        self.options
+            .set_markup(style.label_style.data_markup_mode());
         self.style = style;
         self
     }
 
-    fn new_internal(label: String, options: Options<T>) -> Self {
+    /// Highlight the option with the given index when the prompt is displayed
+    pub fn default_index(mut self, index: usize) -> Self {
+        if index < self.options.all_options().len() {
+            self.current_selection = index;
+        }
+        self
+    }
+
+    /// Highlight the first option that satisfies the predicate when the prompt is displayed
+    pub fn default_option<P>(self, predicate: P) -> Self
+    where
+        P: Fn(&T) -> bool,
+    {
+        match self.options.all_options().iter().position(predicate) {
+            Some(index) => self.default_index(index),
+            None => self,
+        }
+    }
+
+    /// Prefix the options with numbers or letters. Pressing one of them picks the corresponding
+    /// option and submits the prompt
+    pub fn shortcuts(mut self, shortcuts: Shortcuts) -> Self {
+        self.shortcuts = Some(shortcuts);
+        self
+    }
+
+    /// If set to `true`, pressing a shortcut only highlights the option and the choice has to be
+    /// confirmed with Enter
+    pub fn confirm_shortcut(mut self, confirm_shortcut: bool) -> Self {
+        self.confirm_shortcut = confirm_shortcut;
+        self
+    }
+
+    fn draw_shortcut(&self, option_index: usize, cmd_buffer: &mut impl CommandBuffer) {
+        match self.shortcuts.map(|shortcuts| shortcuts.key(option_index)) {
+            Some(Some(key)) => self
+                .style
+                .shortcut_formatting
+                .print(format!("{}) ", key), cmd_buffer),
+            Some(None) => cmd_buffer.print("   "),
+            None => {}
+        }
+    }
+
+    fn with_return_mode<R>(self) -> Selection<T, R> {
         Selection {
-            label,
-            options,
-            current_selection: 0_usize,
-            max_options: DEFAULT_OPTIONS_COUNT,
-            current_filter: String::new(),
-            is_submitted: false,
-            style: SelectionStyle::default(),
+            label: self.label,
+            options: self.options,
+            current_selection: self.current_selection,
+            max_options: self.max_options,
+            wrap_around: self.wrap_around,
+            current_filter: self.current_filter,
+            help_message: self.help_message,
+            is_submitted: self.is_submitted,
+            is_aborted: self.is_aborted,
+            style: self.style,
+            creator: self.creator,
+            shortcuts: self.shortcuts,
+            confirm_shortcut: self.confirm_shortcut,
+            return_mode: PhantomData,
         }
     }
 }
 
-impl<T> MultiOptionPrompt<T> for Selection<T> {
+impl<T, M> MultiOptionPrompt<T> for Selection<T, M> {
     fn max_options_count(&self) -> u16 {
         self.max_options
     }
@@ -112,23 +394,39 @@ impl<T> MultiOptionPrompt<T> for Selection<T> {
         self.current_selection
     }
 
+    fn wraps_around(&self) -> bool {
+        self.wrap_around
+    }
+
+    fn draw_scroll_indicator(&self, text: &str, cmd_buffer: &mut impl CommandBuffer) {
+        self.style
+            .scroll_indicator_formatting
+            .print(text, cmd_buffer);
+    }
+
     fn draw_option(
         &self,
-        _: usize,
+        option_index: usize,
         option_label: &str,
         is_selected: bool,
         cmd_buffer: &mut impl CommandBuffer,
     ) {
         if is_selected {
             self.style.selected_marker.print(cmd_buffer);
-            self.style
-                .selected_option_formatting
-                .print(option_label, cmd_buffer);
+            self.draw_shortcut(option_index, cmd_buffer);
+            self.style.label_style.print_data_text(
+                option_label,
+                &self.style.selected_option_formatting,
+                cmd_buffer,
+            );
         } else {
             self.style.not_selected_marker.print(cmd_buffer);
-            self.style
-                .option_formatting
-                .print(option_label, cmd_buffer)
+            self.draw_shortcut(option_index, cmd_buffer);
+            self.style.label_style.print_data_text(
+                option_label,
+                &self.style.option_formatting,
+                cmd_buffer,
+            );
         }
     }
 
@@ -136,28 +434,75 @@ impl<T> MultiOptionPrompt<T> for Selection<T> {
         if is_submitted {
             let selected_option_index = self.options.filtered_options()[self.current_selection];
             let selected_option = &self.options.transformed_options()[selected_option_index];
-            self.style
-                .submitted_formatting
-                .print(selected_option, commands);
+            self.style.label_style.print_data_text(
+                selected_option,
+                &self.style.submitted_formatting,
+                commands,
+            );
         } else {
             self.style
                 .filter_formatting
                 .print(&self.current_filter, commands);
+
+            if let Some(error) = self.creator.as_ref().and_then(Creator::error) {
+                commands.print(" ");
+                self.style
+                    .error_formatting
+                    .print(format!("[{}]", error), commands);
+            } else if let Some(help_message) = self.help_message.as_ref() {
+                commands.print(" ");
+                self.style.label_style.print_help_message(
+                    help_message,
+                    &self.style.help_message_formatting,
+                    commands,
+                );
+            }
         }
     }
 }
 
-impl<T> Prompt<T> for Selection<T> {
+impl<T, M> Prompt<M::Output> for Selection<T, M>
+where
+    M: ReturnMode<T>,
+{
     fn draw(&self, commands: &mut impl CommandBuffer) {
-        self.draw_multioption(
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        self.draw_multioption_with_state(
             &self.label,
-            self.is_submitted,
+            PromptState::new(
+                self.is_submitted,
+                self.creator.as_ref().and_then(Creator::error).is_some(),
+            ),
             &self.style.label_style,
             commands,
-        )
+        );
+
+        if !self.is_submitted && self.is_create_option_shown() {
+            self.style.selected_marker.print(commands);
+            self.style
+                .selected_option_formatting
+                .print(Creator::<T>::label(&self.current_filter), commands);
+            commands.new_line();
+        }
     }
 
-    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<T> {
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<M::Output> {
+        if let Some(creator) = self.creator.as_mut() {
+            creator.clear_error();
+        }
+
+        if let Some(new_selection) = self.navigate(&key) {
+            self.current_selection = new_selection;
+            return EventOutcome::Continue;
+        }
+
+        if let (Key::Char(c), Some(shortcuts)) = (&key, self.shortcuts) {
+            return self.on_shortcut_pressed(shortcuts, *c);
+        }
+
         match key {
             Key::Char(c) => {
                 self.current_filter.push(c);
@@ -165,28 +510,200 @@ impl<T> Prompt<T> for Selection<T> {
                 self.current_selection = 0;
                 EventOutcome::Continue
             }
-            Key::Backspace if self.current_filter.len() > 0 => {
+            Key::Backspace if !self.current_filter.is_empty() => {
                 self.current_filter.pop();
                 self.options.filter(&self.current_filter);
                 self.current_selection = 0;
                 EventOutcome::Continue
             }
-            Key::Up if self.current_selection > 0 => {
-                self.current_selection -= 1;
-                EventOutcome::Continue
-            }
-            Key::Down if self.current_selection < self.options.filtered_options().len() - 1 => {
-                self.current_selection += 1;
-                EventOutcome::Continue
+            Key::Tab if self.creator.is_some() && !self.current_filter.is_empty() => {
+                self.create_option()
             }
-            Key::Enter if self.options.filtered_options().len() > 0 => {
+            Key::Enter if self.is_create_option_shown() => self.create_option(),
+            Key::Enter if !self.options.filtered_options().is_empty() => {
                 self.is_submitted = true;
                 let selected_option_index = self.options.filtered_options()[self.current_selection];
-                let result = self.options.all_options_mut().remove(selected_option_index);
-                EventOutcome::Done(result)
+                EventOutcome::Done(M::output(&mut self.options, selected_option_index))
             }
             Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
             _ => EventOutcome::Continue,
         }
     }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
+
+impl<T, M> Selection<T, M>
+where
+    M: ReturnMode<T>,
+{
+    fn on_shortcut_pressed(&mut self, shortcuts: Shortcuts, key: char) -> EventOutcome<M::Output> {
+        let option_index = match shortcuts.option_index(key) {
+            Some(option_index) => option_index,
+            None if shortcuts == Shortcuts::Numbers => {
+                self.current_filter.push(key);
+                self.options.filter(&self.current_filter);
+                self.current_selection = 0;
+                return EventOutcome::Continue;
+            }
+            None => return EventOutcome::Continue,
+        };
+
+        let filtered_index = self
+            .options
+            .filtered_options()
+            .iter()
+            .position(|&i| i == option_index);
+
+        if let Some(filtered_index) = filtered_index {
+            self.current_selection = filtered_index;
+            if !self.confirm_shortcut {
+                return self.on_key_pressed(Key::Enter);
+            }
+        }
+
+        EventOutcome::Continue
+    }
+
+    fn is_create_option_shown(&self) -> bool {
+        self.creator.is_some() && Creator::is_shown(&self.current_filter, &self.options)
+    }
+
+    fn create_option(&mut self) -> EventOutcome<M::Output> {
+        let created_option_index = match self.creator.as_mut() {
+            Some(creator) => creator.create(&self.current_filter, &mut self.options),
+            None => None,
+        };
+
+        match created_option_index {
+            Some(option_index) => {
+                self.options.filter(&self.current_filter);
+                self.current_selection = self
+                    .options
+                    .filtered_options()
+                    .iter()
+                    .position(|&i| i == option_index)
+                    .unwrap_or_default();
+                self.is_submitted = true;
+                EventOutcome::Done(M::output(&mut self.options, option_index))
+            }
+            None => EventOutcome::Continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::MarkupMode;
+
+    const FRUITS: [&str; 4] = ["apple", "banana", "cherry", "date"];
+
+    fn submit<O>(prompt: &mut impl Prompt<O>) -> O {
+        match prompt.on_key_pressed(Key::Enter) {
+            EventOutcome::Done(result) => result,
+            _ => panic!("The prompt is expected to be submitted"),
+        }
+    }
+
+    #[test]
+    fn starts_on_default_option() {
+        let mut prompt =
+            Selection::new("Fruit", FRUITS.into_iter()).default_option(|f| f.starts_with('c'));
+        assert_eq!(submit(&mut prompt), "cherry");
+    }
+
+    #[test]
+    fn returns_index_in_original_list() {
+        let mut prompt = Selection::new("Fruit", FRUITS.into_iter()).return_index();
+        prompt.on_key_pressed(Key::Char('d'));
+        assert_eq!(submit(&mut prompt), 3);
+    }
+
+    #[test]
+    fn returns_index_and_borrowed_item() {
+        let fruits = FRUITS.to_vec();
+        let mut prompt = Selection::from_slice("Fruit", &fruits)
+            .default_index(1)
+            .return_index_and_item();
+        assert_eq!(submit(&mut prompt), (1, &"banana"));
+    }
+
+    #[test]
+    fn picks_option_by_shortcut() {
+        let mut prompt = Selection::new("Fruit", FRUITS.into_iter()).shortcuts(Shortcuts::Numbers);
+        assert!(matches!(
+            prompt.on_key_pressed(Key::Char('3')),
+            EventOutcome::Done("cherry")
+        ));
+
+        let mut prompt = Selection::new("Fruit", FRUITS.into_iter())
+            .shortcuts(Shortcuts::Letters)
+            .confirm_shortcut(true);
+        prompt.on_key_pressed(Key::Char('b'));
+        assert_eq!(submit(&mut prompt), "banana");
+    }
+
+    #[test]
+    fn creates_new_option_when_nothing_matches() {
+        let mut prompt = Selection::new("Tag", ["v1".to_string()].into_iter())
+            .creatable(|s| Ok(s.to_uppercase()));
+        prompt.on_key_pressed(Key::Char('v'));
+        prompt.on_key_pressed(Key::Char('2'));
+        assert_eq!(submit(&mut prompt), "V2");
+    }
+
+    #[test]
+    fn keeps_prompt_open_when_creation_fails() {
+        let mut prompt = Selection::new("Fruit", FRUITS.into_iter())
+            .creatable(|_| Err("Cannot create fruits".into()));
+        prompt.on_key_pressed(Key::Char('a'));
+
+        assert!(matches!(
+            prompt.on_key_pressed(Key::Tab),
+            EventOutcome::Continue
+        ));
+        assert!(prompt.creator.as_ref().unwrap().error().is_some());
+    }
+
+    fn rendered_after_typing(prompt: &mut Selection<&'static str>, c: char) -> String {
+        prompt.on_key_pressed(Key::Char(c));
+
+        let mut commands = crate::engine::RecordingBuffer::default();
+        prompt.draw(&mut commands);
+        commands.text()
+    }
+
+    #[test]
+    fn displays_options_as_is_by_default() {
+        let options = ["[red]production[/]", "a[[0]]"];
+        let mut prompt = Selection::new("Deploy [bold]where[/]?", options.into_iter());
+
+        let text = rendered_after_typing(&mut prompt, '[');
+        assert!(text.contains("Deploy where?"));
+        assert!(text.contains("[red]production[/]"));
+        assert!(text.contains("a[[0]]"));
+    }
+
+    #[test]
+    fn renders_and_filters_options_without_markup_when_enabled() {
+        let label_style = crate::style::LabelStyle::default().data_markup(MarkupMode::Lenient);
+        let mut prompt = Selection::new("Deploy", ["[red]production[/]", "[[staging]"].into_iter())
+            .style(SelectionStyle::default().label_style(label_style));
+
+        let text = rendered_after_typing(&mut prompt, '[');
+        assert!(text.contains("[staging]"));
+        assert!(!text.contains("production"));
+    }
+
+    #[test]
+    fn displays_help_message_after_filter() {
+        let mut prompt =
+            Selection::new("Fruit", FRUITS.into_iter()).help_message("Pick one to eat");
+
+        let text = rendered_after_typing(&mut prompt, 'a');
+        assert!(text.starts_with("? Fruit: a [Pick one to eat]\n"));
+    }
 }
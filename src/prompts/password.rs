@@ -0,0 +1,190 @@
+use super::Prompt;
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{AbortReason, EventOutcome},
+    style::{InputStyle, PromptState},
+};
+
+const DEFAULT_MASK: char = '*';
+
+/// Text input prompt that doesn't show the typed text. Each typed character is displayed as the
+/// mask character, or nothing is displayed at all if the input is hidden.
+/// Validation and transformation of the input work the same as in the `Input` prompt
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{Password, AbortReason},
+///     DisplayPrompt
+/// };
+///
+/// fn main() {
+///     let password = Password::new("Database password", |p| {
+///         if p.len() >= 8 {
+///             Ok(p.to_string())
+///         } else {
+///             Err("Password must be at least 8 characters long".into())
+///         }
+///     });
+///
+///     let password : Result<String, AbortReason> = password.display();
+///     match password {
+///         Ok(password) => println!("The password is {} characters long", password.len()),
+///         Err(abort_reason) => println!("Prompt is aborted because of {:?}", abort_reason),
+///     }
+/// }
+/// ```
+pub struct Password<F> {
+    label: String,
+    input: String,
+    mask: Option<char>,
+    help_message: Option<String>,
+    is_submitted: bool,
+    is_aborted: bool,
+    error: Option<String>,
+    validation: F,
+    style: InputStyle,
+}
+
+impl<F, T> Password<F>
+where
+    F: Fn(&str) -> Result<T, String>,
+{
+    /// Constructs a password prompt with a given label and a validation function
+    pub fn new(label: impl Into<String>, validation: F) -> Self {
+        Self {
+            label: label.into(),
+            input: String::new(),
+            mask: Some(DEFAULT_MASK),
+            help_message: None,
+            is_submitted: false,
+            is_aborted: false,
+            error: None,
+            validation,
+            style: InputStyle::default(),
+        }
+    }
+
+    /// Sets a help message which will be displayed after the input string
+    /// until the prompt is completed
+    pub fn help_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.help_message = Some(message.into());
+        self
+    }
+
+    /// Sets the character that is displayed instead of each typed character
+    pub fn mask(mut self, mask: char) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Makes the prompt display nothing while typing
+    pub fn hide_input(mut self) -> Self {
+        self.mask = None;
+        self
+    }
+
+    /// Sets the style for the prompt
+    pub fn style(mut self, style: InputStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    fn masked_input(&self) -> String {
+        self.mask
+            .map(|mask| std::iter::repeat_n(mask, self.input.chars().count()).collect())
+            .unwrap_or_default()
+    }
+}
+
+impl<T, F> Prompt<T> for Password<F>
+where
+    F: Fn(&str) -> Result<T, String>,
+{
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        let state = PromptState::new(self.is_submitted, self.error.is_some());
+        self.style
+            .label_style
+            .print_with_state(&self.label, state, commands);
+
+        if let Some(error) = self.error.as_ref() {
+            self.style
+                .error_formatting
+                .print(format!("[{}]", error), commands);
+        } else if self.is_submitted {
+            self.style
+                .submitted_formatting
+                .print(self.masked_input(), commands);
+        } else {
+            self.style
+                .input_formatting
+                .print(self.masked_input(), commands);
+        }
+
+        if let Some(help_message) = self.help_message.as_ref() {
+            self.style.label_style.print_help_message(
+                help_message,
+                &self.style.help_message_formatting,
+                commands,
+            );
+        }
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<T> {
+        match key {
+            Key::Char(c) => {
+                self.error = None;
+                self.input.push(c);
+                EventOutcome::Continue
+            }
+            Key::Backspace => {
+                self.error = None;
+                self.input.pop();
+                EventOutcome::Continue
+            }
+            Key::Enter => match (self.validation)(&self.input) {
+                Ok(result) => {
+                    self.is_submitted = true;
+                    EventOutcome::Done(result)
+                }
+                Err(error) => {
+                    self.error = Some(error);
+                    self.input.clear();
+                    EventOutcome::Continue
+                }
+            },
+            Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::RecordingBuffer;
+
+    #[test]
+    fn masks_typed_characters() {
+        let mut prompt = Password::new("Password", |p| Ok(p.to_string())).mask('#');
+        prompt.on_key_pressed(Key::Char('a'));
+        prompt.on_key_pressed(Key::Char('b'));
+
+        let mut commands = RecordingBuffer::default();
+        prompt.draw(&mut commands);
+        assert_eq!(commands.text(), "? Password: ##");
+
+        match prompt.on_key_pressed(Key::Enter) {
+            EventOutcome::Done(password) => assert_eq!(password, "ab"),
+            _ => panic!("The prompt is expected to be submitted"),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+pub mod progress_bar;
+pub mod spinner;
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::{
+    engine::CommandBuffer,
+    style::{PromptState, SpinnerStyle},
+};
+
+/// Message of the task whose handles have all been dropped before it finished
+const ABANDONED_MESSAGE: &str = "the task has stopped without finishing";
+
+/// Handle that drives a `Spinner` or a `ProgressBar` from another thread, usually the one that
+/// does the work. The handle can be cloned and sent to several threads.
+///
+/// The component completes once `success` or `fail` is called. If all handles are dropped
+/// before that, e.g. because the worker thread has panicked, the component completes as failed.
+#[derive(Clone)]
+pub struct TaskHandle {
+    state: Arc<Mutex<TaskState>>,
+}
+
+#[derive(Default)]
+struct TaskState {
+    message: String,
+    position: u64,
+    total: u64,
+    outcome: Option<Result<String, String>>,
+    has_handles: bool,
+    is_aborted: bool,
+}
+
+impl TaskHandle {
+
+    /// Sets the message that describes the current step of the task
+    pub fn set_message<S: Into<String>>(&self, message: S) {
+        self.lock().message = message.into();
+    }
+
+    /// Sets the number of the completed units of work. Only the `ProgressBar` displays it
+    pub fn set_position(&self, position: u64) {
+        let mut state = self.lock();
+        state.position = position.min(state.total);
+    }
+
+    /// Adds to the number of the completed units of work
+    pub fn inc(&self, delta: u64) {
+        let mut state = self.lock();
+        state.position = state.position.saturating_add(delta).min(state.total);
+    }
+
+    /// Sets the total number of units of work
+    pub fn set_total(&self, total: u64) {
+        let mut state = self.lock();
+        state.total = total;
+        state.position = state.position.min(total);
+    }
+
+    /// Completes the task successfully with the given message
+    pub fn success<S: Into<String>>(&self, message: S) {
+        self.lock().outcome.get_or_insert(Ok(message.into()));
+    }
+
+    /// Completes the task as failed with the given message
+    pub fn fail<S: Into<String>>(&self, message: S) {
+        self.lock().outcome.get_or_insert(Err(message.into()));
+    }
+
+    /// Returns whether the user has aborted the component. The task should stop in this case
+    pub fn is_aborted(&self) -> bool {
+        self.lock().is_aborted
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TaskState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The state of the task that is shared between the component and its handles
+struct Task {
+    handle: TaskHandle,
+    outcome: Option<Result<String, String>>,
+    is_aborted: bool,
+}
+
+impl Task {
+    fn new(total: u64) -> Self {
+        let state = TaskState {
+            total,
+            ..Default::default()
+        };
+        Task {
+            handle: TaskHandle {
+                state: Arc::new(Mutex::new(state)),
+            },
+            outcome: None,
+            is_aborted: false,
+        }
+    }
+
+    fn handle(&self) -> TaskHandle {
+        self.handle.lock().has_handles = true;
+        self.handle.clone()
+    }
+
+    /// Takes the outcome of the task if it has finished
+    fn poll_outcome(&mut self) -> Option<Result<String, String>> {
+        let is_abandoned = Arc::strong_count(&self.handle.state) == 1;
+        let mut state = self.handle.lock();
+        if state.outcome.is_none() && state.has_handles && is_abandoned {
+            state.outcome = Some(Err(ABANDONED_MESSAGE.into()));
+        }
+
+        self.outcome = state.outcome.clone();
+        self.outcome.clone()
+    }
+
+    fn abort(&mut self) {
+        self.is_aborted = true;
+        self.handle.lock().is_aborted = true;
+    }
+
+    /// Draws the final frame of the finished task. Returns `false` if it's still running
+    fn draw_finished(
+        &self,
+        label: &str,
+        style: &SpinnerStyle,
+        commands: &mut impl CommandBuffer,
+    ) -> bool {
+        let label_style = &style.label_style;
+        match &self.outcome {
+            _ if self.is_aborted => label_style.print_aborted(label, commands),
+            Some(Ok(message)) => {
+                label_style.print_with_state(label, PromptState::Submitted, commands);
+                label_style.print_data_text(message, &style.success_formatting, commands);
+            }
+            Some(Err(message)) => {
+                label_style.print_with_state(label, PromptState::Error, commands);
+                label_style.print_data_text(message, &style.failure_formatting, commands);
+            }
+            None => return false,
+        }
+        true
+    }
+}
+
+/// Returns the frame of the animation for the given tick
+fn frame(style: &SpinnerStyle, tick: usize) -> &str {
+    match style.frames.len() {
+        0 => "",
+        len => &style.frames[tick % len],
+    }
+}
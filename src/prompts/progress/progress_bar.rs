@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{AbortReason, EventOutcome, Prompt},
+    style::{ProgressBarStyle, SpinnerStyle},
+};
+
+use super::{Task, TaskHandle};
+
+/// A component that displays the progress of a task that is running on another thread.
+/// The task moves the bar and completes it through the `TaskHandle`.
+/// Returns `Ok` with the success message or `Err` with the failure one.
+///
+/// ```rust,no_run
+/// use std::thread;
+/// use cli_prompts::{prompts::ProgressBar, DisplayPrompt};
+///
+/// let files = vec!["a.txt", "b.txt", "c.txt"];
+/// let progress_bar = ProgressBar::new("Uploading", files.len() as u64);
+/// let handle = progress_bar.handle();
+/// thread::spawn(move || {
+///     for file in files {
+///         handle.set_message(file);
+///         // upload the file
+///         handle.inc(1);
+///     }
+///     handle.success("3 files uploaded");
+/// });
+///
+/// let result = progress_bar.display();
+/// ```
+pub struct ProgressBar {
+    label: String,
+    task: Task,
+    frame: usize,
+    interval: Duration,
+    style: SpinnerStyle,
+    bar_style: ProgressBarStyle,
+}
+
+impl ProgressBar {
+
+    /// Constructs a new progress bar with the given label and the total number of units of work
+    pub fn new<S: Into<String>>(label: S, total: u64) -> Self {
+        ProgressBar {
+            label: label.into(),
+            task: Task::new(total),
+            frame: 0,
+            interval: Duration::from_millis(80),
+            style: SpinnerStyle::default(),
+            bar_style: ProgressBarStyle::default(),
+        }
+    }
+
+    /// Sets the time between the redraws of the bar
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the style of the label, the messages and the animation before the label
+    pub fn style(mut self, s: SpinnerStyle) -> Self {
+        self.style = s;
+        self
+    }
+
+    /// Sets the style of the bar itself
+    pub fn bar_style(mut self, s: ProgressBarStyle) -> Self {
+        self.bar_style = s;
+        self
+    }
+
+    /// Returns the handle to drive the progress bar from another thread
+    pub fn handle(&self) -> TaskHandle {
+        self.task.handle()
+    }
+
+    fn draw_bar(&self, position: u64, total: u64, commands: &mut impl CommandBuffer) {
+        let width = self.bar_style.width.min(commands.terminal_width() / 2) as u128;
+        let (position, total) = (position.min(total) as u128, total as u128);
+        let (filled, percentage) = match total {
+            0 => (0, 0),
+            _ => (width * position / total, 100 * position / total),
+        };
+
+        let bar_style = &self.bar_style;
+        bar_style
+            .filled_formatting
+            .print(bar_style.filled.repeat(filled as usize), commands);
+        bar_style
+            .empty_formatting
+            .print(bar_style.empty.repeat((width - filled) as usize), commands);
+        bar_style
+            .percentage_formatting
+            .print(format!(" {:>3}%", percentage), commands);
+    }
+}
+
+impl Prompt<Result<String, String>> for ProgressBar {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.task.draw_finished(&self.label, &self.style, commands) {
+            return;
+        }
+
+        let label_style = &self.style.label_style;
+        let frame = super::frame(&self.style, self.frame);
+        label_style.print_with_prefix(&self.label, frame, &self.style.frame_formatting, commands);
+
+        let (position, total, message) = {
+            let state = self.task.handle.lock();
+            (state.position, state.total, state.message.clone())
+        };
+        self.draw_bar(position, total, commands);
+        if !message.is_empty() {
+            commands.print(" ");
+            label_style.print_data_text(&message, &self.style.message_formatting, commands);
+        }
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Result<String, String>> {
+        match key {
+            Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.task.abort();
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn on_tick(&mut self) -> EventOutcome<Result<String, String>> {
+        self.frame = self.frame.wrapping_add(1);
+        match self.task.poll_outcome() {
+            Some(outcome) => EventOutcome::Done(outcome),
+            None => EventOutcome::Continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::RecordingBuffer, style::Theme};
+
+    fn rendered(progress_bar: &ProgressBar) -> String {
+        let mut commands = RecordingBuffer::default();
+        progress_bar.draw(&mut commands);
+        commands.text()
+    }
+
+    #[test]
+    fn fills_the_bar_with_the_progress() {
+        let theme = Theme::minimal();
+        let progress_bar = ProgressBar::new("Upload", 10)
+            .style(SpinnerStyle::from_theme(&theme))
+            .bar_style(ProgressBarStyle::from_theme(&theme).width(10));
+        let handle = progress_bar.handle();
+        handle.set_position(2);
+        handle.inc(3);
+        handle.set_message("b.txt");
+        assert_eq!(rendered(&progress_bar), "- Upload: #####-----  50% b.txt");
+
+        handle.inc(100);
+        assert!(rendered(&progress_bar).contains("########## 100%"));
+    }
+
+    #[test]
+    fn draws_huge_totals_without_overflow() {
+        let theme = Theme::minimal();
+        let progress_bar = ProgressBar::new("Download", u64::MAX)
+            .style(SpinnerStyle::from_theme(&theme))
+            .bar_style(ProgressBarStyle::from_theme(&theme).width(10));
+        progress_bar.handle().set_position(u64::MAX - 1);
+        assert!(rendered(&progress_bar).contains("#########-  99%"));
+    }
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{AbortReason, EventOutcome, Prompt},
+    style::SpinnerStyle,
+};
+
+use super::{Task, TaskHandle};
+
+/// A component that displays an animation while a task is running on another thread.
+/// The task reports its progress and completes the spinner through the `TaskHandle`.
+/// Returns `Ok` with the success message or `Err` with the failure one.
+///
+/// ```rust,no_run
+/// use std::{thread, time::Duration};
+/// use cli_prompts::{prompts::Spinner, DisplayPrompt};
+///
+/// let spinner = Spinner::new("Deploying");
+/// let handle = spinner.handle();
+/// thread::spawn(move || {
+///     for step in ["Building", "Uploading", "Restarting"] {
+///         if handle.is_aborted() {
+///             return;
+///         }
+///         handle.set_message(step);
+///         thread::sleep(Duration::from_secs(1));
+///     }
+///     handle.success("done");
+/// });
+///
+/// match spinner.display() {
+///     Ok(Ok(_)) => println!("Deployed"),
+///     Ok(Err(error)) => println!("Deployment failed: {}", error),
+///     Err(abort_reason) => println!("Deployment was aborted: {:?}", abort_reason),
+/// }
+/// ```
+pub struct Spinner {
+    label: String,
+    task: Task,
+    frame: usize,
+    interval: Duration,
+    style: SpinnerStyle,
+}
+
+impl Spinner {
+
+    /// Constructs a new spinner with the given label
+    pub fn new<S: Into<String>>(label: S) -> Self {
+        Spinner {
+            label: label.into(),
+            task: Task::new(0),
+            frame: 0,
+            interval: Duration::from_millis(80),
+            style: SpinnerStyle::default(),
+        }
+    }
+
+    /// Sets the time between the frames of the animation
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the style of the spinner
+    pub fn style(mut self, s: SpinnerStyle) -> Self {
+        self.style = s;
+        self
+    }
+
+    /// Returns the handle to drive the spinner from another thread
+    pub fn handle(&self) -> TaskHandle {
+        self.task.handle()
+    }
+}
+
+impl Prompt<Result<String, String>> for Spinner {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.task.draw_finished(&self.label, &self.style, commands) {
+            return;
+        }
+
+        let label_style = &self.style.label_style;
+        let frame = super::frame(&self.style, self.frame);
+        label_style.print_with_prefix(&self.label, frame, &self.style.frame_formatting, commands);
+        let message = self.task.handle.lock().message.clone();
+        label_style.print_data_text(&message, &self.style.message_formatting, commands);
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Result<String, String>> {
+        match key {
+            Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.task.abort();
+    }
+
+    fn tick_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn on_tick(&mut self) -> EventOutcome<Result<String, String>> {
+        self.frame = self.frame.wrapping_add(1);
+        match self.task.poll_outcome() {
+            Some(outcome) => EventOutcome::Done(outcome),
+            None => EventOutcome::Continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{engine::RecordingBuffer, style::Theme};
+
+    fn rendered(spinner: &Spinner) -> String {
+        let mut commands = RecordingBuffer::default();
+        spinner.draw(&mut commands);
+        commands.text()
+    }
+
+    #[test]
+    fn animates_until_the_task_succeeds() {
+        let mut spinner = Spinner::new("Deploy").style(SpinnerStyle::from_theme(&Theme::minimal()));
+        let handle = spinner.handle();
+        handle.set_message("building");
+        assert_eq!(rendered(&spinner), "- Deploy: building");
+
+        assert!(matches!(spinner.on_tick(), EventOutcome::Continue));
+        assert_eq!(rendered(&spinner), "\\ Deploy: building");
+
+        handle.success("done");
+        assert!(matches!(spinner.on_tick(), EventOutcome::Done(Ok(m)) if m == "done"));
+        assert_eq!(rendered(&spinner), "v Deploy: done");
+    }
+
+    #[test]
+    fn fails_when_all_handles_are_dropped() {
+        let mut spinner = Spinner::new("Deploy");
+        std::thread::spawn({
+            let handle = spinner.handle();
+            move || handle.set_message("building")
+        })
+        .join()
+        .unwrap();
+
+        assert!(matches!(spinner.on_tick(), EventOutcome::Done(Err(_))));
+        assert!(rendered(&spinner).starts_with("! Deploy: "));
+    }
+
+    #[test]
+    fn tells_the_task_it_is_aborted() {
+        let mut spinner = Spinner::new("Deploy");
+        let handle = spinner.handle();
+        spinner.on_abort();
+        assert!(handle.is_aborted());
+        assert_eq!(rendered(&spinner), "✖ Deploy: cancelled");
+    }
+}
@@ -0,0 +1,372 @@
+pub mod table_multiselect;
+pub mod table_select;
+
+use std::cmp::Ordering;
+
+use crate::{
+    engine::CommandBuffer,
+    prompts::options::Options,
+    style::{Formatting, TableStyle},
+};
+
+/// Alignment of the text in a table column
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    /// Align the text to the left edge of the column
+    Left,
+
+    /// Align the text to the right edge of the column
+    Right,
+
+    /// Center the text in the column
+    Center,
+}
+
+/// A helper struct for the table prompts. Keeps the rows as `Options` and the text of their
+/// cells, which is used for filtering, sorting and drawing
+pub struct Table<T> {
+    options: Options<T>,
+    columns: Vec<Column>,
+    cells: Vec<Vec<String>>,
+    filter: String,
+    filter_column: Option<usize>,
+    focused_column: usize,
+    sorting: Option<Sorting>,
+}
+
+struct Column {
+    title: String,
+    alignment: Alignment,
+    natural_width: usize,
+}
+
+#[derive(Clone, Copy)]
+struct Sorting {
+    column: usize,
+    is_descending: bool,
+}
+
+impl<T> Table<T> {
+    /// Create a table without columns from the given rows
+    pub fn new<I>(rows: I) -> Self
+    where
+        I: Iterator<Item = T>,
+    {
+        let options = Options::from_iter_transformed(rows, |_| String::new());
+        let rows_count = options.transformed_options().len();
+
+        Table {
+            options,
+            columns: vec![],
+            cells: (0..rows_count).map(|_| vec![]).collect(),
+            filter: String::new(),
+            filter_column: None,
+            focused_column: 0,
+            sorting: None,
+        }
+    }
+
+    /// Rows of the table. The filtered ones are in the order they are displayed
+    pub fn options(&self) -> &Options<T> {
+        &self.options
+    }
+
+    /// Append a column with the given title. The extractor function produces the text of the
+    /// column's cells from the rows
+    pub fn add_column<F>(&mut self, title: String, alignment: Alignment, extractor: F)
+    where
+        F: Fn(&T) -> String,
+    {
+        let mut natural_width = title.chars().count();
+        for (row, cells) in self.options.all_options().iter().zip(self.cells.iter_mut()) {
+            let cell = extractor(row);
+            natural_width = natural_width.max(cell.chars().count());
+            cells.push(cell);
+        }
+
+        self.columns.push(Column {
+            title,
+            alignment,
+            natural_width,
+        });
+    }
+
+    /// Make the filter match only the text of the column with the given index. By default the
+    /// filter matches the text of all columns. The column may be added later, until then its
+    /// cells are empty
+    pub fn set_filter_column(&mut self, column: usize) {
+        self.filter_column = Some(column);
+        self.refresh();
+    }
+
+    /// Index of the column the filter matches, if it is set
+    pub fn filter_column(&self) -> Option<usize> {
+        self.filter_column
+    }
+
+    /// Show only the rows that contain the filter string
+    pub fn filter(&mut self, filter: &str) {
+        self.filter = filter.to_owned();
+        self.refresh();
+    }
+
+    /// Move the focus to the next column
+    pub fn focus_next_column(&mut self) {
+        if self.focused_column + 1 < self.columns.len() {
+            self.focused_column += 1;
+        }
+    }
+
+    /// Move the focus to the previous column
+    pub fn focus_previous_column(&mut self) {
+        self.focused_column = self.focused_column.saturating_sub(1);
+    }
+
+    /// Sort the rows by the focused column in the ascending order, or in the descending one if
+    /// they are already sorted by it in the ascending order. Cells that are numbers are compared
+    /// as numbers
+    pub fn sort_by_focused_column(&mut self) {
+        let is_descending = matches!(
+            self.sorting,
+            Some(s) if s.column == self.focused_column && !s.is_descending
+        );
+        self.sorting = Some(Sorting {
+            column: self.focused_column,
+            is_descending,
+        });
+        self.refresh();
+    }
+
+    /// Take the given rows out of the table in the order the indices are listed
+    pub fn take_rows(&mut self, rows: &[usize]) -> Vec<T> {
+        self.options.take_options(rows)
+    }
+
+    /// Text of the cell in the given row and column. Empty if the column doesn't exist
+    pub fn cell(&self, row: usize, column: usize) -> &str {
+        self.cells[row].get(column).map_or("", String::as_str)
+    }
+
+    /// Calculates the width of each column so that the table with the given prefix in front of
+    /// each row fits into the terminal. The widest columns are shrunk first
+    pub fn column_widths(
+        &self,
+        prefix_width: usize,
+        style: &TableStyle,
+        terminal_width: u16,
+    ) -> Vec<usize> {
+        let separators_width =
+            style.column_separator.chars().count() * self.columns.len().saturating_sub(1);
+        let available_width =
+            usize::from(terminal_width).saturating_sub(prefix_width + separators_width + 1);
+
+        let mut widths: Vec<usize> = self
+            .columns
+            .iter()
+            .map(|c| c.natural_width + style.sort_indicator_width())
+            .collect();
+        let mut excess = widths.iter().sum::<usize>().saturating_sub(available_width);
+        while excess > 0 {
+            let widest = widths.iter().copied().max().unwrap_or(0);
+            if widest <= 1 {
+                break;
+            }
+
+            // All the widest columns are shrunk at once down to the next width. If the excess
+            // is smaller than their count, the last ones are shrunk by one character
+            let next_width = widths.iter().copied().filter(|&w| w < widest).max();
+            let widest_count = widths.iter().filter(|&&w| w == widest).count();
+            let step = (widest - next_width.unwrap_or(0).max(1)).min(excess / widest_count);
+            if step > 0 {
+                widths
+                    .iter_mut()
+                    .filter(|w| **w == widest)
+                    .for_each(|w| *w -= step);
+                excess -= step * widest_count;
+            } else {
+                widths
+                    .iter_mut()
+                    .rev()
+                    .filter(|w| **w == widest)
+                    .take(excess)
+                    .for_each(|w| *w -= 1);
+                excess = 0;
+            }
+        }
+
+        widths
+    }
+
+    /// Draws the row with the column titles
+    pub fn draw_header_row(
+        &self,
+        widths: &[usize],
+        style: &TableStyle,
+        commands: &mut impl CommandBuffer,
+    ) {
+        for (index, (column, &width)) in self.columns.iter().zip(widths).enumerate() {
+            if index > 0 {
+                commands.print(&style.column_separator);
+            }
+
+            let sort_indicator = match self.sorting {
+                Some(s) if s.column == index && s.is_descending => &style.descending_indicator,
+                Some(s) if s.column == index => &style.ascending_indicator,
+                _ => "",
+            };
+            let formatting = if index == self.focused_column {
+                &style.focused_header_formatting
+            } else {
+                &style.header_formatting
+            };
+            let title = format!("{}{}", column.title, sort_indicator);
+            formatting.print(fit_to_width(&title, width, column.alignment), commands);
+        }
+    }
+
+    /// Draws the cells of the given row
+    pub fn draw_row(
+        &self,
+        row: usize,
+        widths: &[usize],
+        formatting: &Formatting,
+        style: &TableStyle,
+        commands: &mut impl CommandBuffer,
+    ) {
+        commands.set_formatting(formatting);
+        for (index, (column, &width)) in self.columns.iter().zip(widths).enumerate() {
+            if index > 0 {
+                commands.print(&style.column_separator);
+            }
+            commands.print(&fit_to_width(
+                &self.cells[row][index],
+                width,
+                column.alignment,
+            ));
+        }
+        commands.reset_formatting();
+    }
+
+    fn refresh(&mut self) {
+        let cells = &self.cells;
+        let filter = &self.filter;
+        let filter_column = self.filter_column;
+        let cell = |row: usize, column: usize| cells[row].get(column).map_or("", String::as_str);
+        self.options.filter_by(|row| match filter_column {
+            Some(column) => cell(row, column).contains(filter.as_str()),
+            None => cells[row].iter().any(|cell| cell.contains(filter.as_str())),
+        });
+
+        if let Some(sorting) = self.sorting {
+            self.options.sort_filtered_options(|&a, &b| {
+                let ordering = compare_cells(cell(a, sorting.column), cell(b, sorting.column));
+                if sorting.is_descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+    }
+}
+
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+fn fit_to_width(text: &str, width: usize, alignment: Alignment) -> String {
+    let length = text.chars().count();
+    if length > width {
+        let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+        if width > 0 {
+            truncated.push('…');
+        }
+        return truncated;
+    }
+
+    let padding = width - length;
+    match alignment {
+        Alignment::Left => format!("{}{}", text, " ".repeat(padding)),
+        Alignment::Right => format!("{}{}", " ".repeat(padding), text),
+        Alignment::Center => format!(
+            "{}{}{}",
+            " ".repeat(padding / 2),
+            text,
+            " ".repeat(padding - padding / 2)
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table<(&'static str, u32)> {
+        let mut table = Table::new([("web", 12), ("api", 3), ("worker", 120)].into_iter());
+        table.add_column("Name".into(), Alignment::Left, |r| r.0.to_string());
+        table.add_column("Age".into(), Alignment::Right, |r| r.1.to_string());
+        table
+    }
+
+    #[test]
+    fn sorts_numeric_cells_as_numbers() {
+        let mut table = table();
+        table.focus_next_column();
+        table.sort_by_focused_column();
+        assert_eq!(table.options().filtered_options(), &[1, 0, 2]);
+
+        table.sort_by_focused_column();
+        assert_eq!(table.options().filtered_options(), &[2, 0, 1]);
+    }
+
+    #[test]
+    fn filters_by_given_column() {
+        let mut table = table();
+        table.filter("2");
+        assert_eq!(table.options().filtered_options(), &[0, 2]);
+
+        table.set_filter_column(0);
+        assert!(table.options().filtered_options().is_empty());
+    }
+
+    #[test]
+    fn sets_filter_column_before_it_is_added() {
+        let mut table = Table::new([("web", 12), ("api", 3)].into_iter());
+        table.set_filter_column(1);
+        assert_eq!(table.options().filtered_options(), &[0, 1]);
+
+        table.add_column("Name".into(), Alignment::Left, |r| r.0.to_string());
+        table.add_column("Age".into(), Alignment::Right, |r| r.1.to_string());
+        table.filter("3");
+        assert_eq!(table.options().filtered_options(), &[1]);
+    }
+
+    #[test]
+    fn filter_column_that_does_not_exist_has_empty_cells() {
+        let mut table = table();
+        table.set_filter_column(5);
+        assert_eq!(table.options().filtered_options(), &[0, 1, 2]);
+        assert_eq!(table.cell(0, 5), "");
+
+        table.filter("web");
+        assert!(table.options().filtered_options().is_empty());
+    }
+
+    #[test]
+    fn shrinks_widest_columns_to_fit_terminal() {
+        let mut table = Table::new(["a".repeat(50)].into_iter());
+        table.add_column("Long".into(), Alignment::Left, |r| r.clone());
+        table.add_column("Short".into(), Alignment::Left, |_| "x".into());
+
+        let style = TableStyle::default();
+        let widths = table.column_widths(2, &style, 40);
+        assert!(widths.iter().sum::<usize>() + 2 + 2 < 40);
+        assert_eq!(widths[1], 5 + style.sort_indicator_width());
+        assert_eq!(table.column_widths(2, &style, 9), [2, 2]);
+        assert_eq!(fit_to_width("abcdef", 4, Alignment::Left), "abc…");
+        assert_eq!(fit_to_width("ab", 4, Alignment::Right), "  ab");
+    }
+}
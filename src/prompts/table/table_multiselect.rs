@@ -0,0 +1,336 @@
+use std::cell::RefCell;
+
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{
+        options::{multioption_prompt::MultiOptionPrompt, Options},
+        AbortReason, EventOutcome, Prompt,
+    },
+    style::{MultiselectionStyle, PromptState, TableStyle},
+};
+
+use super::{Alignment, Table};
+
+const DEFAULT_OPTIONS_COUNT: u16 = 10;
+const DEFAULT_HELP_MESSAGE: &str = "Space to select, enter to submit";
+
+/// Prompt that allows to select multiple rows from a table.
+/// Each column is defined by a function that extracts the text of its cells from the rows.
+/// The widths of the columns are calculated automatically to fit the terminal.
+/// Typing filters the rows, Left and Right arrows move the focus between the columns and
+/// `Ctrl+S` sorts the rows by the focused column. The selected rows are returned in their
+/// original order.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{TableMultiselect, Alignment, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// fn main() {
+///     let nodes = [("node-a", 4, "Ready"), ("node-b", 16, "NotReady")];
+///
+///     let prompt = TableMultiselect::new("Select nodes to drain", nodes.into_iter())
+///                     .column("Name", |n| n.0.to_string())
+///                     .aligned_column("CPUs", Alignment::Right, |n| n.1.to_string())
+///                     .column("Status", |n| n.2.to_string());
+///     let selected : Result<Vec<(&str, u32, &str)>, AbortReason> = prompt.display();
+/// }
+/// ```
+pub struct TableMultiselect<T> {
+    label: String,
+    table: Table<T>,
+    selected_rows: Vec<usize>,
+    currently_selected_index: usize,
+    max_options: u16,
+    filter: String,
+    help_message: Option<String>,
+    error: Option<String>,
+    is_submitted: bool,
+    is_aborted: bool,
+    style: MultiselectionStyle,
+    table_style: TableStyle,
+    column_widths: RefCell<Vec<usize>>,
+}
+
+impl<T> TableMultiselect<T> {
+    /// Create new prompt with the given label and rows. The columns are added with `column`
+    pub fn new<S, I>(label: S, rows: I) -> Self
+    where
+        S: Into<String>,
+        I: Iterator<Item = T>,
+    {
+        TableMultiselect {
+            label: label.into(),
+            table: Table::new(rows),
+            selected_rows: vec![],
+            currently_selected_index: 0,
+            max_options: DEFAULT_OPTIONS_COUNT,
+            filter: String::new(),
+            help_message: Some(DEFAULT_HELP_MESSAGE.into()),
+            error: None,
+            is_submitted: false,
+            is_aborted: false,
+            style: MultiselectionStyle::default(),
+            table_style: TableStyle::default(),
+            column_widths: RefCell::default(),
+        }
+    }
+
+    /// Add a left-aligned column with the given title. The extractor function produces the text
+    /// of the column's cells from the rows
+    pub fn column<S, F>(self, title: S, extractor: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&T) -> String,
+    {
+        self.aligned_column(title, Alignment::Left, extractor)
+    }
+
+    /// Add a column with the given title and alignment. The extractor function produces the
+    /// text of the column's cells from the rows
+    pub fn aligned_column<S, F>(mut self, title: S, alignment: Alignment, extractor: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&T) -> String,
+    {
+        self.table.add_column(title.into(), alignment, extractor);
+        self
+    }
+
+    /// Make the filter match only the column with the given index. This column is also displayed
+    /// when the prompt is submitted. By default the filter matches all the columns
+    pub fn filter_column(mut self, column: usize) -> Self {
+        self.table.set_filter_column(column);
+        self
+    }
+
+    /// Set help message to be displayed after the filter string
+    pub fn help_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.help_message = Some(message.into());
+        self
+    }
+
+    /// Makes prompt not to display the help message
+    pub fn dont_display_help_message(mut self) -> Self {
+        self.help_message = None;
+        self
+    }
+
+    /// Set maximum number of rows that can be displayed on the screen
+    pub fn max_displayed_options(mut self, max_options: u16) -> Self {
+        self.max_options = max_options;
+        self
+    }
+
+    /// Set the prompt style
+    pub fn style(mut self, style: MultiselectionStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the table
+    pub fn table_style(mut self, table_style: TableStyle) -> Self {
+        self.table_style = table_style;
+        self
+    }
+
+    fn prefix_width(&self) -> usize {
+        let marker = &self.style.marker;
+        marker.opening_sign.chars().count()
+            + marker.selection_sign.chars().count()
+            + marker.closing_sign.chars().count()
+            + 1
+    }
+
+    /// Calculates the widths of the columns once per drawing, so that all rows share them
+    fn update_column_widths(&self, commands: &impl CommandBuffer) {
+        *self.column_widths.borrow_mut() = self.table.column_widths(
+            self.prefix_width(),
+            &self.table_style,
+            commands.terminal_width(),
+        );
+    }
+
+    fn highlighted_row(&self) -> Option<usize> {
+        self.table
+            .options()
+            .filtered_options()
+            .get(self.currently_selected_index)
+            .copied()
+    }
+
+    fn highlight_row(&mut self, row: Option<usize>) {
+        self.currently_selected_index = row
+            .and_then(|row| {
+                self.table
+                    .options()
+                    .filtered_options()
+                    .iter()
+                    .position(|&r| r == row)
+            })
+            .unwrap_or(0);
+    }
+}
+
+impl<T> MultiOptionPrompt<T> for TableMultiselect<T> {
+    fn max_options_count(&self) -> u16 {
+        self.max_options
+    }
+
+    fn options(&self) -> &Options<T> {
+        self.table.options()
+    }
+
+    fn currently_selected_index(&self) -> usize {
+        self.currently_selected_index
+    }
+
+    fn draw_scroll_indicator(&self, text: &str, commands: &mut impl CommandBuffer) {
+        self.style.scroll_indicator_formatting.print(text, commands);
+    }
+
+    fn draw_option(
+        &self,
+        option_index: usize,
+        _: &str,
+        is_selected: bool,
+        commands: &mut impl CommandBuffer,
+    ) {
+        let formatting = if is_selected {
+            &self.style.highlighted_option_formatting
+        } else {
+            &self.style.normal_option_formatting
+        };
+
+        commands.set_formatting(formatting);
+        self.style
+            .marker
+            .print(self.selected_rows.contains(&option_index), commands);
+        commands.print(" ");
+        commands.reset_formatting();
+
+        let widths = self.column_widths.borrow();
+        self.table.draw_row(
+            option_index,
+            &widths,
+            formatting,
+            &self.table_style,
+            commands,
+        );
+    }
+
+    fn draw_header(&self, commands: &mut impl CommandBuffer, is_submitted: bool) {
+        if is_submitted {
+            let column = self.table.filter_column().unwrap_or(0);
+            let cells: Vec<&str> = self
+                .selected_rows
+                .iter()
+                .map(|&row| self.table.cell(row, column))
+                .collect();
+            self.style
+                .submitted_formatting
+                .print(cells.join(", "), commands);
+        } else {
+            self.style.filter_formatting.print(&self.filter, commands);
+            commands.print(" ");
+            if let Some(error) = self.error.as_ref() {
+                self.style
+                    .error_formatting
+                    .print(format!("[{}]", error), commands);
+            } else if let Some(help_message) = self.help_message.as_ref() {
+                self.style.label_style.print_help_message(
+                    help_message,
+                    &self.style.help_message_formatting,
+                    commands,
+                );
+            }
+
+            commands.new_line();
+            commands.print(&" ".repeat(self.prefix_width()));
+            let widths = self.column_widths.borrow();
+            self.table
+                .draw_header_row(&widths, &self.table_style, commands);
+        }
+    }
+}
+
+impl<T> Prompt<Vec<T>> for TableMultiselect<T> {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        self.update_column_widths(commands);
+        self.draw_multioption_with_state(
+            &self.label,
+            PromptState::new(self.is_submitted, self.error.is_some()),
+            &self.style.label_style,
+            commands,
+        );
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Vec<T>> {
+        self.error = None;
+        if let Some(new_index) = self.navigate(&key) {
+            self.currently_selected_index = new_index;
+            return EventOutcome::Continue;
+        }
+
+        match key {
+            Key::Left => {
+                self.table.focus_previous_column();
+                EventOutcome::Continue
+            }
+            Key::Right => {
+                self.table.focus_next_column();
+                EventOutcome::Continue
+            }
+            Key::Ctrl('s') => {
+                let highlighted_row = self.highlighted_row();
+                self.table.sort_by_focused_column();
+                self.highlight_row(highlighted_row);
+                EventOutcome::Continue
+            }
+            Key::Char(' ') => {
+                if let Some(row) = self.highlighted_row() {
+                    match self.selected_rows.iter().position(|&r| r == row) {
+                        Some(i) => {
+                            self.selected_rows.remove(i);
+                        }
+                        None => self.selected_rows.push(row),
+                    }
+                }
+                EventOutcome::Continue
+            }
+            Key::Char(c) => {
+                self.filter.push(c);
+                self.table.filter(&self.filter);
+                self.currently_selected_index = 0;
+                EventOutcome::Continue
+            }
+            Key::Backspace if !self.filter.is_empty() => {
+                self.filter.pop();
+                self.table.filter(&self.filter);
+                self.currently_selected_index = 0;
+                EventOutcome::Continue
+            }
+            Key::Enter if self.selected_rows.is_empty() => {
+                self.error = Some("Select at least one option".into());
+                EventOutcome::Continue
+            }
+            Key::Enter => {
+                self.is_submitted = true;
+                self.selected_rows.sort();
+                EventOutcome::Done(self.table.take_rows(&self.selected_rows))
+            }
+            Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
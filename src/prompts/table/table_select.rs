@@ -0,0 +1,309 @@
+use std::cell::RefCell;
+
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{
+        options::{multioption_prompt::MultiOptionPrompt, Options},
+        AbortReason, EventOutcome, Prompt,
+    },
+    style::{SelectionStyle, TableStyle},
+};
+
+use super::{Alignment, Table};
+
+const DEFAULT_OPTIONS_COUNT: u16 = 10;
+
+/// Prompt that allows to select one row from a table.
+/// Each column is defined by a function that extracts the text of its cells from the rows.
+/// The widths of the columns are calculated automatically to fit the terminal.
+/// Typing filters the rows, Left and Right arrows move the focus between the columns and
+/// `Ctrl+S` sorts the rows by the focused column.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{TableSelect, Alignment, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// struct Pod {
+///     name: &'static str,
+///     status: &'static str,
+///     restarts: u32,
+/// }
+///
+/// fn main() {
+///     let pods = vec![
+///         Pod { name: "web-7d9f", status: "Running", restarts: 0 },
+///         Pod { name: "worker-5c2a", status: "CrashLoopBackOff", restarts: 14 },
+///     ];
+///
+///     let prompt = TableSelect::new("Select the pod", pods.into_iter())
+///                     .column("Name", |p: &Pod| p.name.to_string())
+///                     .column("Status", |p: &Pod| p.status.to_string())
+///                     .aligned_column("Restarts", Alignment::Right, |p: &Pod| p.restarts.to_string())
+///                     .filter_column(0);
+///     let pod : Result<Pod, AbortReason> = prompt.display();
+///     match pod {
+///         Ok(pod) => println!("Showing logs for {}", pod.name),
+///         Err(abort_reason) => println!("Prompt is aborted because of {:?}", abort_reason),
+///     }
+/// }
+/// ```
+pub struct TableSelect<T> {
+    label: String,
+    table: Table<T>,
+    current_selection: usize,
+    max_options: u16,
+    current_filter: String,
+    is_submitted: bool,
+    is_aborted: bool,
+    style: SelectionStyle,
+    table_style: TableStyle,
+    column_widths: RefCell<Vec<usize>>,
+}
+
+impl<T> TableSelect<T> {
+    /// Create new prompt with the given label and rows. The columns are added with `column`
+    pub fn new<S, I>(label: S, rows: I) -> Self
+    where
+        S: Into<String>,
+        I: Iterator<Item = T>,
+    {
+        TableSelect {
+            label: label.into(),
+            table: Table::new(rows),
+            current_selection: 0,
+            max_options: DEFAULT_OPTIONS_COUNT,
+            current_filter: String::new(),
+            is_submitted: false,
+            is_aborted: false,
+            style: SelectionStyle::default(),
+            table_style: TableStyle::default(),
+            column_widths: RefCell::default(),
+        }
+    }
+
+    /// Add a left-aligned column with the given title. The extractor function produces the text
+    /// of the column's cells from the rows
+    pub fn column<S, F>(self, title: S, extractor: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&T) -> String,
+    {
+        self.aligned_column(title, Alignment::Left, extractor)
+    }
+
+    /// Add a column with the given title and alignment. The extractor function produces the
+    /// text of the column's cells from the rows
+    pub fn aligned_column<S, F>(mut self, title: S, alignment: Alignment, extractor: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&T) -> String,
+    {
+        self.table.add_column(title.into(), alignment, extractor);
+        self
+    }
+
+    /// Make the filter match only the column with the given index. This column is also displayed
+    /// when the prompt is submitted. By default the filter matches all the columns
+    pub fn filter_column(mut self, column: usize) -> Self {
+        self.table.set_filter_column(column);
+        self
+    }
+
+    /// Set maximum number of rows that can be displayed on the screen
+    pub fn displayed_options_count(mut self, options_count: u16) -> Self {
+        self.max_options = options_count;
+        self
+    }
+
+    /// Set the prompt style
+    pub fn style(mut self, style: SelectionStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the table
+    pub fn table_style(mut self, table_style: TableStyle) -> Self {
+        self.table_style = table_style;
+        self
+    }
+
+    fn prefix_width(&self) -> usize {
+        self.style
+            .selected_marker
+            .marker
+            .chars()
+            .count()
+            .max(self.style.not_selected_marker.marker.chars().count())
+    }
+
+    /// Calculates the widths of the columns once per drawing, so that all rows share them
+    fn update_column_widths(&self, commands: &impl CommandBuffer) {
+        *self.column_widths.borrow_mut() = self.table.column_widths(
+            self.prefix_width(),
+            &self.table_style,
+            commands.terminal_width(),
+        );
+    }
+
+    fn highlighted_row(&self) -> Option<usize> {
+        self.table
+            .options()
+            .filtered_options()
+            .get(self.current_selection)
+            .copied()
+    }
+
+    fn highlight_row(&mut self, row: Option<usize>) {
+        self.current_selection = row
+            .and_then(|row| {
+                self.table
+                    .options()
+                    .filtered_options()
+                    .iter()
+                    .position(|&r| r == row)
+            })
+            .unwrap_or(0);
+    }
+}
+
+impl<T> MultiOptionPrompt<T> for TableSelect<T> {
+    fn max_options_count(&self) -> u16 {
+        self.max_options
+    }
+
+    fn options(&self) -> &Options<T> {
+        self.table.options()
+    }
+
+    fn currently_selected_index(&self) -> usize {
+        self.current_selection
+    }
+
+    fn draw_scroll_indicator(&self, text: &str, cmd_buffer: &mut impl CommandBuffer) {
+        self.style
+            .scroll_indicator_formatting
+            .print(text, cmd_buffer);
+    }
+
+    fn draw_option(
+        &self,
+        option_index: usize,
+        _: &str,
+        is_selected: bool,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        let (marker, formatting) = if is_selected {
+            (
+                &self.style.selected_marker,
+                &self.style.selected_option_formatting,
+            )
+        } else {
+            (
+                &self.style.not_selected_marker,
+                &self.style.option_formatting,
+            )
+        };
+
+        marker.print(cmd_buffer);
+        let widths = self.column_widths.borrow();
+        self.table.draw_row(
+            option_index,
+            &widths,
+            formatting,
+            &self.table_style,
+            cmd_buffer,
+        );
+    }
+
+    fn draw_header(&self, commands: &mut impl CommandBuffer, is_submitted: bool) {
+        if is_submitted {
+            if let Some(row) = self.highlighted_row() {
+                let column = self.table.filter_column().unwrap_or(0);
+                self.style
+                    .submitted_formatting
+                    .print(self.table.cell(row, column), commands);
+            }
+        } else {
+            self.style
+                .filter_formatting
+                .print(&self.current_filter, commands);
+            commands.new_line();
+            commands.print(&" ".repeat(self.prefix_width()));
+
+            let widths = self.column_widths.borrow();
+            self.table
+                .draw_header_row(&widths, &self.table_style, commands);
+        }
+    }
+}
+
+impl<T> Prompt<T> for TableSelect<T> {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        self.update_column_widths(commands);
+        self.draw_multioption(
+            &self.label,
+            self.is_submitted,
+            &self.style.label_style,
+            commands,
+        );
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<T> {
+        if let Some(new_selection) = self.navigate(&key) {
+            self.current_selection = new_selection;
+            return EventOutcome::Continue;
+        }
+
+        match key {
+            Key::Left => {
+                self.table.focus_previous_column();
+                EventOutcome::Continue
+            }
+            Key::Right => {
+                self.table.focus_next_column();
+                EventOutcome::Continue
+            }
+            Key::Ctrl('s') => {
+                let highlighted_row = self.highlighted_row();
+                self.table.sort_by_focused_column();
+                self.highlight_row(highlighted_row);
+                EventOutcome::Continue
+            }
+            Key::Char(c) => {
+                self.current_filter.push(c);
+                self.table.filter(&self.current_filter);
+                self.current_selection = 0;
+                EventOutcome::Continue
+            }
+            Key::Backspace if !self.current_filter.is_empty() => {
+                self.current_filter.pop();
+                self.table.filter(&self.current_filter);
+                self.current_selection = 0;
+                EventOutcome::Continue
+            }
+            Key::Enter => match self.highlighted_row() {
+                Some(row) => {
+                    self.is_submitted = true;
+                    match self.table.take_rows(&[row]).pop() {
+                        Some(result) => EventOutcome::Done(result),
+                        None => EventOutcome::Continue,
+                    }
+                }
+                None => EventOutcome::Continue,
+            },
+            Key::Esc => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
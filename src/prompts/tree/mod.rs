@@ -0,0 +1,261 @@
+pub mod tree_multiselect;
+pub mod tree_select;
+
+use crate::{prompts::options::Options, style::MarkupMode};
+
+/// A node of the tree to select from. Consists of the label to display, the value to return and
+/// the child nodes
+pub struct TreeNode<T> {
+    label: String,
+    value: T,
+    children: Vec<TreeNode<T>>,
+}
+
+/// The node chosen in a tree prompt
+#[derive(Debug, PartialEq, Eq)]
+pub struct TreeItem<T> {
+    /// Labels of all the nodes from the root to the chosen one, inclusive
+    pub path: Vec<String>,
+
+    /// The value of the chosen node
+    pub value: T,
+}
+
+impl<T> TreeNode<T> {
+    /// Create a node without children
+    pub fn new<S: Into<String>>(label: S, value: T) -> Self {
+        TreeNode {
+            label: label.into(),
+            value,
+            children: vec![],
+        }
+    }
+
+    /// Add a child node
+    pub fn child(mut self, child: TreeNode<T>) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    /// Add multiple child nodes
+    pub fn children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator<Item = TreeNode<T>>,
+    {
+        self.children.extend(children);
+        self
+    }
+}
+
+/// A helper struct for the tree prompts. Flattens the tree into `Options` in depth-first order
+/// and keeps only the nodes that are visible on the screen among the filtered options
+pub struct Tree<T> {
+    options: Options<T>,
+    parents: Vec<Option<usize>>,
+    depths: Vec<usize>,
+    subtree_ends: Vec<usize>,
+    expanded: Vec<bool>,
+    expanded_before_filter: Option<Vec<bool>>,
+    filter: String,
+}
+
+impl<T> Tree<T> {
+    /// Create the tree from its root nodes. All nodes are collapsed initially
+    pub fn new<I>(roots: I) -> Self
+    where
+        I: IntoIterator<Item = TreeNode<T>>,
+    {
+        let mut tree = Tree {
+            options: Options::from_iter_transformed(std::iter::empty(), |_| String::new()),
+            parents: vec![],
+            depths: vec![],
+            subtree_ends: vec![],
+            expanded: vec![],
+            expanded_before_filter: None,
+            filter: String::new(),
+        };
+
+        for root in roots {
+            tree.add_node(root, None, 0);
+        }
+        tree.refresh();
+        tree
+    }
+
+    /// Options in depth-first order. Only the visible nodes are among the filtered ones
+    pub fn options(&self) -> &Options<T> {
+        &self.options
+    }
+
+    /// Nesting level of the node. Root nodes have the depth of 0
+    pub fn depth(&self, node: usize) -> usize {
+        self.depths[node]
+    }
+
+    /// Parent of the node or `None` for the root nodes
+    pub fn parent(&self, node: usize) -> Option<usize> {
+        self.parents[node]
+    }
+
+    /// Whether the node has child nodes
+    pub fn has_children(&self, node: usize) -> bool {
+        self.subtree_ends[node] > node + 1
+    }
+
+    /// Whether the children of the node are shown
+    pub fn is_expanded(&self, node: usize) -> bool {
+        self.expanded[node]
+    }
+
+    /// Indices of the node and all of its descendants
+    pub fn subtree(&self, node: usize) -> std::ops::Range<usize> {
+        node..self.subtree_ends[node]
+    }
+
+    /// Expands or collapses the node
+    pub fn set_expanded(&mut self, node: usize, is_expanded: bool) {
+        self.expanded[node] = is_expanded;
+        self.refresh();
+    }
+
+    /// Expands the collapsed node or moves to its first child if it is already expanded.
+    /// Returns the node that should be highlighted
+    pub fn expand_or_descend(&mut self, node: usize) -> usize {
+        if !self.has_children(node) {
+            node
+        } else if self.is_expanded(node) {
+            node + 1
+        } else {
+            self.set_expanded(node, true);
+            node
+        }
+    }
+
+    /// Collapses the expanded node or moves to its parent if it is already collapsed.
+    /// Returns the node that should be highlighted
+    pub fn collapse_or_ascend(&mut self, node: usize) -> usize {
+        if self.has_children(node) && self.is_expanded(node) {
+            self.set_expanded(node, false);
+            node
+        } else {
+            self.parent(node).unwrap_or(node)
+        }
+    }
+
+    /// Sets how the markup in the labels is handled. The nodes are filtered by their labels
+    /// without the markup
+    pub fn set_markup(&mut self, mode: MarkupMode) {
+        self.options.set_markup(mode);
+        self.refresh();
+    }
+
+    /// Shows only the nodes whose labels contain the filter string along with their ancestors
+    /// and descendants. The ancestors of the matching nodes get expanded. The nodes are expanded
+    /// and collapsed as before the filtering once the filter is cleared
+    pub fn filter(&mut self, filter: &str) {
+        self.filter = filter.to_owned();
+        if self.filter.is_empty() {
+            if let Some(expanded) = self.expanded_before_filter.take() {
+                self.expanded = expanded;
+            }
+        } else {
+            let (_, has_matching_descendant) = self.matches();
+            let expanded_before_filter = self
+                .expanded_before_filter
+                .get_or_insert_with(|| self.expanded.clone());
+            self.expanded = expanded_before_filter
+                .iter()
+                .zip(has_matching_descendant)
+                .map(|(&expanded, has_match)| expanded || has_match)
+                .collect();
+        }
+        self.refresh();
+    }
+
+    /// The first node that matches the current filter
+    pub fn first_match(&self) -> Option<usize> {
+        self.options
+            .plain_options()
+            .iter()
+            .position(|label| label.contains(&self.filter))
+    }
+
+    /// Labels of all the nodes from the root to the given one, inclusive
+    pub fn path(&self, node: usize) -> Vec<String> {
+        let mut path = vec![];
+        let mut current = Some(node);
+        while let Some(n) = current {
+            path.push(self.options.transformed_options()[n].clone());
+            current = self.parent(n);
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Take the values of the given nodes out of the tree along with their paths
+    pub fn take_items(&mut self, nodes: &[usize]) -> Vec<TreeItem<T>> {
+        let paths: Vec<Vec<String>> = nodes.iter().map(|&node| self.path(node)).collect();
+        self.options
+            .take_options(nodes)
+            .into_iter()
+            .zip(paths)
+            .map(|(value, path)| TreeItem { path, value })
+            .collect()
+    }
+
+    fn add_node(&mut self, node: TreeNode<T>, parent: Option<usize>, depth: usize) {
+        let index = self.options.push(node.value, node.label);
+        self.parents.push(parent);
+        self.depths.push(depth);
+        self.subtree_ends.push(index + 1);
+        self.expanded.push(false);
+
+        for child in node.children {
+            self.add_node(child, Some(index), depth + 1);
+        }
+        self.subtree_ends[index] = self.options.transformed_options().len();
+    }
+
+    /// Whether each node matches the filter and whether any of its descendants does
+    fn matches(&self) -> (Vec<bool>, Vec<bool>) {
+        let nodes_count = self.parents.len();
+        let matches: Vec<bool> = self
+            .options
+            .plain_options()
+            .iter()
+            .map(|label| label.contains(&self.filter))
+            .collect();
+
+        let mut has_matching_descendant = vec![false; nodes_count];
+        for node in (0..nodes_count).rev() {
+            if let Some(parent) = self.parents[node] {
+                if matches[node] || has_matching_descendant[node] {
+                    has_matching_descendant[parent] = true;
+                }
+            }
+        }
+
+        (matches, has_matching_descendant)
+    }
+
+    fn refresh(&mut self) {
+        let nodes_count = self.parents.len();
+        let (matches, has_matching_descendant) = self.matches();
+
+        let mut has_matching_ancestor = vec![false; nodes_count];
+        let mut is_visible = vec![false; nodes_count];
+        for node in 0..nodes_count {
+            let parent = self.parents[node];
+            has_matching_ancestor[node] =
+                parent.is_some_and(|p| matches[p] || has_matching_ancestor[p]);
+
+            let is_relevant =
+                matches[node] || has_matching_descendant[node] || has_matching_ancestor[node];
+            is_visible[node] =
+                is_relevant && parent.is_none_or(|p| is_visible[p] && self.expanded[p]);
+        }
+
+        self.options.filter_by(|node| is_visible[node]);
+    }
+}
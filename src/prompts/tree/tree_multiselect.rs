@@ -0,0 +1,437 @@
+use std::cell::RefCell;
+
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{
+        options::{multioption_prompt::MultiOptionPrompt, Options},
+        AbortReason, EventOutcome, Prompt,
+    },
+    style::{MultiselectionStyle, PromptState, TreeStyle},
+};
+
+use super::{Tree, TreeItem, TreeNode};
+
+const DEFAULT_OPTIONS_COUNT: u16 = 10;
+const DEFAULT_HELP_MESSAGE: &str = "Space to select, arrows to expand, enter to submit";
+
+/// Prompt that allows to select multiple nodes from a tree.
+/// Selecting a node selects all of its descendants. A node some of whose descendants are
+/// selected is displayed as partially selected. Right arrow expands the highlighted node, Left
+/// arrow collapses it. Typing filters the nodes and expands the ancestors of the matching ones.
+///
+/// The prompt returns all the fully selected nodes, both leaves and the inner ones, in
+/// depth-first order.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{TreeMultiselect, TreeNode, TreeItem, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// fn main() {
+///     let src = TreeNode::new("src", "src").children([
+///         TreeNode::new("main.rs", "src/main.rs"),
+///         TreeNode::new("lib.rs", "src/lib.rs"),
+///     ]);
+///     let readme = TreeNode::new("README.md", "README.md");
+///
+///     let prompt = TreeMultiselect::new("Select files to stage", [src, readme]);
+///     let selection : Result<Vec<TreeItem<&str>>, AbortReason> = prompt.display();
+///     match selection {
+///         Ok(items) => {
+///             for item in items {
+///                 println!("Staging {}", item.value);
+///             }
+///         }
+///         Err(abort_reason) => println!("Prompt is aborted because of {:?}", abort_reason),
+///     }
+/// }
+/// ```
+pub struct TreeMultiselect<T> {
+    label: String,
+    tree: Tree<T>,
+    selected_nodes: Vec<bool>,
+    currently_selected_index: usize,
+    max_options: u16,
+    filter: String,
+    help_message: Option<String>,
+    error: Option<String>,
+    is_submitted: bool,
+    is_aborted: bool,
+    style: MultiselectionStyle,
+    tree_style: TreeStyle,
+    selection_states: RefCell<Vec<SelectionState>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum SelectionState {
+    NotSelected,
+    PartiallySelected,
+    Selected,
+}
+
+impl<T> TreeMultiselect<T> {
+    /// Create new prompt with the given label and the root nodes of the tree
+    pub fn new<S, I>(label: S, roots: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = TreeNode<T>>,
+    {
+        let style = MultiselectionStyle::default();
+        let mut tree = Tree::new(roots);
+        tree.set_markup(style.label_style.data_markup_mode());
+        let nodes_count = tree.options().transformed_options().len();
+
+        TreeMultiselect {
+            label: label.into(),
+            tree,
+            selected_nodes: vec![false; nodes_count],
+            currently_selected_index: 0,
+            max_options: DEFAULT_OPTIONS_COUNT,
+            filter: String::new(),
+            help_message: Some(DEFAULT_HELP_MESSAGE.into()),
+            error: None,
+            is_submitted: false,
+            is_aborted: false,
+            style,
+            tree_style: TreeStyle::default(),
+            selection_states: RefCell::default(),
+        }
+    }
+
+    /// Set help message to be displayed after the filter string
+    pub fn help_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.help_message = Some(message.into());
+        self
+    }
+
+    /// Makes prompt not to display the help message
+    pub fn dont_display_help_message(mut self) -> Self {
+        self.help_message = None;
+        self
+    }
+
+    /// Set maximum number of nodes that can be displayed on the screen
+    pub fn max_displayed_options(mut self, max_options: u16) -> Self {
+        self.max_options = max_options;
+        self
+    }
+
+    /// Set the prompt style
+    pub fn style(mut self, style: MultiselectionStyle) -> Self {
+        self.tree.set_markup(style.label_style.data_markup_mode());
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the tree
+    pub fn tree_style(mut self, tree_style: TreeStyle) -> Self {
+        self.tree_style = tree_style;
+        self
+    }
+
+    /// Calculates the selection states of all nodes in one pass. The children follow their
+    /// parents in the tree, so the nodes are visited in reverse to count the selected leaves of
+    /// each subtree before its root
+    fn calculate_selection_states(&self) -> Vec<SelectionState> {
+        let mut counts = vec![(0, 0); self.selected_nodes.len()];
+        for node in (0..counts.len()).rev() {
+            if !self.tree.has_children(node) {
+                counts[node] = (usize::from(self.selected_nodes[node]), 1);
+            }
+            if let Some(parent) = self.tree.parent(node) {
+                counts[parent].0 += counts[node].0;
+                counts[parent].1 += counts[node].1;
+            }
+        }
+
+        counts
+            .into_iter()
+            .map(|(selected_count, leaves_count)| {
+                if selected_count == 0 {
+                    SelectionState::NotSelected
+                } else if selected_count == leaves_count {
+                    SelectionState::Selected
+                } else {
+                    SelectionState::PartiallySelected
+                }
+            })
+            .collect()
+    }
+
+    fn toggle_node(&mut self, node: usize) {
+        let is_selected = self.calculate_selection_states()[node] != SelectionState::Selected;
+        for n in self.tree.subtree(node) {
+            self.selected_nodes[n] = is_selected;
+        }
+    }
+
+    fn highlighted_node(&self) -> Option<usize> {
+        self.tree
+            .options()
+            .filtered_options()
+            .get(self.currently_selected_index)
+            .copied()
+    }
+
+    fn highlight_node(&mut self, node: usize) {
+        if let Some(index) = self
+            .tree
+            .options()
+            .filtered_options()
+            .iter()
+            .position(|&n| n == node)
+        {
+            self.currently_selected_index = index;
+        }
+    }
+
+    fn apply_filter(&mut self) {
+        self.tree.filter(&self.filter);
+        self.currently_selected_index = 0;
+        if let Some(node) = self.tree.first_match() {
+            self.highlight_node(node);
+        }
+    }
+
+    fn fully_selected_nodes(&self) -> Vec<usize> {
+        self.calculate_selection_states()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, state)| *state == SelectionState::Selected)
+            .map(|(node, _)| node)
+            .collect()
+    }
+}
+
+impl<T> MultiOptionPrompt<T> for TreeMultiselect<T> {
+    fn max_options_count(&self) -> u16 {
+        self.max_options
+    }
+
+    fn options(&self) -> &Options<T> {
+        self.tree.options()
+    }
+
+    fn currently_selected_index(&self) -> usize {
+        self.currently_selected_index
+    }
+
+    fn draw_scroll_indicator(&self, text: &str, commands: &mut impl CommandBuffer) {
+        self.style.scroll_indicator_formatting.print(text, commands);
+    }
+
+    fn draw_option(
+        &self,
+        option_index: usize,
+        option_label: &str,
+        is_selected: bool,
+        commands: &mut impl CommandBuffer,
+    ) {
+        self.tree_style.print_node_prefix(
+            self.tree.depth(option_index),
+            self.tree.has_children(option_index),
+            self.tree.is_expanded(option_index),
+            commands,
+        );
+
+        let formatting = if is_selected {
+            &self.style.highlighted_option_formatting
+        } else {
+            &self.style.normal_option_formatting
+        };
+        commands.set_formatting(formatting);
+        match self.selection_states.borrow()[option_index] {
+            SelectionState::PartiallySelected => commands.print(&format!(
+                "{}{}{}",
+                self.style.marker.opening_sign,
+                self.tree_style.partial_selection_sign,
+                self.style.marker.closing_sign
+            )),
+            state => self
+                .style
+                .marker
+                .print(state == SelectionState::Selected, commands),
+        }
+        commands.print(" ");
+        commands.reset_formatting();
+        self.style
+            .label_style
+            .print_data_text(option_label, formatting, commands);
+    }
+
+    fn draw_header(&self, commands: &mut impl CommandBuffer, is_submitted: bool) {
+        if is_submitted {
+            let labels: Vec<&str> = self
+                .fully_selected_nodes()
+                .into_iter()
+                .map(|node| self.tree.options().transformed_options()[node].as_str())
+                .collect();
+            self.style.label_style.print_data_text(
+                &labels.join(", "),
+                &self.style.submitted_formatting,
+                commands,
+            );
+        } else {
+            self.style.filter_formatting.print(&self.filter, commands);
+            commands.print(" ");
+            if let Some(error) = self.error.as_ref() {
+                self.style
+                    .error_formatting
+                    .print(format!("[{}]", error), commands);
+            } else if let Some(help_message) = self.help_message.as_ref() {
+                self.style.label_style.print_help_message(
+                    help_message,
+                    &self.style.help_message_formatting,
+                    commands,
+                );
+            }
+        }
+    }
+}
+
+impl<T> Prompt<Vec<TreeItem<T>>> for TreeMultiselect<T> {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        *self.selection_states.borrow_mut() = self.calculate_selection_states();
+        self.draw_multioption_with_state(
+            &self.label,
+            PromptState::new(self.is_submitted, self.error.is_some()),
+            &self.style.label_style,
+            commands,
+        );
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Vec<TreeItem<T>>> {
+        self.error = None;
+        if let Some(new_index) = self.navigate(&key) {
+            self.currently_selected_index = new_index;
+            return EventOutcome::Continue;
+        }
+
+        let highlighted_node = self.highlighted_node();
+        match (key, highlighted_node) {
+            (Key::Right, Some(node)) => {
+                let node = self.tree.expand_or_descend(node);
+                self.highlight_node(node);
+                EventOutcome::Continue
+            }
+            (Key::Left, Some(node)) => {
+                let node = self.tree.collapse_or_ascend(node);
+                self.highlight_node(node);
+                EventOutcome::Continue
+            }
+            (Key::Char(' '), Some(node)) => {
+                self.toggle_node(node);
+                EventOutcome::Continue
+            }
+            (Key::Char(' '), None) => EventOutcome::Continue,
+            (Key::Char(c), _) => {
+                self.filter.push(c);
+                self.apply_filter();
+                EventOutcome::Continue
+            }
+            (Key::Backspace, _) if !self.filter.is_empty() => {
+                self.filter.pop();
+                self.apply_filter();
+                EventOutcome::Continue
+            }
+            (Key::Enter, _) => {
+                let selected_nodes = self.fully_selected_nodes();
+                if selected_nodes.is_empty() {
+                    self.error = Some("Select at least one option".into());
+                    return EventOutcome::Continue;
+                }
+
+                self.is_submitted = true;
+                EventOutcome::Done(self.tree.take_items(&selected_nodes))
+            }
+            (Key::Esc, _) => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prompt() -> TreeMultiselect<&'static str> {
+        let web = TreeNode::new("web", "web").children([
+            TreeNode::new("staging", "web-staging"),
+            TreeNode::new("production", "web-production"),
+        ]);
+        let api = TreeNode::new("api", "api").child(TreeNode::new("staging", "api-staging"));
+
+        TreeMultiselect::new("Environments", [web, api])
+    }
+
+    fn submit(mut prompt: TreeMultiselect<&'static str>) -> Vec<TreeItem<&'static str>> {
+        match prompt.on_key_pressed(Key::Enter) {
+            EventOutcome::Done(result) => result,
+            _ => panic!("The prompt is expected to be submitted"),
+        }
+    }
+
+    #[test]
+    fn cascades_selection_to_children() {
+        let mut prompt = prompt();
+        prompt.on_key_pressed(Key::Char(' '));
+
+        let values: Vec<&str> = submit(prompt).into_iter().map(|i| i.value).collect();
+        assert_eq!(values, vec!["web", "web-staging", "web-production"]);
+    }
+
+    #[test]
+    fn marks_parent_as_partially_selected() {
+        let mut prompt = prompt();
+        prompt.on_key_pressed(Key::Right);
+        prompt.on_key_pressed(Key::Down);
+        prompt.on_key_pressed(Key::Char(' '));
+
+        let states = prompt.calculate_selection_states();
+        assert_eq!(states[0], SelectionState::PartiallySelected);
+        assert_eq!(states[1], SelectionState::Selected);
+        assert_eq!(states[3], SelectionState::NotSelected);
+        assert_eq!(
+            submit(prompt),
+            vec![TreeItem {
+                path: vec!["web".to_string(), "staging".to_string()],
+                value: "web-staging"
+            }]
+        );
+    }
+
+    #[test]
+    fn filter_expands_ancestors_of_matching_nodes() {
+        let mut prompt = prompt();
+        for c in "prod".chars() {
+            prompt.on_key_pressed(Key::Char(c));
+        }
+
+        assert_eq!(prompt.tree.options().filtered_options(), &[0, 2]);
+        assert_eq!(prompt.highlighted_node(), Some(2));
+        assert!(prompt.tree.is_expanded(0));
+    }
+
+    #[test]
+    fn clearing_filter_restores_expansion() {
+        let mut prompt = prompt();
+        prompt.on_key_pressed(Key::Right);
+        for key in [Key::Char('s'), Key::Backspace] {
+            prompt.on_key_pressed(key);
+        }
+
+        assert!(prompt.tree.is_expanded(0));
+        assert!(!prompt.tree.is_expanded(3));
+        assert_eq!(prompt.tree.options().filtered_options(), &[0, 1, 2, 3]);
+    }
+}
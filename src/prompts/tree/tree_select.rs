@@ -0,0 +1,266 @@
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{
+        options::{multioption_prompt::MultiOptionPrompt, Options},
+        AbortReason, EventOutcome, Prompt,
+    },
+    style::{SelectionStyle, TreeStyle},
+};
+
+use super::{Tree, TreeItem, TreeNode};
+
+const DEFAULT_OPTIONS_COUNT: u16 = 10;
+
+/// Prompt that allows to select one node from a tree.
+/// Right arrow expands the highlighted node, Left arrow collapses it. Typing filters the nodes
+/// and expands the ancestors of the matching ones.
+///
+/// ```rust
+/// use cli_prompts::{
+///     prompts::{TreeSelect, TreeNode, TreeItem, AbortReason},
+///     DisplayPrompt,
+/// };
+///
+/// fn main() {
+///     let projects = TreeNode::new("acme", "acme").children([
+///         TreeNode::new("web", "web").children([
+///             TreeNode::new("staging", "web-staging"),
+///             TreeNode::new("production", "web-production"),
+///         ]),
+///         TreeNode::new("api", "api").child(TreeNode::new("staging", "api-staging")),
+///     ]);
+///
+///     let prompt = TreeSelect::new("Select the environment", [projects]).leaves_only(true);
+///     let selection : Result<TreeItem<&str>, AbortReason> = prompt.display();
+///     match selection {
+///         Ok(item) => println!("Deploying to {} ({})", item.value, item.path.join("/")),
+///         Err(abort_reason) => println!("Prompt is aborted because of {:?}", abort_reason),
+///     }
+/// }
+/// ```
+pub struct TreeSelect<T> {
+    label: String,
+    tree: Tree<T>,
+    current_selection: usize,
+    max_options: u16,
+    current_filter: String,
+    leaves_only: bool,
+    is_submitted: bool,
+    is_aborted: bool,
+    style: SelectionStyle,
+    tree_style: TreeStyle,
+}
+
+impl<T> TreeSelect<T> {
+    /// Create new prompt with the given label and the root nodes of the tree
+    pub fn new<S, I>(label: S, roots: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = TreeNode<T>>,
+    {
+        let style = SelectionStyle::default();
+        let mut tree = Tree::new(roots);
+        tree.set_markup(style.label_style.data_markup_mode());
+
+        TreeSelect {
+            label: label.into(),
+            tree,
+            current_selection: 0,
+            max_options: DEFAULT_OPTIONS_COUNT,
+            current_filter: String::new(),
+            leaves_only: false,
+            is_submitted: false,
+            is_aborted: false,
+            style,
+            tree_style: TreeStyle::default(),
+        }
+    }
+
+    /// Set maximum number of nodes that can be displayed on the screen
+    pub fn displayed_options_count(mut self, options_count: u16) -> Self {
+        self.max_options = options_count;
+        self
+    }
+
+    /// If set to `true`, only the nodes without children can be selected. Pressing Enter on
+    /// other nodes expands or collapses them
+    pub fn leaves_only(mut self, leaves_only: bool) -> Self {
+        self.leaves_only = leaves_only;
+        self
+    }
+
+    /// Set the prompt style
+    pub fn style(mut self, style: SelectionStyle) -> Self {
+        self.tree.set_markup(style.label_style.data_markup_mode());
+        self.style = style;
+        self
+    }
+
+    /// Set the style of the tree
+    pub fn tree_style(mut self, tree_style: TreeStyle) -> Self {
+        self.tree_style = tree_style;
+        self
+    }
+
+    fn highlighted_node(&self) -> Option<usize> {
+        self.tree
+            .options()
+            .filtered_options()
+            .get(self.current_selection)
+            .copied()
+    }
+
+    fn highlight_node(&mut self, node: usize) {
+        if let Some(index) = self
+            .tree
+            .options()
+            .filtered_options()
+            .iter()
+            .position(|&n| n == node)
+        {
+            self.current_selection = index;
+        }
+    }
+
+    fn apply_filter(&mut self) {
+        self.tree.filter(&self.current_filter);
+        self.current_selection = 0;
+        if let Some(node) = self.tree.first_match() {
+            self.highlight_node(node);
+        }
+    }
+}
+
+impl<T> MultiOptionPrompt<T> for TreeSelect<T> {
+    fn max_options_count(&self) -> u16 {
+        self.max_options
+    }
+
+    fn options(&self) -> &Options<T> {
+        self.tree.options()
+    }
+
+    fn currently_selected_index(&self) -> usize {
+        self.current_selection
+    }
+
+    fn draw_scroll_indicator(&self, text: &str, cmd_buffer: &mut impl CommandBuffer) {
+        self.style
+            .scroll_indicator_formatting
+            .print(text, cmd_buffer);
+    }
+
+    fn draw_option(
+        &self,
+        option_index: usize,
+        option_label: &str,
+        is_selected: bool,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        let (marker, formatting) = if is_selected {
+            (
+                &self.style.selected_marker,
+                &self.style.selected_option_formatting,
+            )
+        } else {
+            (
+                &self.style.not_selected_marker,
+                &self.style.option_formatting,
+            )
+        };
+
+        marker.print(cmd_buffer);
+        self.tree_style.print_node_prefix(
+            self.tree.depth(option_index),
+            self.tree.has_children(option_index),
+            self.tree.is_expanded(option_index),
+            cmd_buffer,
+        );
+        self.style
+            .label_style
+            .print_data_text(option_label, formatting, cmd_buffer);
+    }
+
+    fn draw_header(&self, commands: &mut impl CommandBuffer, is_submitted: bool) {
+        if is_submitted {
+            if let Some(node) = self.highlighted_node() {
+                let path = self.tree.path(node).join(&self.tree_style.path_separator);
+                self.style.label_style.print_data_text(
+                    &path,
+                    &self.style.submitted_formatting,
+                    commands,
+                );
+            }
+        } else {
+            self.style
+                .filter_formatting
+                .print(&self.current_filter, commands);
+        }
+    }
+}
+
+impl<T> Prompt<TreeItem<T>> for TreeSelect<T> {
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        if self.is_aborted {
+            return self.style.label_style.print_aborted(&self.label, commands);
+        }
+
+        self.draw_multioption(
+            &self.label,
+            self.is_submitted,
+            &self.style.label_style,
+            commands,
+        );
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<TreeItem<T>> {
+        if let Some(new_selection) = self.navigate(&key) {
+            self.current_selection = new_selection;
+            return EventOutcome::Continue;
+        }
+
+        let highlighted_node = self.highlighted_node();
+        match (key, highlighted_node) {
+            (Key::Right, Some(node)) => {
+                let node = self.tree.expand_or_descend(node);
+                self.highlight_node(node);
+                EventOutcome::Continue
+            }
+            (Key::Left, Some(node)) => {
+                let node = self.tree.collapse_or_ascend(node);
+                self.highlight_node(node);
+                EventOutcome::Continue
+            }
+            (Key::Char(c), _) => {
+                self.current_filter.push(c);
+                self.apply_filter();
+                EventOutcome::Continue
+            }
+            (Key::Backspace, _) if !self.current_filter.is_empty() => {
+                self.current_filter.pop();
+                self.apply_filter();
+                EventOutcome::Continue
+            }
+            (Key::Enter, Some(node)) if self.leaves_only && self.tree.has_children(node) => {
+                let is_expanded = self.tree.is_expanded(node);
+                self.tree.set_expanded(node, !is_expanded);
+                self.highlight_node(node);
+                EventOutcome::Continue
+            }
+            (Key::Enter, Some(node)) => {
+                self.is_submitted = true;
+                match self.tree.take_items(&[node]).pop() {
+                    Some(item) => EventOutcome::Done(item),
+                    None => EventOutcome::Continue,
+                }
+            }
+            (Key::Esc, _) => EventOutcome::Abort(AbortReason::Interrupt),
+            _ => EventOutcome::Continue,
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.is_aborted = true;
+    }
+}
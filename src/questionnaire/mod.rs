@@ -0,0 +1,324 @@
+//! Questionnaires that are defined declaratively in JSON, TOML or YAML files.
+//!
+//! The definition is a list of questions, each of them is asked with one of the built-in
+//! prompts. The answers are returned as a JSON object with a field per answered question.
+//!
+//! ```yaml
+//! questions:
+//!   - name: project
+//!     type: input
+//!     label: Project name
+//!     validate:
+//!       required: true
+//!       regex: "^[a-z][a-z0-9-]*$"
+//!   - name: use_database
+//!     type: confirm
+//!     label: Use a database?
+//!   - name: engine
+//!     type: select
+//!     label: Database engine
+//!     options: [postgres, mysql, sqlite]
+//!     when:
+//!       answer: use_database
+//!   - name: port
+//!     type: number
+//!     label: Database port
+//!     integer: true
+//!     default: 5432
+//!     validate:
+//!       min: 1
+//!       max: 65535
+//!     when:
+//!       answer: engine
+//!       one_of: [postgres, mysql]
+//! ```
+//!
+//! This module is available with the `serde` feature.
+
+mod question;
+
+pub use question::{Condition, Question, QuestionKind, Validation};
+
+use std::{collections::HashSet, fmt::Display, path::Path};
+
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    prompts::{AbortReason, Answers, Form},
+    DisplayPrompt,
+};
+
+/// List of questions that can be loaded from a file and asked one by one
+///
+/// ```rust,no_run
+/// use cli_prompts::questionnaire::Questionnaire;
+///
+/// fn main() {
+///     let answers = Questionnaire::from_file("onboarding.yaml").and_then(|q| q.run());
+///     match answers {
+///         Ok(answers) => println!("{}", answers),
+///         Err(error) => println!("Failed to complete the questionnaire: {}", error),
+///     }
+/// }
+/// ```
+#[derive(Deserialize, Clone, Debug)]
+pub struct Questionnaire {
+    /// Questions in the order they are asked
+    pub questions: Vec<Question>,
+
+    /// Whether the answers are reviewed before the questionnaire is completed
+    #[serde(default = "default_review")]
+    pub review: bool,
+}
+
+/// Errors that can occur while loading or running the questionnaire
+#[derive(Debug)]
+pub enum QuestionnaireError {
+    /// The definition file couldn't be read
+    Io(std::io::Error),
+
+    /// The definition couldn't be parsed
+    Parse(String),
+
+    /// The definition is parsed, but is not valid
+    InvalidDefinition(String),
+
+    /// The answers couldn't be converted to the requested type
+    Deserialize(serde_json::Error),
+
+    /// One of the prompts was aborted
+    Aborted(AbortReason),
+}
+
+impl Questionnaire {
+    /// Loads the questionnaire from a file. The format is detected by the file extension:
+    /// `json`, `toml`, `yaml` or `yml`
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, QuestionnaireError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(QuestionnaireError::Io)?;
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("json") => Self::from_json(&contents),
+            Some("toml") => Self::from_toml(&contents),
+            Some("yaml") | Some("yml") => Self::from_yaml(&contents),
+            _ => Err(QuestionnaireError::Parse(format!(
+                "unsupported file format: {}",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Parses the questionnaire definition in JSON format
+    pub fn from_json(definition: &str) -> Result<Self, QuestionnaireError> {
+        serde_json::from_str::<Self>(definition)
+            .map_err(|e| QuestionnaireError::Parse(e.to_string()))
+            .and_then(Self::checked)
+    }
+
+    /// Parses the questionnaire definition in TOML format. The questions are the array of
+    /// tables named `questions`
+    pub fn from_toml(definition: &str) -> Result<Self, QuestionnaireError> {
+        toml::from_str::<Self>(definition)
+            .map_err(|e| QuestionnaireError::Parse(e.to_string()))
+            .and_then(Self::checked)
+    }
+
+    /// Parses the questionnaire definition in YAML format
+    pub fn from_yaml(definition: &str) -> Result<Self, QuestionnaireError> {
+        serde_yaml::from_str::<Self>(definition)
+            .map_err(|e| QuestionnaireError::Parse(e.to_string()))
+            .and_then(Self::checked)
+    }
+
+    /// Asks the questions and returns the answers as a JSON object
+    pub fn run(&self) -> Result<Value, QuestionnaireError> {
+        let answers = self.form().display().map_err(QuestionnaireError::Aborted)?;
+        Ok(self.collect_answers(answers))
+    }
+
+    /// Asks the questions and deserializes the answers into the given type
+    pub fn run_into<T: DeserializeOwned>(&self) -> Result<T, QuestionnaireError> {
+        serde_json::from_value(self.run()?).map_err(QuestionnaireError::Deserialize)
+    }
+
+    /// Creates the form that asks the questions. Its answers are `serde_json::Value`s
+    pub fn form(&self) -> Form {
+        let form = if self.review {
+            Form::new()
+        } else {
+            Form::new().without_review()
+        };
+
+        self.questions
+            .iter()
+            .fold(form, |form, question| question.add_to(form))
+    }
+
+    /// Collects the answers of the form created by `form` into a JSON object
+    pub fn collect_answers(&self, mut answers: Answers) -> Value {
+        let fields: Map<String, Value> = self
+            .questions
+            .iter()
+            .filter_map(|q| {
+                answers
+                    .remove::<Value>(&q.name)
+                    .map(|a| (q.name.clone(), a))
+            })
+            .collect();
+        Value::Object(fields)
+    }
+
+    fn checked(self) -> Result<Self, QuestionnaireError> {
+        let mut names = HashSet::new();
+        for question in &self.questions {
+            if let Some(condition) = question.when.as_ref() {
+                if !names.contains(condition.answer.as_str()) {
+                    return Err(QuestionnaireError::InvalidDefinition(format!(
+                        "question '{}' depends on '{}', which is not asked before it",
+                        question.name, condition.answer
+                    )));
+                }
+            }
+
+            if !names.insert(question.name.as_str()) {
+                return Err(QuestionnaireError::InvalidDefinition(format!(
+                    "question '{}' is defined more than once",
+                    question.name
+                )));
+            }
+
+            question.check()?;
+        }
+
+        Ok(self)
+    }
+}
+
+impl Display for QuestionnaireError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QuestionnaireError::Io(error) => write!(f, "I/O error: {}", error),
+            QuestionnaireError::Parse(error) => write!(f, "parse error: {}", error),
+            QuestionnaireError::InvalidDefinition(error) => {
+                write!(f, "invalid definition: {}", error)
+            }
+            QuestionnaireError::Deserialize(error) => write!(f, "invalid answers: {}", error),
+            QuestionnaireError::Aborted(reason) => write!(f, "aborted: {:?}", reason),
+        }
+    }
+}
+
+impl std::error::Error for QuestionnaireError {}
+
+fn default_review() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{input::Key, prompts::EventOutcome, prompts::Prompt};
+
+    const DEFINITION: &str = r#"
+        review = false
+
+        [[questions]]
+        name = "name"
+        type = "input"
+        label = "Name"
+        validate = { required = true, regex = "^[a-z]+$" }
+
+        [[questions]]
+        name = "use_database"
+        type = "confirm"
+        label = "Use a database?"
+
+        [[questions]]
+        name = "port"
+        type = "number"
+        label = "Port"
+        integer = true
+        default = 5432
+        validate = { min = 1, max = 65535 }
+        when = { answer = "use_database", equals = true }
+    "#;
+
+    fn answer(questionnaire: &Questionnaire, keys: &str) -> Value {
+        let mut form = questionnaire.form();
+        for key in keys.chars() {
+            let key = if key == '\n' {
+                Key::Enter
+            } else {
+                Key::Char(key)
+            };
+            if let EventOutcome::Done(answers) = form.on_key_pressed(key) {
+                return questionnaire.collect_answers(answers);
+            }
+        }
+        panic!("The questionnaire is expected to be completed");
+    }
+
+    #[test]
+    fn asks_questions_from_toml() {
+        let questionnaire = Questionnaire::from_toml(DEFINITION).unwrap();
+
+        let answers = answer(&questionnaire, "Ann\nann\ny\n70000\n8080\n");
+        assert_eq!(
+            answers,
+            serde_json::json!({ "name": "ann", "use_database": true, "port": 8080 })
+        );
+
+        let answers = answer(&questionnaire, "ann\ny\n\n");
+        assert_eq!(answers["port"], 5432);
+
+        let answers = answer(&questionnaire, "ann\nn\n");
+        assert_eq!(
+            answers,
+            serde_json::json!({ "name": "ann", "use_database": false })
+        );
+    }
+
+    #[test]
+    fn loads_json_and_yaml() {
+        let json = r#"{ "questions": [
+            { "name": "color", "type": "select", "label": "Color", "options": ["red", "blue"] }
+        ] }"#;
+        let yaml = "questions:\n  - name: secret\n    type: password\n    message: Secret\n";
+
+        assert!(matches!(
+            Questionnaire::from_json(json).unwrap().questions[0].kind,
+            QuestionKind::Select { .. }
+        ));
+        assert_eq!(
+            Questionnaire::from_yaml(yaml).unwrap().questions[0].label,
+            "Secret"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_definitions() {
+        let unknown_dependency = r#"{ "questions": [
+            { "name": "a", "type": "confirm", "label": "A", "when": { "answer": "b" } }
+        ] }"#;
+        let invalid_regex = r#"{ "questions": [
+            { "name": "a", "type": "input", "label": "A", "validate": { "regex": "(" } }
+        ] }"#;
+
+        for definition in [unknown_dependency, invalid_regex] {
+            assert!(matches!(
+                Questionnaire::from_json(definition),
+                Err(QuestionnaireError::InvalidDefinition(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn matches_conditions() {
+        let condition: Condition =
+            serde_json::from_value(serde_json::json!({ "answer": "a", "one_of": [1, "x"] }))
+                .unwrap();
+        assert!(condition.matches(Some(&Value::from(1.0))));
+        assert!(!condition.matches(Some(&Value::from("y"))));
+        assert!(!condition.matches(None));
+    }
+}
@@ -0,0 +1,426 @@
+use std::{marker::PhantomData, rc::Rc};
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::{
+    engine::CommandBuffer,
+    input::Key,
+    prompts::{Confirmation, EventOutcome, Form, Input, Multiselect, Password, Prompt, Selection},
+};
+
+use super::QuestionnaireError;
+
+/// Single question of the questionnaire
+#[derive(Deserialize, Clone, Debug)]
+pub struct Question {
+    /// Key of the answer in the resulting object
+    pub name: String,
+
+    /// Text of the prompt
+    #[serde(alias = "message")]
+    pub label: String,
+
+    /// Help message that is displayed by the prompts that support it
+    #[serde(default)]
+    pub help: Option<String>,
+
+    /// Validation rules for the answer
+    #[serde(default)]
+    pub validate: Validation,
+
+    /// Condition on the previous answers that must hold for the question to be asked
+    #[serde(default)]
+    pub when: Option<Condition>,
+
+    /// Kind of the prompt and its specific settings
+    #[serde(flatten)]
+    pub kind: QuestionKind,
+}
+
+/// Kind of the prompt that asks the question. Selected by the `type` field
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum QuestionKind {
+    /// Text input. The answer is a string
+    Input {
+        #[serde(default)]
+        default: Option<String>,
+    },
+
+    /// Text input that doesn't show the typed text. The answer is a string
+    Password {},
+
+    /// Yes or no question. The answer is a boolean
+    Confirm {
+        #[serde(default)]
+        default: bool,
+    },
+
+    /// Selection of one of the options. The answer is the selected option
+    Select {
+        options: Vec<String>,
+        #[serde(default)]
+        default: Option<String>,
+    },
+
+    /// Selection of several options. The answer is an array of the selected options
+    Multiselect {
+        options: Vec<String>,
+        #[serde(default)]
+        default: Vec<String>,
+    },
+
+    /// Numeric input. The answer is a number
+    Number {
+        #[serde(default)]
+        default: Option<f64>,
+        #[serde(default)]
+        integer: bool,
+    },
+}
+
+/// Validation rules of the answer
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct Validation {
+    /// Empty answer is not accepted. For multiselect at least one option has to be selected
+    #[serde(default)]
+    pub required: bool,
+
+    /// Regular expression that the text answer must match
+    #[serde(default)]
+    pub regex: Option<String>,
+
+    /// Minimum value of a number, length of a text or number of the selected options
+    #[serde(default)]
+    pub min: Option<f64>,
+
+    /// Maximum value of a number, length of a text or number of the selected options
+    #[serde(default)]
+    pub max: Option<f64>,
+
+    /// Error message that replaces the default ones
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+/// Condition on the answer to one of the previous questions.
+/// Without any comparison it holds when the answer is truthy: `true`, a non-zero number or a
+/// non-empty string or array
+#[derive(Deserialize, Clone, Debug)]
+pub struct Condition {
+    /// Name of the question which answer is checked
+    #[serde(alias = "question")]
+    pub answer: String,
+
+    /// The answer must be equal to this value
+    #[serde(default)]
+    pub equals: Option<Value>,
+
+    /// The answer must not be equal to this value
+    #[serde(default)]
+    pub not_equals: Option<Value>,
+
+    /// The answer must be equal to one of these values
+    #[serde(default)]
+    pub one_of: Option<Vec<Value>>,
+
+    /// The answer must be an array that contains this value
+    #[serde(default)]
+    pub contains: Option<Value>,
+}
+
+struct Validator {
+    rules: Validation,
+    regex: Option<Regex>,
+}
+
+/// Converts the result of a prompt to JSON value
+struct ValuePrompt<P, T> {
+    prompt: P,
+    output: PhantomData<fn() -> T>,
+}
+
+impl Question {
+    pub(super) fn check(&self) -> Result<(), QuestionnaireError> {
+        let invalid = |message: String| {
+            Err(QuestionnaireError::InvalidDefinition(format!(
+                "question '{}': {}",
+                self.name, message
+            )))
+        };
+
+        if let Some(regex) = self.validate.regex.as_ref() {
+            if let Err(error) = Regex::new(regex) {
+                return invalid(format!("invalid regex: {}", error));
+            }
+        }
+
+        match &self.kind {
+            QuestionKind::Select { options, default } => {
+                if options.is_empty() {
+                    return invalid("no options".into());
+                }
+                if let Some(default) = default.as_ref().filter(|d| !options.contains(d)) {
+                    return invalid(format!("default option '{}' is not in the list", default));
+                }
+            }
+            QuestionKind::Multiselect { options, default } => {
+                if options.is_empty() {
+                    return invalid("no options".into());
+                }
+                if let Some(default) = default.iter().find(|d| !options.contains(d)) {
+                    return invalid(format!("default option '{}' is not in the list", default));
+                }
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn add_to(&self, form: Form) -> Form {
+        let label = self.label.clone();
+        let help = self.help.clone();
+        let validator = Rc::new(Validator::new(self.validate.clone()));
+
+        let form = match self.kind.clone() {
+            QuestionKind::Input { default } => form.step(&self.name, move || {
+                let validator = validator.clone();
+                let mut prompt = Input::new(&label, move |text: &str| {
+                    validator.check_text(text).map(|_| Value::from(text))
+                });
+                if let Some(default) = default.as_ref() {
+                    prompt = prompt.default_value(default);
+                }
+                if let Some(help) = help.as_ref() {
+                    prompt = prompt.help_message(help);
+                }
+                ValuePrompt::new(prompt)
+            }),
+            QuestionKind::Password {} => form.step(&self.name, move || {
+                let validator = validator.clone();
+                let mut prompt = Password::new(&label, move |text: &str| {
+                    validator.check_text(text).map(|_| Value::from(text))
+                });
+                if let Some(help) = help.as_ref() {
+                    prompt = prompt.help_message(help);
+                }
+                ValuePrompt::new(prompt)
+            }),
+            QuestionKind::Confirm { default } => form.step(&self.name, move || {
+                ValuePrompt::new(Confirmation::new(&label).default_positive(default))
+            }),
+            QuestionKind::Select { options, default } => form.step(&self.name, move || {
+                let prompt = Selection::new(&label, options.clone().into_iter());
+                ValuePrompt::new(match default.as_ref() {
+                    Some(default) => prompt.default_option(|o| o == default),
+                    None => prompt,
+                })
+            }),
+            QuestionKind::Multiselect { options, default } => form.step(&self.name, move || {
+                let (min, max) = validator.selections_range();
+                let mut prompt = Multiselect::new(&label, options.clone().into_iter())
+                    .min_selections(min)
+                    .preselect(|o| default.contains(o));
+                if let Some(max) = max {
+                    prompt = prompt.max_selections(max);
+                }
+                if let Some(help) = help.as_ref() {
+                    prompt = prompt.help_message(help);
+                }
+                ValuePrompt::new(prompt)
+            }),
+            QuestionKind::Number { default, integer } => form.step(&self.name, move || {
+                let validator = validator.clone();
+                let mut prompt = Input::new(&label, move |text: &str| {
+                    validator.check_number(text, integer)
+                });
+                if let Some(default) = default {
+                    prompt = prompt.default_value(default.to_string());
+                }
+                if let Some(help) = help.as_ref() {
+                    prompt = prompt.help_message(help);
+                }
+                ValuePrompt::new(prompt)
+            }),
+        };
+
+        match self.when.clone() {
+            Some(condition) => {
+                form.when(move |answers| condition.matches(answers.get::<Value>(&condition.answer)))
+            }
+            None => form,
+        }
+    }
+}
+
+impl Condition {
+    /// Checks the condition against the given answer
+    pub fn matches(&self, answer: Option<&Value>) -> bool {
+        let Some(answer) = answer else {
+            return false;
+        };
+
+        let has_comparison = self.equals.is_some()
+            || self.not_equals.is_some()
+            || self.one_of.is_some()
+            || self.contains.is_some();
+        if !has_comparison {
+            return is_truthy(answer);
+        }
+
+        self.equals
+            .as_ref()
+            .is_none_or(|expected| values_equal(answer, expected))
+            && self
+                .not_equals
+                .as_ref()
+                .is_none_or(|unexpected| !values_equal(answer, unexpected))
+            && self
+                .one_of
+                .as_ref()
+                .is_none_or(|values| values.iter().any(|v| values_equal(answer, v)))
+            && self.contains.as_ref().is_none_or(|expected| {
+                answer
+                    .as_array()
+                    .is_some_and(|items| items.iter().any(|v| values_equal(v, expected)))
+            })
+    }
+}
+
+impl Validator {
+    fn new(rules: Validation) -> Self {
+        let regex = rules.regex.as_ref().and_then(|r| Regex::new(r).ok());
+        Validator { rules, regex }
+    }
+
+    fn error<T>(&self, default_message: String) -> Result<T, String> {
+        Err(self.rules.message.clone().unwrap_or(default_message))
+    }
+
+    fn check_text(&self, text: &str) -> Result<(), String> {
+        let length = text.chars().count() as f64;
+        if text.is_empty() {
+            return if self.rules.required {
+                self.error("The answer is required".into())
+            } else {
+                Ok(())
+            };
+        }
+
+        if self.rules.min.is_some_and(|min| length < min) {
+            return self.error(format!(
+                "The answer must be at least {} characters long",
+                self.rules.min.unwrap_or_default()
+            ));
+        }
+        if self.rules.max.is_some_and(|max| length > max) {
+            return self.error(format!(
+                "The answer must be at most {} characters long",
+                self.rules.max.unwrap_or_default()
+            ));
+        }
+        if self.regex.as_ref().is_some_and(|r| !r.is_match(text)) {
+            return self.error("The answer has invalid format".into());
+        }
+
+        Ok(())
+    }
+
+    fn check_number(&self, text: &str, integer: bool) -> Result<Value, String> {
+        let text = text.trim();
+        if text.is_empty() && !self.rules.required {
+            return Ok(Value::Null);
+        }
+
+        let number = if integer {
+            text.parse::<i64>().map(|n| n as f64).ok()
+        } else {
+            text.parse::<f64>().ok().filter(|n| n.is_finite())
+        };
+        let Some(number) = number else {
+            return self.error(if integer {
+                "The answer must be an integer number".into()
+            } else {
+                "The answer must be a number".into()
+            });
+        };
+
+        match (self.rules.min, self.rules.max) {
+            (Some(min), Some(max)) if number < min || number > max => {
+                self.error(format!("The number must be between {} and {}", min, max))
+            }
+            (Some(min), _) if number < min => {
+                self.error(format!("The number must not be less than {}", min))
+            }
+            (_, Some(max)) if number > max => {
+                self.error(format!("The number must not be greater than {}", max))
+            }
+            _ if integer => Ok(Value::from(number as i64)),
+            _ => Ok(Value::from(number)),
+        }
+    }
+
+    fn selections_range(&self) -> (usize, Option<usize>) {
+        let min = self
+            .rules
+            .min
+            .map(|min| min as usize)
+            .unwrap_or(if self.rules.required { 1 } else { 0 });
+        (min, self.rules.max.map(|max| max as usize))
+    }
+}
+
+impl<P, T> ValuePrompt<P, T>
+where
+    P: Prompt<T>,
+{
+    fn new(prompt: P) -> Self {
+        ValuePrompt {
+            prompt,
+            output: PhantomData,
+        }
+    }
+}
+
+impl<P, T> Prompt<Value> for ValuePrompt<P, T>
+where
+    P: Prompt<T>,
+    T: Into<Value>,
+{
+    fn draw(&self, commands: &mut impl CommandBuffer) {
+        self.prompt.draw(commands);
+    }
+
+    fn on_key_pressed(&mut self, key: Key) -> EventOutcome<Value> {
+        match self.prompt.on_key_pressed(key) {
+            EventOutcome::Done(result) => EventOutcome::Done(result.into()),
+            EventOutcome::Continue => EventOutcome::Continue,
+            EventOutcome::Abort(reason) => EventOutcome::Abort(reason),
+        }
+    }
+
+    fn on_abort(&mut self) {
+        self.prompt.on_abort();
+    }
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
+
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a == b,
+    }
+}
@@ -1,4 +1,8 @@
-#[derive(Copy, Clone)]
+use std::{fmt::Display, str::FromStr};
+
+use crate::engine::ColorDepth;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
 pub enum Color {
     /// Reset the color to default value
     Reset,
@@ -32,3 +36,280 @@ pub enum Color {
     AnsiValue(u8),
 }
 
+
+impl FromStr for Color {
+    type Err = String;
+
+    /// Parses a color from its name (e.g. `red`, `dark-green`), a hex code (`#ff8800`) or an
+    /// ANSI color number (`0` to `255`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace(['-', '_', ' '], "");
+        let color = match name.as_str() {
+            "reset" | "default" => Color::Reset,
+            "black" => Color::Black,
+            "darkgrey" | "darkgray" => Color::DarkGrey,
+            "red" => Color::Red,
+            "darkred" => Color::DarkRed,
+            "green" => Color::Green,
+            "darkgreen" => Color::DarkGreen,
+            "yellow" => Color::Yellow,
+            "darkyellow" => Color::DarkYellow,
+            "blue" => Color::Blue,
+            "darkblue" => Color::DarkBlue,
+            "magenta" => Color::Magenta,
+            "darkmagenta" => Color::DarkMagenta,
+            "cyan" => Color::Cyan,
+            "darkcyan" => Color::DarkCyan,
+            "white" => Color::White,
+            "grey" | "gray" => Color::Grey,
+            _ => {
+                if let Some(hex) = name.strip_prefix('#') {
+                    parse_hex(hex).ok_or_else(|| format!("Invalid hex color: {}", s))?
+                } else {
+                    name.parse::<u8>()
+                        .map(Color::AnsiValue)
+                        .map_err(|_| format!("Unknown color: {}", s))?
+                }
+            }
+        };
+
+        Ok(color)
+    }
+}
+
+/// The basic colors in the order of their ANSI numbers along with their RGB values in xterm
+const ANSI_16_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::DarkRed, (205, 0, 0)),
+    (Color::DarkGreen, (0, 205, 0)),
+    (Color::DarkYellow, (205, 205, 0)),
+    (Color::DarkBlue, (0, 0, 238)),
+    (Color::DarkMagenta, (205, 0, 205)),
+    (Color::DarkCyan, (0, 205, 205)),
+    (Color::Grey, (229, 229, 229)),
+    (Color::DarkGrey, (127, 127, 127)),
+    (Color::Red, (255, 0, 0)),
+    (Color::Green, (0, 255, 0)),
+    (Color::Yellow, (255, 255, 0)),
+    (Color::Blue, (92, 92, 255)),
+    (Color::Magenta, (255, 0, 255)),
+    (Color::Cyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of the red, green and blue channels of the 6x6x6 color cube of the 256 color palette
+const ANSI_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl Color {
+    /// Converts the color to the nearest one that can be displayed with the given color depth.
+    /// Returns `None` for the monochrome terminals
+    pub fn downgrade(self, depth: ColorDepth) -> Option<Color> {
+        match (self, depth) {
+            (_, ColorDepth::Monochrome) => None,
+            (Color::Rgb { r, g, b }, ColorDepth::Ansi256) => Some(nearest_ansi_256(r, g, b)),
+            (Color::Rgb { r, g, b }, ColorDepth::Ansi16) => Some(nearest_ansi_16(r, g, b)),
+            (Color::AnsiValue(value), ColorDepth::Ansi16) => {
+                let (r, g, b) = ansi_256_to_rgb(value);
+                Some(nearest_ansi_16(r, g, b))
+            }
+            (color, _) => Some(color),
+        }
+    }
+
+    /// Returns the ANSI number of the basic color or the color from the 256 color palette
+    pub fn ansi_value(self) -> Option<u8> {
+        match self {
+            Color::AnsiValue(value) => Some(value),
+            Color::Reset | Color::Rgb { .. } => None,
+            color => ANSI_16_COLORS
+                .iter()
+                .position(|(c, _)| *c == color)
+                .map(|index| index as u8),
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    channel(r1, r2) + channel(g1, g2) + channel(b1, b2)
+}
+
+fn nearest_ansi_16(r: u8, g: u8, b: u8) -> Color {
+    ANSI_16_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+fn nearest_ansi_256(r: u8, g: u8, b: u8) -> Color {
+    let cube_index = |channel: u8| {
+        (0..ANSI_CUBE_LEVELS.len())
+            .min_by_key(|&i| ANSI_CUBE_LEVELS[i].abs_diff(channel))
+            .unwrap_or_default() as u8
+    };
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube_color = 16 + 36 * ri + 6 * gi + bi;
+
+    let average = ((u16::from(r) + u16::from(g) + u16::from(b)) / 3) as u8;
+    let grey_color = 232 + (average.saturating_sub(3) / 10).min(23);
+
+    let target = (r, g, b);
+    if distance(ansi_256_to_rgb(grey_color), target) < distance(ansi_256_to_rgb(cube_color), target)
+    {
+        Color::AnsiValue(grey_color)
+    } else {
+        Color::AnsiValue(cube_color)
+    }
+}
+
+fn ansi_256_to_rgb(value: u8) -> (u8, u8, u8) {
+    match value {
+        0..=15 => ANSI_16_COLORS[usize::from(value)].1,
+        16..=231 => {
+            let index = value - 16;
+            let level = |i: u8| ANSI_CUBE_LEVELS[usize::from(i)];
+            (level(index / 36), level(index / 6 % 6), level(index % 6))
+        }
+        _ => {
+            let level = 8 + (value - 232) * 10;
+            (level, level, level)
+        }
+    }
+}
+
+impl Display for Color {
+    /// Writes the color in the format that is accepted by `from_str`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Color::Reset => "reset",
+            Color::Black => "black",
+            Color::DarkGrey => "dark-grey",
+            Color::Red => "red",
+            Color::DarkRed => "dark-red",
+            Color::Green => "green",
+            Color::DarkGreen => "dark-green",
+            Color::Yellow => "yellow",
+            Color::DarkYellow => "dark-yellow",
+            Color::Blue => "blue",
+            Color::DarkBlue => "dark-blue",
+            Color::Magenta => "magenta",
+            Color::DarkMagenta => "dark-magenta",
+            Color::Cyan => "cyan",
+            Color::DarkCyan => "dark-cyan",
+            Color::White => "white",
+            Color::Grey => "grey",
+            Color::Rgb { r, g, b } => return write!(f, "#{:02x}{:02x}{:02x}", r, g, b),
+            Color::AnsiValue(value) => return write!(f, "{}", value),
+        };
+        f.write_str(name)
+    }
+}
+
+/// Colors are serialized as strings in the format of `from_str`. ANSI color numbers can also be
+/// given as integers
+#[cfg(feature = "serde")]
+impl serde::Serialize for Color {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Color {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ColorVisitor;
+
+        impl serde::de::Visitor<'_> for ColorVisitor {
+            type Value = Color;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a color name, a hex code or an ANSI color number")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Color, E> {
+                value.parse().map_err(E::custom)
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Color, E> {
+                u8::try_from(value)
+                    .map(Color::AnsiValue)
+                    .map_err(|_| E::custom(format!("Invalid ANSI color: {}", value)))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Color, E> {
+                u8::try_from(value)
+                    .map(Color::AnsiValue)
+                    .map_err(|_| E::custom(format!("Invalid ANSI color: {}", value)))
+            }
+        }
+
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Color> {
+    let channel = |range| u8::from_str_radix(hex.get(range)?, 16).ok();
+    match hex.len() {
+        6 => Some(Color::Rgb {
+            r: channel(0..2)?,
+            g: channel(2..4)?,
+            b: channel(4..6)?,
+        }),
+        3 => {
+            let short_channel = |i| channel(i..i + 1).map(|c| c * 17);
+            Some(Color::Rgb {
+                r: short_channel(0)?,
+                g: short_channel(1)?,
+                b: short_channel(2)?,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_names_hex_and_ansi_values() {
+        assert!(matches!("Dark-Green".parse(), Ok(Color::DarkGreen)));
+        assert!(matches!(
+            "#ff8800".parse(),
+            Ok(Color::Rgb { r: 255, g: 136, b: 0 })
+        ));
+        assert!(matches!("#0f0".parse(), Ok(Color::Rgb { r: 0, g: 255, b: 0 })));
+        assert!(matches!("208".parse(), Ok(Color::AnsiValue(208))));
+        assert!("purple".parse::<Color>().is_err());
+    }
+
+    #[test]
+    fn downgrades_to_nearest_supported_color() {
+        let orange: Color = "#ff8800".parse().unwrap();
+        assert_eq!(orange.downgrade(ColorDepth::TrueColor), Some(orange));
+        assert_eq!(
+            orange.downgrade(ColorDepth::Ansi256),
+            Some(Color::AnsiValue(208))
+        );
+        assert_eq!(orange.downgrade(ColorDepth::Ansi16), Some(Color::DarkYellow));
+
+        let dark_grey: Color = "#282828".parse().unwrap();
+        assert_eq!(
+            dark_grey.downgrade(ColorDepth::Ansi256),
+            Some(Color::AnsiValue(235))
+        );
+        assert_eq!(
+            Color::AnsiValue(196).downgrade(ColorDepth::Ansi16),
+            Some(Color::Red)
+        );
+        assert_eq!(Color::Green.downgrade(ColorDepth::Monochrome), None);
+    }
+
+    #[test]
+    fn displays_parsable_colors() {
+        for color in ["dark-green", "#ff8800", "208"] {
+            assert_eq!(color.parse::<Color>().unwrap().to_string(), color);
+        }
+    }
+}
@@ -1,9 +1,11 @@
-use crate::engine::CommandBuffer;
+use crate::engine::{ColorDepth, CommandBuffer};
 
 use super::color::Color;
 
 /// Set of text formatting options
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
 pub enum FormattingOption {
     /// Reset the formatting
     Reset,
@@ -19,34 +21,57 @@ pub enum FormattingOption {
 
     /// Cross the text out
     CrossedOut,
+
+    /// Make the text dim
+    Dim,
+
+    /// Swap the text and the background colors
+    Reverse,
+
+    /// Make the text blink
+    Blink,
+
+    /// Hide the text
+    Hidden,
+
+    /// Underline the text with a double line
+    DoubleUnderline,
+
+    /// Underline the text with a wavy line
+    CurlyUnderline,
+
+    /// Underline the text with a dotted line
+    DottedUnderline,
+
+    /// Underline the text with a dashed line
+    DashedUnderline,
 }
 
 /// Represent the text formatting which includes
 /// - Color of the text
 /// - Color of the background
+/// - Color of the underline
 /// - Text formatting options
-#[derive(Clone)]
+#[derive(Clone, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Formatting {
     /// Text color
+    #[cfg_attr(feature = "serde", serde(alias = "fg"))]
     pub foreground_color: Option<Color>,
 
     /// Background color
+    #[cfg_attr(feature = "serde", serde(alias = "bg"))]
     pub background_color: Option<Color>,
 
+    /// Underline color. Makes the text underlined if none of the underline options is set
+    pub underline_color: Option<Color>,
+
     /// List of formatting options
+    #[cfg_attr(feature = "serde", serde(alias = "options"))]
     pub text_formatting: Vec<FormattingOption>,
 }
 
-impl Default for Formatting {
-    fn default() -> Self {
-        Formatting {
-            foreground_color: None,
-            background_color: None,
-            text_formatting: vec![],
-        }
-    }
-}
-
 impl Formatting {
 
     /// Set the text color
@@ -85,6 +110,60 @@ impl Formatting {
         self
     }
 
+    /// Make the text dim
+    pub fn dim(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::Dim);
+        self
+    }
+
+    /// Swap the text and the background colors
+    pub fn reverse(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::Reverse);
+        self
+    }
+
+    /// Make the text blink
+    pub fn blink(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::Blink);
+        self
+    }
+
+    /// Hide the text
+    pub fn hidden(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::Hidden);
+        self
+    }
+
+    /// Underline the text with a double line
+    pub fn double_underline(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::DoubleUnderline);
+        self
+    }
+
+    /// Underline the text with a wavy line
+    pub fn curly_underline(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::CurlyUnderline);
+        self
+    }
+
+    /// Underline the text with a dotted line
+    pub fn dotted_underline(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::DottedUnderline);
+        self
+    }
+
+    /// Underline the text with a dashed line
+    pub fn dashed_underline(mut self) -> Self {
+        self.text_formatting.push(FormattingOption::DashedUnderline);
+        self
+    }
+
+    /// Set the underline color
+    pub fn underline_color(mut self, color: Color) -> Self {
+        self.underline_color = Some(color);
+        self
+    }
+
     /// Reset text formatting (colors and options)
     pub fn reset() -> Self {
         let mut f = Self::default();
@@ -92,6 +171,37 @@ impl Formatting {
         f
     }
 
+    /// Converts the colors to the ones that can be displayed with the given color depth.
+    /// The colors are removed for the monochrome terminals, while the text formatting options
+    /// are kept.
+    ///
+    /// The underline styles and colors are supported by far fewer terminals than the basic
+    /// attributes. They are only kept for the true color terminals, the rest get a plain underline
+    pub fn downgrade(&self, depth: ColorDepth) -> Formatting {
+        let has_styled_underline = depth == ColorDepth::TrueColor;
+        let mut text_formatting: Vec<FormattingOption> = self
+            .text_formatting
+            .iter()
+            .map(|&option| match option {
+                o if o.is_styled_underline() && !has_styled_underline => {
+                    FormattingOption::Underline
+                }
+                o => o,
+            })
+            .collect();
+        let is_underlined = self.text_formatting.iter().any(|o| o.is_underline());
+        if self.underline_color.is_some() && !is_underlined {
+            text_formatting.push(FormattingOption::Underline);
+        }
+
+        Formatting {
+            foreground_color: self.foreground_color.and_then(|c| c.downgrade(depth)),
+            background_color: self.background_color.and_then(|c| c.downgrade(depth)),
+            underline_color: self.underline_color.filter(|_| has_styled_underline),
+            text_formatting,
+        }
+    }
+
     /// Print the given text using the current formatting to the provided command buffer
     pub fn print(&self, text: impl Into<String>, cmd_buffer: &mut impl CommandBuffer) {
         cmd_buffer.set_formatting(self);
@@ -99,3 +209,50 @@ impl Formatting {
         cmd_buffer.reset_formatting();
     }
 }
+
+impl FormattingOption {
+    /// Whether the option is one of the underline styles other than the plain underline
+    pub fn is_styled_underline(&self) -> bool {
+        matches!(
+            self,
+            FormattingOption::DoubleUnderline
+                | FormattingOption::CurlyUnderline
+                | FormattingOption::DottedUnderline
+                | FormattingOption::DashedUnderline
+        )
+    }
+
+    /// Whether the option underlines the text
+    pub fn is_underline(&self) -> bool {
+        *self == FormattingOption::Underline || self.is_styled_underline()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downgrades_underline_styles() {
+        let error = Formatting::default()
+            .curly_underline()
+            .underline_color(Color::Rgb { r: 255, g: 0, b: 0 });
+        let downgraded = error.downgrade(ColorDepth::TrueColor);
+        assert_eq!(
+            downgraded.text_formatting,
+            [FormattingOption::CurlyUnderline]
+        );
+        assert!(downgraded.underline_color.is_some());
+
+        let downgraded = error.downgrade(ColorDepth::Ansi256);
+        assert_eq!(downgraded.text_formatting, [FormattingOption::Underline]);
+        assert!(downgraded.underline_color.is_none());
+
+        let hint = Formatting::default().dim().underline_color(Color::Red);
+        let downgraded = hint.downgrade(ColorDepth::Monochrome);
+        assert_eq!(
+            downgraded.text_formatting,
+            [FormattingOption::Dim, FormattingOption::Underline]
+        );
+    }
+}
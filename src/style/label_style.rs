@@ -1,17 +1,88 @@
 use crate::engine::CommandBuffer;
 
-use super::{Color, Formatting};
+use super::{
+    markup::{self, MarkupMode},
+    Formatting, Theme,
+};
+
+/// State of the prompt that defines which prefix is displayed before its label
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PromptState {
+    /// The prompt waits for the user's input
+    #[default]
+    Active,
+
+    /// The prompt has been submitted
+    Submitted,
+
+    /// The prompt has been aborted by the user
+    Aborted,
+
+    /// The prompt waits for the user to correct the invalid input
+    Error,
+}
+
+impl PromptState {
+    /// Returns the state of the prompt from whether it has been submitted and whether its input
+    /// is invalid
+    pub fn new(is_submitted: bool, has_error: bool) -> Self {
+        if is_submitted {
+            PromptState::Submitted
+        } else if has_error {
+            PromptState::Error
+        } else {
+            PromptState::Active
+        }
+    }
+}
 
 /// Style for the common part of all prompts: the prompt itself.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct LabelStyle {
     prefix: String,
     prefix_formatting: Formatting,
+    submitted_prefix: String,
+    submitted_prefix_formatting: Formatting,
+    aborted_prefix: String,
+    aborted_prefix_formatting: Formatting,
+    aborted_label_formatting: Formatting,
+    aborted_message: String,
+    aborted_message_formatting: Formatting,
+    error_prefix: String,
+    error_prefix_formatting: Formatting,
     prompt_formatting: Formatting,
+    suffix: String,
+    input_on_new_line: bool,
+    markup: MarkupMode,
+    data_markup: MarkupMode,
 }
 
 impl LabelStyle {
 
+    /// Creates the style from the given theme
+    pub fn from_theme(theme: &Theme) -> Self {
+        LabelStyle {
+            prefix: theme.prefix.clone(),
+            prefix_formatting: theme.prefix_formatting.clone(),
+            submitted_prefix: theme.submitted_prefix.clone(),
+            submitted_prefix_formatting: theme.submitted_prefix_formatting.clone(),
+            aborted_prefix: theme.aborted_prefix.clone(),
+            aborted_prefix_formatting: theme.aborted_prefix_formatting.clone(),
+            aborted_label_formatting: theme.aborted_label_formatting.clone(),
+            aborted_message: theme.aborted_message.clone(),
+            aborted_message_formatting: theme.aborted_message_formatting.clone(),
+            error_prefix: theme.error_prefix.clone(),
+            error_prefix_formatting: theme.error_prefix_formatting.clone(),
+            prompt_formatting: theme.label_formatting.clone(),
+            suffix: theme.label_suffix.clone(),
+            input_on_new_line: theme.input_on_new_line,
+            markup: theme.markup,
+            data_markup: theme.data_markup,
+        }
+    }
+
     /// Sets the string that is displayed before the user's input
     pub fn prefix<S: Into<String>>(mut self, p: S) -> Self {
         self.prefix = p.into();
@@ -24,32 +95,246 @@ impl LabelStyle {
         self
     }
 
+    /// Sets the prefix and its formatting that are displayed after the prompt is submitted
+    pub fn submitted_prefix<S: Into<String>>(mut self, p: S, f: Formatting) -> Self {
+        self.submitted_prefix = p.into();
+        self.submitted_prefix_formatting = f;
+        self
+    }
+
+    /// Sets the prefix and its formatting that are displayed after the prompt is aborted
+    pub fn aborted_prefix<S: Into<String>>(mut self, p: S, f: Formatting) -> Self {
+        self.aborted_prefix = p.into();
+        self.aborted_prefix_formatting = f;
+        self
+    }
+
+    /// Sets the formatting of the label after the prompt is aborted
+    pub fn aborted_label_formatting(mut self, f: Formatting) -> Self {
+        self.aborted_label_formatting = f;
+        self
+    }
+
+    /// Sets the message and its formatting that are displayed after the label of the aborted
+    /// prompt
+    pub fn aborted_message<S: Into<String>>(mut self, m: S, f: Formatting) -> Self {
+        self.aborted_message = m.into();
+        self.aborted_message_formatting = f;
+        self
+    }
+
+    /// Sets the prefix and its formatting that are displayed while the input is invalid
+    pub fn error_prefix<S: Into<String>>(mut self, p: S, f: Formatting) -> Self {
+        self.error_prefix = p.into();
+        self.error_prefix_formatting = f;
+        self
+    }
+
     /// Sets formatting for the user input string
     pub fn prompt_formatting(mut self, f: Formatting) -> Self {
         self.prompt_formatting = f;
         self
     }
 
+    /// Sets the string that is displayed right after the label, `:` by default
+    pub fn suffix<S: Into<String>>(mut self, s: S) -> Self {
+        self.suffix = s.into();
+        self
+    }
+
+    /// Sets whether the input is displayed on the line below the label
+    pub fn input_on_new_line(mut self, on_new_line: bool) -> Self {
+        self.input_on_new_line = on_new_line;
+        self
+    }
+
+    /// Sets how the markup in the label and help messages of the prompt is handled
+    pub fn markup(mut self, mode: MarkupMode) -> Self {
+        self.markup = mode;
+        self
+    }
+
+    /// Sets how the markup in the text that comes from the caller's data, like the options, the
+    /// answers and the messages of the tasks, is handled. Such text is displayed as is by default
+    pub fn data_markup(mut self, mode: MarkupMode) -> Self {
+        self.data_markup = mode;
+        self
+    }
+
+    /// How the markup in the text that comes from the caller's data is handled
+    pub fn data_markup_mode(&self) -> MarkupMode {
+        self.data_markup
+    }
+
     /// Prints the formatted prefix and the input text to the provided command buffer
     pub fn print(&self, text: impl Into<String>, cmd_buffer: &mut impl CommandBuffer) {
-        cmd_buffer.set_formatting(&self.prefix_formatting);
-        cmd_buffer.print(&self.prefix);
-        cmd_buffer.reset_formatting();
-        cmd_buffer.print(" ");
-        cmd_buffer.set_formatting(&self.prompt_formatting);
-        cmd_buffer.print(&text.into());
-        cmd_buffer.print(":");
-        cmd_buffer.reset_formatting();
-        cmd_buffer.print(" ");
+        self.print_with_state(text, PromptState::Active, cmd_buffer);
+    }
+
+    /// Prints the prefix for the given state of the prompt and the input text to the provided
+    /// command buffer
+    pub fn print_with_state(
+        &self,
+        text: impl Into<String>,
+        state: PromptState,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        let (prefix, prefix_formatting) = match state {
+            PromptState::Active => (&self.prefix, &self.prefix_formatting),
+            PromptState::Submitted => (&self.submitted_prefix, &self.submitted_prefix_formatting),
+            PromptState::Aborted => (&self.aborted_prefix, &self.aborted_prefix_formatting),
+            PromptState::Error => (&self.error_prefix, &self.error_prefix_formatting),
+        };
+
+        let label_formatting = if state == PromptState::Aborted {
+            &self.aborted_label_formatting
+        } else {
+            &self.prompt_formatting
+        };
+
+        self.print_label(
+            &text.into(),
+            prefix,
+            prefix_formatting,
+            label_formatting,
+            cmd_buffer,
+        );
+    }
+
+    /// Prints the custom prefix, like a frame of the spinner, and the input text to the provided
+    /// command buffer
+    pub fn print_with_prefix(
+        &self,
+        text: impl Into<String>,
+        prefix: &str,
+        prefix_formatting: &Formatting,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        self.print_label(
+            &text.into(),
+            prefix,
+            prefix_formatting,
+            &self.prompt_formatting,
+            cmd_buffer,
+        );
+    }
+
+    fn print_label(
+        &self,
+        text: &str,
+        prefix: &str,
+        prefix_formatting: &Formatting,
+        label_formatting: &Formatting,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        if !prefix.is_empty() {
+            prefix_formatting.print(prefix, cmd_buffer);
+            cmd_buffer.print(" ");
+        }
+        self.print_text(text, label_formatting, cmd_buffer);
+        label_formatting.print(&self.suffix, cmd_buffer);
+
+        if self.input_on_new_line {
+            cmd_buffer.new_line();
+        } else {
+            cmd_buffer.print(" ");
+        }
+    }
+
+    /// Prints the final frame of the aborted prompt: the label and the aborted message
+    pub fn print_aborted(&self, text: impl Into<String>, cmd_buffer: &mut impl CommandBuffer) {
+        self.print_with_state(text, PromptState::Aborted, cmd_buffer);
+        self.aborted_message_formatting
+            .print(&self.aborted_message, cmd_buffer);
+    }
+
+    /// Prints the text that may contain markup on top of the given formatting
+    pub fn print_text(
+        &self,
+        text: &str,
+        formatting: &Formatting,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        for span in markup::parse_with_mode(text, self.markup) {
+            markup::merge(formatting, &span.formatting).print(span.text, cmd_buffer);
+        }
+    }
+
+    /// Prints the text that comes from the caller's data, like an option, according to the data
+    /// markup mode
+    pub fn print_data_text(
+        &self,
+        text: &str,
+        formatting: &Formatting,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        for span in markup::parse_with_mode(text, self.data_markup) {
+            markup::merge(formatting, &span.formatting).print(span.text, cmd_buffer);
+        }
+    }
+
+    /// Prints the help message that may contain markup in square brackets
+    pub fn print_help_message(
+        &self,
+        help_message: &str,
+        formatting: &Formatting,
+        cmd_buffer: &mut impl CommandBuffer,
+    ) {
+        formatting.print("[", cmd_buffer);
+        self.print_text(help_message, formatting, cmd_buffer);
+        formatting.print("]", cmd_buffer);
+    }
+
+    /// Escapes the text, like the user's input, so that it is displayed as is among the data
+    /// text, e.g. in a synthetic option
+    pub fn escape(&self, text: &str) -> String {
+        match self.data_markup {
+            MarkupMode::Plain => text.to_string(),
+            _ => markup::escape(text),
+        }
+    }
+
+    /// Returns the text without the markup
+    pub fn plain_text(&self, text: &str) -> String {
+        markup::plain_text(text, self.markup)
     }
 }
 
 impl Default for LabelStyle {
     fn default() -> Self {
-        LabelStyle {
-            prefix: "?".into(),
-            prefix_formatting: Formatting::default().bold().foreground_color(Color::Green),
-            prompt_formatting: Formatting::default().bold(),
-        }
+        LabelStyle::from_theme(&Theme::current())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::RecordingBuffer;
+
+    fn rendered(style: &LabelStyle, state: PromptState) -> String {
+        let mut commands = RecordingBuffer::default();
+        style.print_with_state("Name", state, &mut commands);
+        commands.text()
+    }
+
+    #[test]
+    fn switches_prefix_by_state() {
+        let style = LabelStyle::from_theme(&Theme::default())
+            .prefix("?")
+            .submitted_prefix("+", Formatting::default())
+            .error_prefix("!", Formatting::default())
+            .aborted_prefix("x", Formatting::default());
+        assert_eq!(rendered(&style, PromptState::Active), "? Name: ");
+        assert_eq!(rendered(&style, PromptState::Submitted), "+ Name: ");
+        assert_eq!(rendered(&style, PromptState::Error), "! Name: ");
+        assert_eq!(rendered(&style, PromptState::Aborted), "x Name: ");
+    }
+
+    #[test]
+    fn puts_input_on_new_line() {
+        let style = LabelStyle::from_theme(&Theme::default())
+            .suffix(" ›")
+            .input_on_new_line(true);
+        assert_eq!(rendered(&style, PromptState::Active), "? Name ›\n");
     }
 }
@@ -0,0 +1,380 @@
+//! Inline markup that styles parts of the labels, help messages and, optionally, options.
+//!
+//! A style tag lists the formatting options and colors separated by spaces and applies them
+//! until the closing `[/]` tag, which may also repeat the style, like `[/bold]`. The tags can be
+//! nested:
+//!
+//! ```text
+//! Deploy to [bold red]production[/]?
+//! [dim]Press [bold cyan]Tab[/] to switch[/]
+//! [white on dark-red] FAILED [/] [curly-underline]typo[/]
+//! ```
+//!
+//! The colors are the names and hex codes accepted by `Color::from_str`, `on <color>` sets the
+//! background color. `[[` is displayed as a single `[`, and `escape` prepares any text, like the
+//! user's data, to be displayed as is.
+//!
+//! The built-in prompts parse the markup according to the `MarkupMode` of their `LabelStyle`.
+//! The options, answers and task messages come from the caller's data, so their markup is only
+//! parsed if `LabelStyle::data_markup` enables it.
+
+use std::fmt::Display;
+
+use super::{Color, Formatting, FormattingOption};
+
+/// Defines how the markup in the text of the prompts is handled
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum MarkupMode {
+    /// The text is displayed as is
+    Plain,
+
+    /// The valid tags are applied, while the invalid ones are displayed as is
+    #[default]
+    Lenient,
+
+    /// The text with any invalid tag is displayed as is
+    Strict,
+}
+
+/// Part of the text with the same formatting
+#[derive(Clone, Debug)]
+pub struct Span {
+    /// Text of the span
+    pub text: String,
+
+    /// Formatting of the span, combined from all tags the span is enclosed in
+    pub formatting: Formatting,
+}
+
+/// Errors in the markup that are reported in the strict mode
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum MarkupError {
+    /// The tag contains something that is neither a formatting option nor a color
+    UnknownStyle(String),
+
+    /// The tag is opened with `[`, but is not closed with `]`
+    UnterminatedTag,
+
+    /// `[/]` doesn't have a matching opening tag
+    UnmatchedClosingTag,
+
+    /// The style tag isn't closed with `[/]` before the end of the text
+    UnclosedTag(String),
+}
+
+/// Parses the text in the strict mode. Returns an error if any of the tags is invalid
+pub fn parse(text: &str) -> Result<Vec<Span>, MarkupError> {
+    Parser::new(text, MarkupMode::Strict).parse()
+}
+
+/// Parses the text in the lenient mode. Invalid tags are kept in the text as is
+pub fn parse_lenient(text: &str) -> Vec<Span> {
+    Parser::new(text, MarkupMode::Lenient)
+        .parse()
+        .unwrap_or_default()
+}
+
+/// Parses the text in the given mode. The text with invalid markup is returned as a single
+/// span in the strict mode
+pub fn parse_with_mode(text: &str, mode: MarkupMode) -> Vec<Span> {
+    let plain = || {
+        vec![Span {
+            text: text.to_string(),
+            formatting: Formatting::default(),
+        }]
+    };
+
+    match mode {
+        MarkupMode::Plain => plain(),
+        MarkupMode::Lenient => parse_lenient(text),
+        MarkupMode::Strict => parse(text).unwrap_or_else(|_| plain()),
+    }
+}
+
+/// Returns the text without the markup tags
+pub fn plain_text(text: &str, mode: MarkupMode) -> String {
+    parse_with_mode(text, mode)
+        .into_iter()
+        .map(|span| span.text)
+        .collect()
+}
+
+/// Escapes the text so that it is displayed as is
+///
+/// ```rust
+/// use cli_prompts::style::markup;
+///
+/// let branch = "feature/[wip]";
+/// let label = format!("Delete [bold]{}[/]?", markup::escape(branch));
+/// assert_eq!(markup::plain_text(&label, Default::default()), "Delete feature/[wip]?");
+/// ```
+pub fn escape(text: &str) -> String {
+    text.replace('[', "[[")
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    mode: MarkupMode,
+    spans: Vec<Span>,
+    current_text: String,
+    open_tags: Vec<(String, Formatting)>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str, mode: MarkupMode) -> Self {
+        Parser {
+            text,
+            mode,
+            spans: vec![],
+            current_text: String::new(),
+            open_tags: vec![],
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<Span>, MarkupError> {
+        let mut rest = self.text;
+        while let Some(start) = rest.find('[') {
+            self.current_text.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            if let Some(after_escape) = rest.strip_prefix("[[") {
+                self.current_text.push('[');
+                rest = after_escape;
+                continue;
+            }
+
+            let Some(end) = rest.find(']') else {
+                self.fail(MarkupError::UnterminatedTag, rest)?;
+                rest = "";
+                break;
+            };
+
+            let tag = &rest[..=end];
+            self.on_tag(tag)?;
+            rest = &rest[end + 1..];
+        }
+        self.current_text.push_str(rest);
+        self.finish_span();
+
+        if let Some((tag, _)) = self.open_tags.last() {
+            if self.mode == MarkupMode::Strict {
+                return Err(MarkupError::UnclosedTag(tag.clone()));
+            }
+        }
+
+        Ok(self.spans)
+    }
+
+    fn on_tag(&mut self, tag: &str) -> Result<(), MarkupError> {
+        let contents = &tag[1..tag.len() - 1];
+        let is_closing = contents
+            .strip_prefix('/')
+            .is_some_and(|style| style.is_empty() || parse_style(style).is_ok());
+        if is_closing {
+            if self.open_tags.is_empty() {
+                return self.fail(MarkupError::UnmatchedClosingTag, tag);
+            }
+            self.finish_span();
+            self.open_tags.pop();
+            return Ok(());
+        }
+
+        match parse_style(contents) {
+            Ok(formatting) => {
+                self.finish_span();
+                let formatting = merge(&self.current_formatting(), &formatting);
+                self.open_tags.push((contents.to_string(), formatting));
+                Ok(())
+            }
+            Err(error) => self.fail(error, tag),
+        }
+    }
+
+    /// Reports the error in the strict mode or keeps the invalid text as is in the lenient one
+    fn fail(&mut self, error: MarkupError, text: &str) -> Result<(), MarkupError> {
+        if self.mode == MarkupMode::Strict {
+            return Err(error);
+        }
+        self.current_text.push_str(text);
+        Ok(())
+    }
+
+    fn current_formatting(&self) -> Formatting {
+        self.open_tags
+            .last()
+            .map(|(_, formatting)| formatting.clone())
+            .unwrap_or_default()
+    }
+
+    fn finish_span(&mut self) {
+        if !self.current_text.is_empty() {
+            self.spans.push(Span {
+                text: std::mem::take(&mut self.current_text),
+                formatting: self.current_formatting(),
+            });
+        }
+    }
+}
+
+fn parse_style(contents: &str) -> Result<Formatting, MarkupError> {
+    let mut formatting = Formatting::default();
+    let mut words = contents.split_whitespace();
+    let unknown = |word: &str| MarkupError::UnknownStyle(word.to_string());
+
+    if contents.trim().is_empty() {
+        return Err(unknown(contents));
+    }
+
+    while let Some(word) = words.next() {
+        let option = match word.to_lowercase().as_str() {
+            "bold" => Some(FormattingOption::Bold),
+            "italic" => Some(FormattingOption::Italic),
+            "underline" => Some(FormattingOption::Underline),
+            "strike" | "crossed-out" => Some(FormattingOption::CrossedOut),
+            "dim" => Some(FormattingOption::Dim),
+            "reverse" => Some(FormattingOption::Reverse),
+            "blink" => Some(FormattingOption::Blink),
+            "hidden" => Some(FormattingOption::Hidden),
+            "double-underline" => Some(FormattingOption::DoubleUnderline),
+            "curly-underline" => Some(FormattingOption::CurlyUnderline),
+            "dotted-underline" => Some(FormattingOption::DottedUnderline),
+            "dashed-underline" => Some(FormattingOption::DashedUnderline),
+            _ => None,
+        };
+
+        if let Some(option) = option {
+            formatting.text_formatting.push(option);
+        } else if word.eq_ignore_ascii_case("on") {
+            let color = words.next().ok_or_else(|| unknown(word))?;
+            formatting.background_color = Some(parse_color(color).ok_or_else(|| unknown(color))?);
+        } else {
+            formatting.foreground_color = Some(parse_color(word).ok_or_else(|| unknown(word))?);
+        }
+    }
+
+    Ok(formatting)
+}
+
+/// ANSI color numbers are not accepted, so that text like `[1]` is not mistaken for a tag
+fn parse_color(word: &str) -> Option<Color> {
+    if word.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    word.parse().ok()
+}
+
+/// Applies the inner formatting on top of the outer one
+pub(crate) fn merge(outer: &Formatting, inner: &Formatting) -> Formatting {
+    Formatting {
+        foreground_color: inner.foreground_color.or(outer.foreground_color),
+        background_color: inner.background_color.or(outer.background_color),
+        underline_color: inner.underline_color.or(outer.underline_color),
+        text_formatting: outer
+            .text_formatting
+            .iter()
+            .chain(inner.text_formatting.iter())
+            .copied()
+            .collect(),
+    }
+}
+
+impl Display for MarkupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarkupError::UnknownStyle(style) => write!(f, "unknown style: {}", style),
+            MarkupError::UnterminatedTag => write!(f, "tag is not terminated with ']'"),
+            MarkupError::UnmatchedClosingTag => write!(f, "closing tag without opening one"),
+            MarkupError::UnclosedTag(tag) => write!(f, "tag [{}] is not closed", tag),
+        }
+    }
+}
+
+impl std::error::Error for MarkupError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(spans: &[Span]) -> Vec<&str> {
+        spans.iter().map(|s| s.text.as_str()).collect()
+    }
+
+    #[test]
+    fn parses_nested_tags() {
+        let spans = parse("Deploy to [bold red]prod [on white]now[/][/]?").unwrap();
+        assert_eq!(texts(&spans), ["Deploy to ", "prod ", "now", "?"]);
+
+        assert!(spans[0].formatting.foreground_color.is_none());
+        assert_eq!(spans[1].formatting.foreground_color, Some(Color::Red));
+        assert_eq!(
+            spans[1].formatting.text_formatting,
+            [FormattingOption::Bold]
+        );
+        assert_eq!(spans[2].formatting.foreground_color, Some(Color::Red));
+        assert_eq!(spans[2].formatting.background_color, Some(Color::White));
+        assert_eq!(
+            spans[2].formatting.text_formatting,
+            [FormattingOption::Bold]
+        );
+    }
+
+    #[test]
+    fn escaped_text_is_displayed_as_is() {
+        let text = format!("[bold]{}[/]", escape("[red]x[/]"));
+        let spans = parse(&text).unwrap();
+        assert_eq!(texts(&spans), ["[red]x[/]"]);
+        assert_eq!(spans[0].formatting.foreground_color, None);
+    }
+
+    #[test]
+    fn reports_invalid_tags_in_strict_mode() {
+        assert_eq!(
+            parse("[x] done").unwrap_err(),
+            MarkupError::UnknownStyle("x".into())
+        );
+        assert_eq!(parse("[bold").unwrap_err(), MarkupError::UnterminatedTag);
+        assert_eq!(
+            parse("done[/]").unwrap_err(),
+            MarkupError::UnmatchedClosingTag
+        );
+        assert_eq!(
+            parse("[bold]done").unwrap_err(),
+            MarkupError::UnclosedTag("bold".into())
+        );
+        assert_eq!(
+            texts(&parse_with_mode("[bold]a [x] b[/]", MarkupMode::Strict)),
+            ["[bold]a [x] b[/]"]
+        );
+    }
+
+    #[test]
+    fn keeps_invalid_tags_in_lenient_mode() {
+        let spans = parse_lenient("[x] [bold]done[/] [/]");
+        assert_eq!(texts(&spans), ["[x] ", "done", " [/]"]);
+        assert_eq!(
+            plain_text("[y/n] [1] [dim]maybe", MarkupMode::Lenient),
+            "[y/n] [1] maybe"
+        );
+        assert_eq!(
+            plain_text("[dim]maybe[/]", MarkupMode::Plain),
+            "[dim]maybe[/]"
+        );
+    }
+
+    #[test]
+    fn paths_are_not_closing_tags() {
+        let spans = parse_lenient("[bold]path [/usr/local]");
+        assert_eq!(texts(&spans), ["path [/usr/local]"]);
+        assert_eq!(
+            spans[0].formatting.text_formatting,
+            [FormattingOption::Bold]
+        );
+        assert_eq!(
+            parse("[bold]path [/usr/local]").unwrap_err(),
+            MarkupError::UnknownStyle("/usr/local".into())
+        );
+        assert_eq!(texts(&parse("[bold]path[/bold] /").unwrap()), ["path", " /"]);
+    }
+}
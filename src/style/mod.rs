@@ -1,18 +1,37 @@
 //! Module for changing the appearence of the prompts.
 //! Each prompt has its own set of styling attributes, which include text color, different
-//! formatting as well as some prompt-specific styling
+//! formatting as well as some prompt-specific styling.
+//!
+//! The default values of all styles come from the current `Theme`, so the appearance of all
+//! prompts can be changed at once
 
 mod color;
 mod formatting;
 mod label_style;
+pub mod markup;
 mod prompts;
+mod theme;
+#[cfg(feature = "serde")]
+mod theme_file;
 
 pub use color::Color;
 pub use formatting::{Formatting, FormattingOption};
-pub use label_style::LabelStyle;
+pub use label_style::{LabelStyle, PromptState};
+pub use markup::MarkupMode;
 pub use prompts::{
     confirmation::ConfirmationStyle,
+    expand::ExpandStyle,
+    form::FormStyle,
     input::InputStyle,
+    list_builder::ListBuilderStyle,
     multiselection::MultiselectionStyle,
+    progress_bar::ProgressBarStyle,
+    reorder::ReorderStyle,
     selection::{self, SelectionStyle},
+    spinner::SpinnerStyle,
+    table::TableStyle,
+    tree::TreeStyle,
 };
+pub use theme::Theme;
+#[cfg(feature = "serde")]
+pub use theme_file::{ThemeError, THEME_ENV_VAR};
@@ -1,7 +1,9 @@
 pub mod input {
-    use crate::style::{Color, Formatting, LabelStyle};
+    use crate::style::{Formatting, LabelStyle, Theme};
 
     /// Style for the `Input` prompt
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub struct InputStyle {
         /// Style of the prompt itself
         pub label_style: LabelStyle,
@@ -24,13 +26,20 @@ pub mod input {
 
     impl Default for InputStyle {
         fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl InputStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
             InputStyle {
-                label_style: LabelStyle::default(),
-                default_value_formatting: Formatting::default().foreground_color(Color::Grey),
-                error_formatting: Formatting::default().foreground_color(Color::Red),
-                input_formatting: Formatting::default(),
-                submitted_formatting: Formatting::default().foreground_color(Color::Green),
-                help_message_formatting: Formatting::default().foreground_color(Color::DarkGreen),
+                label_style: LabelStyle::from_theme(theme),
+                default_value_formatting: theme.placeholder.clone(),
+                error_formatting: theme.error.clone(),
+                input_formatting: theme.input.clone(),
+                submitted_formatting: theme.success.clone(),
+                help_message_formatting: theme.help.clone(),
             }
         }
     }
@@ -69,9 +78,11 @@ pub mod input {
 }
 
 pub mod confirmation {
-    use crate::style::{Color, Formatting, LabelStyle};
+    use crate::style::{Formatting, LabelStyle, Theme};
 
     /// Style for the `Confirmation` prompt
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub struct ConfirmationStyle {
         /// Style for the prompt itself
         pub label_style: LabelStyle,
@@ -81,14 +92,25 @@ pub mod confirmation {
 
         /// Formatting for the user's input when the prompt is completed
         pub submitted_formatting: Formatting,
+
+        /// Formatting for the help message
+        pub help_message_formatting: Formatting,
     }
 
     impl Default for ConfirmationStyle {
         fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl ConfirmationStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
             ConfirmationStyle {
-                label_style: LabelStyle::default(),
-                input_formatting: Formatting::default(),
-                submitted_formatting: Formatting::default().foreground_color(Color::Green),
+                label_style: LabelStyle::from_theme(theme),
+                input_formatting: theme.input.clone(),
+                submitted_formatting: theme.success.clone(),
+                help_message_formatting: theme.help.clone(),
             }
         }
     }
@@ -108,16 +130,22 @@ pub mod confirmation {
             self.submitted_formatting = f;
             self
         }
+
+        pub fn help_message_formatting(mut self, f: Formatting) -> Self {
+            self.help_message_formatting = f;
+            self
+        }
     }
 }
 
 pub mod selection {
     use crate::{
         engine::CommandBuffer,
-        style::{Color, Formatting, LabelStyle},
+        style::{Formatting, LabelStyle, Theme},
     };
 
     /// Marker that is displayed before the option that is currently highlighted
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Marker {
         /// Marker string
         pub marker: String,
@@ -127,6 +155,8 @@ pub mod selection {
     }
 
     /// Style for the `Selection` prompt
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub struct SelectionStyle {
         /// Style for the prompt itself
         pub label_style: LabelStyle,
@@ -143,29 +173,52 @@ pub mod selection {
         /// Formatting for the filter string
         pub filter_formatting: Formatting,
 
+        /// Formatting for the error message
+        pub error_formatting: Formatting,
+
+        /// Formatting for the help message
+        pub help_message_formatting: Formatting,
+
         /// Marker for the option which is not highlighted
         pub not_selected_marker: Marker,
 
         /// Marker for the option which is currently highlighted
         pub selected_marker: Marker,
+
+        /// Formatting for the scroll indicators and the position counter
+        pub scroll_indicator_formatting: Formatting,
+
+        /// Formatting for the number or letter prefixes of the options when shortcuts are enabled
+        pub shortcut_formatting: Formatting,
     }
 
     impl Default for SelectionStyle {
         fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl SelectionStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
             SelectionStyle {
-                label_style: LabelStyle::default(),
-                submitted_formatting: Formatting::default().foreground_color(Color::Green),
+                label_style: LabelStyle::from_theme(theme),
+                submitted_formatting: theme.success.clone(),
                 option_formatting: Formatting::default(),
-                selected_option_formatting: Formatting::default().bold(),
-                filter_formatting: Formatting::default(),
+                selected_option_formatting: theme.highlight.clone(),
+                filter_formatting: theme.input.clone(),
+                error_formatting: theme.error.clone(),
+                help_message_formatting: theme.help.clone(),
                 not_selected_marker: Marker {
-                    marker: "  ".into(),
+                    marker: theme.not_highlighted_marker.clone(),
                     formatting: Formatting::default(),
                 },
                 selected_marker: Marker {
-                    marker: "> ".into(),
-                    formatting: Formatting::default().bold(),
+                    marker: theme.highlighted_marker.clone(),
+                    formatting: theme.highlight.clone(),
                 },
+                scroll_indicator_formatting: theme.muted.clone(),
+                shortcut_formatting: theme.accent.clone(),
             }
         }
     }
@@ -186,6 +239,16 @@ pub mod selection {
             self
         }
 
+        pub fn error_formatting(mut self, f: Formatting) -> Self {
+            self.error_formatting = f;
+            self
+        }
+
+        pub fn help_message_formatting(mut self, f: Formatting) -> Self {
+            self.help_message_formatting = f;
+            self
+        }
+
         pub fn not_selected_marker(mut self, m: Marker) -> Self {
             self.not_selected_marker = m;
             self
@@ -195,6 +258,16 @@ pub mod selection {
             self.selected_marker = m;
             self
         }
+
+        pub fn scroll_indicator_formatting(mut self, f: Formatting) -> Self {
+            self.scroll_indicator_formatting = f;
+            self
+        }
+
+        pub fn shortcut_formatting(mut self, f: Formatting) -> Self {
+            self.shortcut_formatting = f;
+            self
+        }
     }
 
     impl Marker {
@@ -209,10 +282,12 @@ pub mod selection {
 pub mod multiselection {
     use crate::{
         engine::CommandBuffer,
-        style::{Color, Formatting, LabelStyle},
+        style::{Formatting, LabelStyle, Theme},
     };
 
     /// Style for the `Multiselection` prompt
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub struct MultiselectionStyle {
         /// Style for the prompt itself
         pub label_style: LabelStyle,
@@ -227,6 +302,9 @@ pub mod multiselection {
         /// Formatting for the help message
         pub help_message_formatting: Formatting,
 
+        /// Formatting for the error message
+        pub error_formatting: Formatting,
+
         /// Marker to use
         pub marker: Marker,
 
@@ -235,12 +313,16 @@ pub mod multiselection {
 
         /// Formatting for the option which is not currently highlighted
         pub normal_option_formatting: Formatting,
+
+        /// Formatting for the scroll indicators and the position counter
+        pub scroll_indicator_formatting: Formatting,
     }
 
     /// Marker for the options. It consists of the opening and closing symbols and the symbol that
     /// is put in the middle when the option is selected. Example: 
     /// Not selected: [ ]
     /// Selected:     [X]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Marker {
         /// Opening symbol
         pub opening_sign: String,
@@ -254,19 +336,27 @@ pub mod multiselection {
 
     impl Default for MultiselectionStyle {
         fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl MultiselectionStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
             MultiselectionStyle {
-                label_style: LabelStyle::default(),
-                submitted_formatting: Formatting::default().foreground_color(Color::Green),
-                filter_formatting: Formatting::default(),
-                help_message_formatting: Formatting::default().foreground_color(Color::DarkGreen),
+                label_style: LabelStyle::from_theme(theme),
+                submitted_formatting: theme.success.clone(),
+                filter_formatting: theme.input.clone(),
+                help_message_formatting: theme.help.clone(),
+                error_formatting: theme.error.clone(),
                 marker: Marker {
-                    opening_sign: "[".into(),
-                    selection_sign: "x".into(),
-                    closing_sign: "]".into(),
+                    opening_sign: theme.checkbox_opening.clone(),
+                    selection_sign: theme.checkbox_selection.clone(),
+                    closing_sign: theme.checkbox_closing.clone(),
                 },
-                highlighted_option_formatting: Formatting::default()
-                    .foreground_color(Color::DarkGreen),
+                highlighted_option_formatting: theme.checkbox_highlight.clone(),
                 normal_option_formatting: Formatting::default(),
+                scroll_indicator_formatting: theme.muted.clone(),
             }
         }
     }
@@ -306,9 +396,740 @@ pub mod multiselection {
             commands.set_formatting(formatting);
             self.marker.print(is_selected, commands);
             commands.print(" ");
-
-            commands.print(option_text);
             commands.reset_formatting();
+
+            self.label_style
+                .print_data_text(option_text, formatting, commands);
+        }
+    }
+}
+
+pub mod expand {
+    use crate::style::{Formatting, LabelStyle, Theme};
+
+    /// Style for the `Expand` prompt
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct ExpandStyle {
+        /// Style for the prompt itself
+        pub label_style: LabelStyle,
+
+        /// Formatting for the keys assigned to the choices
+        pub key_formatting: Formatting,
+
+        /// Formatting for the choices in the expanded list
+        pub choice_formatting: Formatting,
+
+        /// Formatting for the choice that is currently picked
+        pub highlighted_choice_formatting: Formatting,
+
+        /// Formatting for the picked choice when the prompt is completed
+        pub submitted_formatting: Formatting,
+    }
+
+    impl Default for ExpandStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl ExpandStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            ExpandStyle {
+                label_style: LabelStyle::from_theme(theme),
+                key_formatting: theme.accent.clone(),
+                choice_formatting: Formatting::default(),
+                highlighted_choice_formatting: theme.highlight.clone(),
+                submitted_formatting: theme.success.clone(),
+            }
+        }
+    }
+
+    impl ExpandStyle {
+        pub fn label_style(mut self, l: LabelStyle) -> Self {
+            self.label_style = l;
+            self
+        }
+
+        pub fn key_formatting(mut self, f: Formatting) -> Self {
+            self.key_formatting = f;
+            self
+        }
+
+        pub fn choice_formatting(mut self, f: Formatting) -> Self {
+            self.choice_formatting = f;
+            self
+        }
+
+        pub fn highlighted_choice_formatting(mut self, f: Formatting) -> Self {
+            self.highlighted_choice_formatting = f;
+            self
+        }
+
+        pub fn submitted_formatting(mut self, f: Formatting) -> Self {
+            self.submitted_formatting = f;
+            self
+        }
+    }
+}
+
+pub mod tree {
+    use crate::{
+        engine::CommandBuffer,
+        style::{Formatting, Theme},
+    };
+
+    /// Tree-specific part of the style of the `TreeSelect` and `TreeMultiselect` prompts.
+    /// The rest is defined by `SelectionStyle` and `MultiselectionStyle` respectively
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct TreeStyle {
+        /// String that is repeated once per nesting level before the node
+        pub indent: String,
+
+        /// Icon of the node whose children are shown
+        pub expanded_icon: String,
+
+        /// Icon of the node whose children are hidden
+        pub collapsed_icon: String,
+
+        /// Icon of the node without children
+        pub leaf_icon: String,
+
+        /// Formatting of the icons
+        pub icon_formatting: Formatting,
+
+        /// The symbol that is put in the marker of a node, some of whose descendants are
+        /// selected
+        pub partial_selection_sign: String,
+
+        /// Separator between the node labels when the path to the node is displayed
+        pub path_separator: String,
+    }
+
+    impl Default for TreeStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl TreeStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            TreeStyle {
+                indent: "  ".into(),
+                expanded_icon: theme.expanded_icon.clone(),
+                collapsed_icon: theme.collapsed_icon.clone(),
+                leaf_icon: " ".repeat(theme.expanded_icon.chars().count()),
+                icon_formatting: theme.muted.clone(),
+                partial_selection_sign: theme.checkbox_partial_selection.clone(),
+                path_separator: " / ".into(),
+            }
+        }
+    }
+
+    impl TreeStyle {
+        pub fn indent<S: Into<String>>(mut self, indent: S) -> Self {
+            self.indent = indent.into();
+            self
+        }
+
+        pub fn expanded_icon<S: Into<String>>(mut self, icon: S) -> Self {
+            self.expanded_icon = icon.into();
+            self
+        }
+
+        pub fn collapsed_icon<S: Into<String>>(mut self, icon: S) -> Self {
+            self.collapsed_icon = icon.into();
+            self
+        }
+
+        pub fn leaf_icon<S: Into<String>>(mut self, icon: S) -> Self {
+            self.leaf_icon = icon.into();
+            self
+        }
+
+        pub fn icon_formatting(mut self, f: Formatting) -> Self {
+            self.icon_formatting = f;
+            self
+        }
+
+        pub fn partial_selection_sign<S: Into<String>>(mut self, sign: S) -> Self {
+            self.partial_selection_sign = sign.into();
+            self
+        }
+
+        pub fn path_separator<S: Into<String>>(mut self, separator: S) -> Self {
+            self.path_separator = separator.into();
+            self
+        }
+
+        /// Prints the indentation and the icon of the node to the provided command buffer
+        pub fn print_node_prefix(
+            &self,
+            depth: usize,
+            has_children: bool,
+            is_expanded: bool,
+            commands: &mut impl CommandBuffer,
+        ) {
+            commands.print(&self.indent.repeat(depth));
+            let icon = match (has_children, is_expanded) {
+                (false, _) => &self.leaf_icon,
+                (true, true) => &self.expanded_icon,
+                (true, false) => &self.collapsed_icon,
+            };
+            self.icon_formatting.print(icon, commands);
+        }
+    }
+}
+
+pub mod table {
+    use crate::style::{Formatting, Theme};
+
+    /// Table-specific part of the style of the `TableSelect` and `TableMultiselect` prompts.
+    /// The rest is defined by `SelectionStyle` and `MultiselectionStyle` respectively
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct TableStyle {
+        /// Formatting of the column titles
+        pub header_formatting: Formatting,
+
+        /// Formatting of the title of the column that is currently focused
+        pub focused_header_formatting: Formatting,
+
+        /// String that is put between the columns
+        pub column_separator: String,
+
+        /// String that is put after the title of the column the rows are sorted by in the
+        /// ascending order
+        pub ascending_indicator: String,
+
+        /// String that is put after the title of the column the rows are sorted by in the
+        /// descending order
+        pub descending_indicator: String,
+    }
+
+    impl Default for TableStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl TableStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            TableStyle {
+                header_formatting: theme.label_formatting.clone(),
+                focused_header_formatting: theme.accent.clone().bold().underline(),
+                column_separator: "  ".into(),
+                ascending_indicator: theme.ascending_indicator.clone(),
+                descending_indicator: theme.descending_indicator.clone(),
+            }
+        }
+    }
+
+    impl TableStyle {
+        pub fn header_formatting(mut self, f: Formatting) -> Self {
+            self.header_formatting = f;
+            self
+        }
+
+        pub fn focused_header_formatting(mut self, f: Formatting) -> Self {
+            self.focused_header_formatting = f;
+            self
+        }
+
+        pub fn column_separator<S: Into<String>>(mut self, separator: S) -> Self {
+            self.column_separator = separator.into();
+            self
+        }
+
+        pub fn ascending_indicator<S: Into<String>>(mut self, indicator: S) -> Self {
+            self.ascending_indicator = indicator.into();
+            self
+        }
+
+        pub fn descending_indicator<S: Into<String>>(mut self, indicator: S) -> Self {
+            self.descending_indicator = indicator.into();
+            self
+        }
+
+        /// Number of columns that is reserved after the column titles for the sort indicators
+        pub fn sort_indicator_width(&self) -> usize {
+            self.ascending_indicator
+                .chars()
+                .count()
+                .max(self.descending_indicator.chars().count())
+        }
+    }
+}
+
+pub mod reorder {
+    use crate::style::{selection::Marker, Formatting, LabelStyle, Theme};
+
+    /// Style for the `Reorder` prompt
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct ReorderStyle {
+        /// Style for the prompt itself
+        pub label_style: LabelStyle,
+
+        /// Formatting for the options when the prompt is completed
+        pub submitted_formatting: Formatting,
+
+        /// Formatting for the help message
+        pub help_message_formatting: Formatting,
+
+        /// Formatting for the options
+        pub option_formatting: Formatting,
+
+        /// Formatting for the option that is currently highlighted
+        pub highlighted_option_formatting: Formatting,
+
+        /// Formatting for the option that is currently grabbed
+        pub grabbed_option_formatting: Formatting,
+
+        /// Formatting for the options that are not returned when only the top ones are ranked
+        pub unranked_option_formatting: Formatting,
+
+        /// Formatting for the scroll indicators and the position counter
+        pub scroll_indicator_formatting: Formatting,
+
+        /// Marker for the option which is not highlighted
+        pub not_highlighted_marker: Marker,
+
+        /// Marker for the option which is currently highlighted
+        pub highlighted_marker: Marker,
+
+        /// Marker for the option which is currently grabbed
+        pub grabbed_marker: Marker,
+    }
+
+    impl Default for ReorderStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl ReorderStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            ReorderStyle {
+                label_style: LabelStyle::from_theme(theme),
+                submitted_formatting: theme.success.clone(),
+                help_message_formatting: theme.help.clone(),
+                option_formatting: Formatting::default(),
+                highlighted_option_formatting: theme.highlight.clone(),
+                grabbed_option_formatting: theme.accent.clone().bold(),
+                unranked_option_formatting: theme.muted.clone(),
+                scroll_indicator_formatting: theme.muted.clone(),
+                not_highlighted_marker: Marker {
+                    marker: theme.not_highlighted_marker.clone(),
+                    formatting: Formatting::default(),
+                },
+                highlighted_marker: Marker {
+                    marker: theme.highlighted_marker.clone(),
+                    formatting: theme.highlight.clone(),
+                },
+                grabbed_marker: Marker {
+                    marker: theme.grabbed_marker.clone(),
+                    formatting: theme.accent.clone().bold(),
+                },
+            }
+        }
+    }
+
+    impl ReorderStyle {
+        pub fn label_style(mut self, l: LabelStyle) -> Self {
+            self.label_style = l;
+            self
+        }
+
+        pub fn submitted_formatting(mut self, f: Formatting) -> Self {
+            self.submitted_formatting = f;
+            self
+        }
+
+        pub fn help_message_formatting(mut self, f: Formatting) -> Self {
+            self.help_message_formatting = f;
+            self
+        }
+
+        pub fn option_formatting(mut self, f: Formatting) -> Self {
+            self.option_formatting = f;
+            self
+        }
+
+        pub fn highlighted_option_formatting(mut self, f: Formatting) -> Self {
+            self.highlighted_option_formatting = f;
+            self
+        }
+
+        pub fn grabbed_option_formatting(mut self, f: Formatting) -> Self {
+            self.grabbed_option_formatting = f;
+            self
+        }
+
+        pub fn unranked_option_formatting(mut self, f: Formatting) -> Self {
+            self.unranked_option_formatting = f;
+            self
+        }
+
+        pub fn not_highlighted_marker(mut self, m: Marker) -> Self {
+            self.not_highlighted_marker = m;
+            self
+        }
+
+        pub fn highlighted_marker(mut self, m: Marker) -> Self {
+            self.highlighted_marker = m;
+            self
+        }
+
+        pub fn grabbed_marker(mut self, m: Marker) -> Self {
+            self.grabbed_marker = m;
+            self
+        }
+    }
+}
+
+pub mod form {
+    use crate::style::{selection::Marker, Formatting, LabelStyle, Theme};
+
+    /// Style for the `Form` review screen
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct FormStyle {
+        /// Style for the review screen label
+        pub label_style: LabelStyle,
+
+        /// Formatting for the help message
+        pub help_message_formatting: Formatting,
+
+        /// Formatting for the submit entry
+        pub submit_formatting: Formatting,
+
+        /// Formatting for the submit entry when it is highlighted
+        pub highlighted_submit_formatting: Formatting,
+
+        /// Marker for the answer which is not highlighted
+        pub not_highlighted_marker: Marker,
+
+        /// Marker for the answer which is currently highlighted
+        pub highlighted_marker: Marker,
+    }
+
+    impl Default for FormStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl FormStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            FormStyle {
+                label_style: LabelStyle::from_theme(theme),
+                help_message_formatting: theme.help.clone(),
+                submit_formatting: Formatting::default(),
+                highlighted_submit_formatting: theme.success.clone().bold(),
+                not_highlighted_marker: Marker {
+                    marker: theme.not_highlighted_marker.clone(),
+                    formatting: Formatting::default(),
+                },
+                highlighted_marker: Marker {
+                    marker: theme.highlighted_marker.clone(),
+                    formatting: theme.highlight.clone(),
+                },
+            }
+        }
+    }
+
+    impl FormStyle {
+        pub fn label_style(mut self, l: LabelStyle) -> Self {
+            self.label_style = l;
+            self
+        }
+
+        pub fn help_message_formatting(mut self, f: Formatting) -> Self {
+            self.help_message_formatting = f;
+            self
+        }
+
+        pub fn submit_formatting(mut self, f: Formatting) -> Self {
+            self.submit_formatting = f;
+            self
+        }
+
+        pub fn highlighted_submit_formatting(mut self, f: Formatting) -> Self {
+            self.highlighted_submit_formatting = f;
+            self
+        }
+
+        pub fn not_highlighted_marker(mut self, m: Marker) -> Self {
+            self.not_highlighted_marker = m;
+            self
+        }
+
+        pub fn highlighted_marker(mut self, m: Marker) -> Self {
+            self.highlighted_marker = m;
+            self
+        }
+    }
+}
+
+pub mod list_builder {
+    use crate::style::{selection::Marker, Formatting, LabelStyle, Theme};
+
+    /// Style for the `ListBuilder` prompt
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct ListBuilderStyle {
+        /// Style for the prompt itself
+        pub label_style: LabelStyle,
+
+        /// Formatting for the number of items when the prompt is completed
+        pub submitted_formatting: Formatting,
+
+        /// Formatting for the help message
+        pub help_message_formatting: Formatting,
+
+        /// Formatting for the error message
+        pub error_formatting: Formatting,
+
+        /// Marker for the item which is not highlighted
+        pub not_highlighted_marker: Marker,
+
+        /// Marker for the item which is currently highlighted
+        pub highlighted_marker: Marker,
+
+        /// Marker for the prompt that adds a new item
+        pub new_item_marker: Marker,
+    }
+
+    impl Default for ListBuilderStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl ListBuilderStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            ListBuilderStyle {
+                label_style: LabelStyle::from_theme(theme),
+                submitted_formatting: theme.success.clone(),
+                help_message_formatting: theme.help.clone(),
+                error_formatting: theme.error.clone(),
+                not_highlighted_marker: Marker {
+                    marker: theme.not_highlighted_marker.clone(),
+                    formatting: Formatting::default(),
+                },
+                highlighted_marker: Marker {
+                    marker: theme.highlighted_marker.clone(),
+                    formatting: theme.highlight.clone(),
+                },
+                new_item_marker: Marker {
+                    marker: theme.new_item_marker.clone(),
+                    formatting: theme.muted.clone(),
+                },
+            }
+        }
+    }
+
+    impl ListBuilderStyle {
+        pub fn label_style(mut self, l: LabelStyle) -> Self {
+            self.label_style = l;
+            self
+        }
+
+        pub fn submitted_formatting(mut self, f: Formatting) -> Self {
+            self.submitted_formatting = f;
+            self
+        }
+
+        pub fn help_message_formatting(mut self, f: Formatting) -> Self {
+            self.help_message_formatting = f;
+            self
+        }
+
+        pub fn error_formatting(mut self, f: Formatting) -> Self {
+            self.error_formatting = f;
+            self
+        }
+
+        pub fn not_highlighted_marker(mut self, m: Marker) -> Self {
+            self.not_highlighted_marker = m;
+            self
+        }
+
+        pub fn highlighted_marker(mut self, m: Marker) -> Self {
+            self.highlighted_marker = m;
+            self
+        }
+
+        pub fn new_item_marker(mut self, m: Marker) -> Self {
+            self.new_item_marker = m;
+            self
+        }
+    }
+}
+
+pub mod spinner {
+    use crate::style::{Formatting, LabelStyle, Theme};
+
+    /// Style for the `Spinner` and the common parts of the `ProgressBar`
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct SpinnerStyle {
+        /// Style for the label. The frames of the spinner replace its prefix while the task is
+        /// running
+        pub label_style: LabelStyle,
+
+        /// Frames of the animation
+        pub frames: Vec<String>,
+
+        /// Formatting for the frames of the animation
+        pub frame_formatting: Formatting,
+
+        /// Formatting for the message that describes the current step of the task
+        pub message_formatting: Formatting,
+
+        /// Formatting for the message of the task that has succeeded
+        pub success_formatting: Formatting,
+
+        /// Formatting for the message of the task that has failed
+        pub failure_formatting: Formatting,
+    }
+
+    impl Default for SpinnerStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl SpinnerStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            SpinnerStyle {
+                label_style: LabelStyle::from_theme(theme),
+                frames: theme.spinner_frames.clone(),
+                frame_formatting: theme.accent.clone(),
+                message_formatting: theme.muted.clone(),
+                success_formatting: theme.success.clone(),
+                failure_formatting: theme.error.clone(),
+            }
+        }
+    }
+
+    impl SpinnerStyle {
+        pub fn label_style(mut self, l: LabelStyle) -> Self {
+            self.label_style = l;
+            self
+        }
+
+        pub fn frames<S: Into<String>>(mut self, frames: impl IntoIterator<Item = S>) -> Self {
+            self.frames = frames.into_iter().map(Into::into).collect();
+            self
+        }
+
+        pub fn frame_formatting(mut self, f: Formatting) -> Self {
+            self.frame_formatting = f;
+            self
+        }
+
+        pub fn message_formatting(mut self, f: Formatting) -> Self {
+            self.message_formatting = f;
+            self
+        }
+
+        pub fn success_formatting(mut self, f: Formatting) -> Self {
+            self.success_formatting = f;
+            self
+        }
+
+        pub fn failure_formatting(mut self, f: Formatting) -> Self {
+            self.failure_formatting = f;
+            self
+        }
+    }
+}
+
+pub mod progress_bar {
+    use crate::style::{Formatting, Theme};
+
+    /// Style for the bar of the `ProgressBar`
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub struct ProgressBarStyle {
+        /// Symbol of the completed part of the bar
+        pub filled: String,
+
+        /// Symbol of the remaining part of the bar
+        pub empty: String,
+
+        /// Width of the bar in columns. The bar takes at most half of the terminal
+        pub width: u16,
+
+        /// Formatting for the completed part of the bar
+        pub filled_formatting: Formatting,
+
+        /// Formatting for the remaining part of the bar
+        pub empty_formatting: Formatting,
+
+        /// Formatting for the percentage after the bar
+        pub percentage_formatting: Formatting,
+    }
+
+    impl Default for ProgressBarStyle {
+        fn default() -> Self {
+            Self::from_theme(&Theme::current())
+        }
+    }
+
+    impl ProgressBarStyle {
+        /// Creates the style from the given theme
+        pub fn from_theme(theme: &Theme) -> Self {
+            ProgressBarStyle {
+                filled: theme.progress_filled.clone(),
+                empty: theme.progress_empty.clone(),
+                width: 30,
+                filled_formatting: theme.accent.clone(),
+                empty_formatting: theme.muted.clone(),
+                percentage_formatting: Formatting::default(),
+            }
+        }
+    }
+
+    impl ProgressBarStyle {
+        pub fn filled<S: Into<String>>(mut self, s: S) -> Self {
+            self.filled = s.into();
+            self
+        }
+
+        pub fn empty<S: Into<String>>(mut self, s: S) -> Self {
+            self.empty = s.into();
+            self
+        }
+
+        pub fn width(mut self, w: u16) -> Self {
+            self.width = w;
+            self
+        }
+
+        pub fn filled_formatting(mut self, f: Formatting) -> Self {
+            self.filled_formatting = f;
+            self
+        }
+
+        pub fn empty_formatting(mut self, f: Formatting) -> Self {
+            self.empty_formatting = f;
+            self
+        }
+
+        pub fn percentage_formatting(mut self, f: Formatting) -> Self {
+            self.percentage_formatting = f;
+            self
         }
     }
 }
@@ -0,0 +1,607 @@
+use std::{cell::RefCell, sync::RwLock};
+
+use super::{markup::MarkupMode, Color, Formatting};
+
+static GLOBAL_THEME: RwLock<Option<Theme>> = RwLock::new(None);
+
+thread_local! {
+    static FLOW_THEME: RefCell<Option<Theme>> = const { RefCell::new(None) };
+}
+
+/// Set of formattings and symbols that all built-in styles take their defaults from.
+///
+/// The formattings describe the semantic roles of the text, e.g. `error` is used for every
+/// error message and `accent` for the hotkeys and the focused elements. This way one theme
+/// changes the appearance of all prompts at once instead of setting the style of every prompt.
+///
+/// The theme is picked up when a prompt is created, so it can be installed either globally with
+/// `install` or only for the prompts created in a closure with `scope`:
+///
+/// ```rust
+/// use cli_prompts::{prompts::Input, style::Theme};
+///
+/// Theme::colorblind_safe().install();
+///
+/// let plain_input = Theme::minimal().scope(|| {
+///     Input::new("Name", |s| Ok(s.to_string())).help_message("No colors here")
+/// });
+/// ```
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Theme {
+    /// String that is displayed before the label of the prompt
+    pub prefix: String,
+
+    /// Formatting for the prefix
+    pub prefix_formatting: Formatting,
+
+    /// Prefix of the prompt that has been submitted
+    pub submitted_prefix: String,
+
+    /// Formatting for the prefix of the submitted prompt
+    pub submitted_prefix_formatting: Formatting,
+
+    /// Prefix of the prompt that has been aborted
+    pub aborted_prefix: String,
+
+    /// Formatting for the prefix of the aborted prompt
+    pub aborted_prefix_formatting: Formatting,
+
+    /// Formatting for the label of the aborted prompt
+    pub aborted_label_formatting: Formatting,
+
+    /// Message that is displayed after the label of the aborted prompt
+    pub aborted_message: String,
+
+    /// Formatting for the message of the aborted prompt
+    pub aborted_message_formatting: Formatting,
+
+    /// Prefix of the prompt with an invalid input
+    pub error_prefix: String,
+
+    /// Formatting for the prefix of the prompt with an invalid input
+    pub error_prefix_formatting: Formatting,
+
+    /// String that is displayed right after the label
+    pub label_suffix: String,
+
+    /// Whether the input is displayed on the line below the label
+    pub input_on_new_line: bool,
+
+    /// Formatting for the label of the prompt
+    pub label_formatting: Formatting,
+
+    /// Formatting for the text that is typed by the user
+    pub input: Formatting,
+
+    /// Formatting for the default values
+    pub placeholder: Formatting,
+
+    /// Formatting for the answer when the prompt is completed
+    pub success: Formatting,
+
+    /// Formatting for the error messages
+    pub error: Formatting,
+
+    /// Formatting for the help messages
+    pub help: Formatting,
+
+    /// Formatting for the hotkeys, shortcuts and focused elements
+    pub accent: Formatting,
+
+    /// Formatting for the secondary elements, like scroll indicators and icons
+    pub muted: Formatting,
+
+    /// Formatting for the option that is currently highlighted
+    pub highlight: Formatting,
+
+    /// Formatting for the option that is currently highlighted in the multiselection prompts
+    pub checkbox_highlight: Formatting,
+
+    /// Marker before the option that is currently highlighted
+    pub highlighted_marker: String,
+
+    /// Marker before the options that are not highlighted. Should have the same width as the
+    /// highlighted one
+    pub not_highlighted_marker: String,
+
+    /// Marker before the option that is moved in the `Reorder` prompt
+    pub grabbed_marker: String,
+
+    /// Marker before the prompt that adds a new item in the `ListBuilder` prompt
+    pub new_item_marker: String,
+
+    /// Opening symbol of the checkbox in the multiselection prompts
+    pub checkbox_opening: String,
+
+    /// Closing symbol of the checkbox in the multiselection prompts
+    pub checkbox_closing: String,
+
+    /// Symbol inside the checkbox of the selected option
+    pub checkbox_selection: String,
+
+    /// Symbol inside the checkbox of the tree node, some of whose descendants are selected
+    pub checkbox_partial_selection: String,
+
+    /// Icon of the tree node whose children are shown
+    pub expanded_icon: String,
+
+    /// Icon of the tree node whose children are hidden
+    pub collapsed_icon: String,
+
+    /// Indicator of the table column the rows are sorted by in the ascending order
+    pub ascending_indicator: String,
+
+    /// Indicator of the table column the rows are sorted by in the descending order
+    pub descending_indicator: String,
+
+    /// Frames of the `Spinner` animation
+    pub spinner_frames: Vec<String>,
+
+    /// Symbol of the completed part of the `ProgressBar`
+    pub progress_filled: String,
+
+    /// Symbol of the remaining part of the `ProgressBar`
+    pub progress_empty: String,
+
+    /// How the markup in the labels and help messages is handled
+    pub markup: MarkupMode,
+
+    /// How the markup in the options, answers and task messages is handled. These come from the
+    /// caller's data, so they are displayed as is by default
+    pub data_markup: MarkupMode,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            prefix: "?".into(),
+            prefix_formatting: Formatting::default().bold().foreground_color(Color::Green),
+            submitted_prefix: "✔".into(),
+            submitted_prefix_formatting: Formatting::default()
+                .bold()
+                .foreground_color(Color::Green),
+            aborted_prefix: "✖".into(),
+            aborted_prefix_formatting: Formatting::default().bold().foreground_color(Color::Red),
+            aborted_label_formatting: Formatting::default()
+                .crossed_out()
+                .foreground_color(Color::DarkGrey),
+            aborted_message: "cancelled".into(),
+            aborted_message_formatting: Formatting::default().foreground_color(Color::Red),
+            error_prefix: "!".into(),
+            error_prefix_formatting: Formatting::default().bold().foreground_color(Color::Red),
+            label_suffix: ":".into(),
+            input_on_new_line: false,
+            label_formatting: Formatting::default().bold(),
+            input: Formatting::default(),
+            placeholder: Formatting::default().foreground_color(Color::Grey),
+            success: Formatting::default().foreground_color(Color::Green),
+            error: Formatting::default().foreground_color(Color::Red),
+            help: Formatting::default().foreground_color(Color::DarkGreen),
+            accent: Formatting::default().foreground_color(Color::Cyan),
+            muted: Formatting::default().foreground_color(Color::DarkGrey),
+            highlight: Formatting::default().bold(),
+            checkbox_highlight: Formatting::default().foreground_color(Color::DarkGreen),
+            highlighted_marker: "> ".into(),
+            not_highlighted_marker: "  ".into(),
+            grabbed_marker: "↕ ".into(),
+            new_item_marker: "+ ".into(),
+            checkbox_opening: "[".into(),
+            checkbox_closing: "]".into(),
+            checkbox_selection: "x".into(),
+            checkbox_partial_selection: "-".into(),
+            expanded_icon: "▾ ".into(),
+            collapsed_icon: "▸ ".into(),
+            ascending_indicator: " ▲".into(),
+            descending_indicator: " ▼".into(),
+            spinner_frames: ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"]
+                .map(String::from)
+                .to_vec(),
+            progress_filled: "█".into(),
+            progress_empty: "░".into(),
+            markup: MarkupMode::default(),
+            data_markup: MarkupMode::Plain,
+        }
+    }
+}
+
+impl Theme {
+    /// Theme without colors that uses only ASCII symbols. Suits the terminals with limited
+    /// capabilities and the logs of the CI runs
+    pub fn minimal() -> Self {
+        Theme {
+            prefix_formatting: Formatting::default(),
+            submitted_prefix: "v".into(),
+            submitted_prefix_formatting: Formatting::default(),
+            aborted_prefix: "x".into(),
+            aborted_prefix_formatting: Formatting::default(),
+            aborted_label_formatting: Formatting::default(),
+            aborted_message_formatting: Formatting::default(),
+            error_prefix_formatting: Formatting::default(),
+            label_formatting: Formatting::default(),
+            input: Formatting::default(),
+            placeholder: Formatting::default(),
+            success: Formatting::default(),
+            error: Formatting::default(),
+            help: Formatting::default(),
+            accent: Formatting::default(),
+            muted: Formatting::default(),
+            highlight: Formatting::default(),
+            checkbox_highlight: Formatting::default(),
+            grabbed_marker: "* ".into(),
+            expanded_icon: "- ".into(),
+            collapsed_icon: "+ ".into(),
+            ascending_indicator: " ^".into(),
+            descending_indicator: " v".into(),
+            spinner_frames: ["-", "\\", "|", "/"].map(String::from).to_vec(),
+            progress_filled: "#".into(),
+            progress_empty: "-".into(),
+            ..Theme::default()
+        }
+    }
+
+    /// Theme with bright colors and bold text that is easier to read on low quality screens and
+    /// for the users with low vision
+    pub fn high_contrast() -> Self {
+        Theme {
+            prefix_formatting: Formatting::default().bold().foreground_color(Color::Yellow),
+            submitted_prefix_formatting: Formatting::default()
+                .bold()
+                .foreground_color(Color::Green),
+            aborted_prefix_formatting: Formatting::default().bold().foreground_color(Color::Red),
+            aborted_label_formatting: Formatting::default()
+                .crossed_out()
+                .foreground_color(Color::White),
+            aborted_message_formatting: Formatting::default().bold().foreground_color(Color::Red),
+            error_prefix_formatting: Formatting::default().bold().foreground_color(Color::Red),
+            label_formatting: Formatting::default().bold().foreground_color(Color::White),
+            input: Formatting::default().bold().foreground_color(Color::White),
+            placeholder: Formatting::default().foreground_color(Color::White),
+            success: Formatting::default().bold().foreground_color(Color::Green),
+            error: Formatting::default().bold().foreground_color(Color::Red),
+            help: Formatting::default().foreground_color(Color::Cyan),
+            accent: Formatting::default().bold().foreground_color(Color::Yellow),
+            muted: Formatting::default().foreground_color(Color::Grey),
+            highlight: Formatting::default()
+                .bold()
+                .underline()
+                .foreground_color(Color::Yellow),
+            checkbox_highlight: Formatting::default()
+                .bold()
+                .underline()
+                .foreground_color(Color::Yellow),
+            highlighted_marker: "▶ ".into(),
+            ..Theme::default()
+        }
+    }
+
+    /// Theme that doesn't rely on telling red and green apart. Uses the colors from the
+    /// Okabe-Ito palette and underlines the errors
+    pub fn colorblind_safe() -> Self {
+        let blue = Color::Rgb { r: 0, g: 114, b: 178 };
+        let sky_blue = Color::Rgb { r: 86, g: 180, b: 233 };
+        let orange = Color::Rgb { r: 230, g: 159, b: 0 };
+        let vermillion = Color::Rgb { r: 213, g: 94, b: 0 };
+
+        Theme {
+            prefix_formatting: Formatting::default().bold().foreground_color(sky_blue),
+            submitted_prefix_formatting: Formatting::default().bold().foreground_color(blue),
+            aborted_prefix_formatting: Formatting::default().bold().foreground_color(vermillion),
+            aborted_message_formatting: Formatting::default().foreground_color(vermillion),
+            error_prefix_formatting: Formatting::default().bold().foreground_color(vermillion),
+            success: Formatting::default().foreground_color(blue),
+            error: Formatting::default().bold().underline().foreground_color(vermillion),
+            help: Formatting::default().foreground_color(sky_blue),
+            accent: Formatting::default().foreground_color(orange),
+            highlight: Formatting::default().bold().foreground_color(orange),
+            checkbox_highlight: Formatting::default().foreground_color(orange),
+            ..Theme::default()
+        }
+    }
+
+    /// Returns the preset with the given name: `default`, `minimal`, `high-contrast` or
+    /// `colorblind-safe`
+    pub fn preset(name: &str) -> Option<Theme> {
+        match name {
+            "default" => Some(Theme::default()),
+            "minimal" => Some(Theme::minimal()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            "colorblind-safe" => Some(Theme::colorblind_safe()),
+            _ => None,
+        }
+    }
+
+    /// Returns the theme the new prompts are created with: the one set by `scope` on the
+    /// current thread, the installed one or the default one
+    pub fn current() -> Theme {
+        FLOW_THEME
+            .with(|theme| theme.borrow().clone())
+            .or_else(|| {
+                GLOBAL_THEME
+                    .read()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .clone()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Makes all prompts that are created after this call use the theme
+    pub fn install(self) {
+        *GLOBAL_THEME.write().unwrap_or_else(|e| e.into_inner()) = Some(self);
+    }
+
+    /// Makes the prompts that are created inside the closure on the current thread use the theme.
+    /// The previous theme is restored when the closure returns
+    pub fn scope<R>(&self, flow: impl FnOnce() -> R) -> R {
+        struct RestoreTheme(Option<Theme>);
+
+        impl Drop for RestoreTheme {
+            fn drop(&mut self) {
+                FLOW_THEME.with(|theme| *theme.borrow_mut() = self.0.take());
+            }
+        }
+
+        let previous = FLOW_THEME.with(|theme| theme.borrow_mut().replace(self.clone()));
+        let _restore = RestoreTheme(previous);
+        flow()
+    }
+
+    pub fn prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    pub fn prefix_formatting(mut self, f: Formatting) -> Self {
+        self.prefix_formatting = f;
+        self
+    }
+
+    pub fn submitted_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.submitted_prefix = prefix.into();
+        self
+    }
+
+    pub fn submitted_prefix_formatting(mut self, f: Formatting) -> Self {
+        self.submitted_prefix_formatting = f;
+        self
+    }
+
+    pub fn aborted_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.aborted_prefix = prefix.into();
+        self
+    }
+
+    pub fn aborted_prefix_formatting(mut self, f: Formatting) -> Self {
+        self.aborted_prefix_formatting = f;
+        self
+    }
+
+    pub fn aborted_label_formatting(mut self, f: Formatting) -> Self {
+        self.aborted_label_formatting = f;
+        self
+    }
+
+    pub fn aborted_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.aborted_message = message.into();
+        self
+    }
+
+    pub fn aborted_message_formatting(mut self, f: Formatting) -> Self {
+        self.aborted_message_formatting = f;
+        self
+    }
+
+    pub fn error_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.error_prefix = prefix.into();
+        self
+    }
+
+    pub fn error_prefix_formatting(mut self, f: Formatting) -> Self {
+        self.error_prefix_formatting = f;
+        self
+    }
+
+    pub fn label_suffix<S: Into<String>>(mut self, suffix: S) -> Self {
+        self.label_suffix = suffix.into();
+        self
+    }
+
+    pub fn input_on_new_line(mut self, on_new_line: bool) -> Self {
+        self.input_on_new_line = on_new_line;
+        self
+    }
+
+    pub fn label_formatting(mut self, f: Formatting) -> Self {
+        self.label_formatting = f;
+        self
+    }
+
+    pub fn input(mut self, f: Formatting) -> Self {
+        self.input = f;
+        self
+    }
+
+    pub fn placeholder(mut self, f: Formatting) -> Self {
+        self.placeholder = f;
+        self
+    }
+
+    pub fn success(mut self, f: Formatting) -> Self {
+        self.success = f;
+        self
+    }
+
+    pub fn error(mut self, f: Formatting) -> Self {
+        self.error = f;
+        self
+    }
+
+    pub fn help(mut self, f: Formatting) -> Self {
+        self.help = f;
+        self
+    }
+
+    pub fn accent(mut self, f: Formatting) -> Self {
+        self.accent = f;
+        self
+    }
+
+    pub fn muted(mut self, f: Formatting) -> Self {
+        self.muted = f;
+        self
+    }
+
+    pub fn highlight(mut self, f: Formatting) -> Self {
+        self.highlight = f;
+        self
+    }
+
+    pub fn checkbox_highlight(mut self, f: Formatting) -> Self {
+        self.checkbox_highlight = f;
+        self
+    }
+
+    pub fn highlighted_marker<S: Into<String>>(mut self, marker: S) -> Self {
+        self.highlighted_marker = marker.into();
+        self
+    }
+
+    pub fn not_highlighted_marker<S: Into<String>>(mut self, marker: S) -> Self {
+        self.not_highlighted_marker = marker.into();
+        self
+    }
+
+    pub fn grabbed_marker<S: Into<String>>(mut self, marker: S) -> Self {
+        self.grabbed_marker = marker.into();
+        self
+    }
+
+    pub fn new_item_marker<S: Into<String>>(mut self, marker: S) -> Self {
+        self.new_item_marker = marker.into();
+        self
+    }
+
+    pub fn checkbox<S: Into<String>>(mut self, opening: S, selection: S, closing: S) -> Self {
+        self.checkbox_opening = opening.into();
+        self.checkbox_selection = selection.into();
+        self.checkbox_closing = closing.into();
+        self
+    }
+
+    pub fn checkbox_partial_selection<S: Into<String>>(mut self, sign: S) -> Self {
+        self.checkbox_partial_selection = sign.into();
+        self
+    }
+
+    pub fn expanded_icon<S: Into<String>>(mut self, icon: S) -> Self {
+        self.expanded_icon = icon.into();
+        self
+    }
+
+    pub fn collapsed_icon<S: Into<String>>(mut self, icon: S) -> Self {
+        self.collapsed_icon = icon.into();
+        self
+    }
+
+    pub fn ascending_indicator<S: Into<String>>(mut self, indicator: S) -> Self {
+        self.ascending_indicator = indicator.into();
+        self
+    }
+
+    pub fn descending_indicator<S: Into<String>>(mut self, indicator: S) -> Self {
+        self.descending_indicator = indicator.into();
+        self
+    }
+
+    pub fn spinner_frames<S: Into<String>>(mut self, frames: impl IntoIterator<Item = S>) -> Self {
+        self.spinner_frames = frames.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn progress_bar<S: Into<String>>(mut self, filled: S, empty: S) -> Self {
+        self.progress_filled = filled.into();
+        self.progress_empty = empty.into();
+        self
+    }
+
+    pub fn markup(mut self, mode: MarkupMode) -> Self {
+        self.markup = mode;
+        self
+    }
+
+    pub fn data_markup(mut self, mode: MarkupMode) -> Self {
+        self.data_markup = mode;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::RecordingBuffer,
+        prompts::{Multiselect, Prompt},
+        style::TreeStyle,
+    };
+
+    fn rendered(prompt: &impl Prompt<Vec<&'static str>>) -> String {
+        let mut commands = RecordingBuffer::default();
+        prompt.draw(&mut commands);
+        commands.text()
+    }
+
+    #[test]
+    fn styles_are_created_from_theme_in_scope() {
+        let theme = Theme::default().prefix(">>").checkbox("(", "*", ")");
+        let themed = theme.scope(|| Multiselect::new("Toppings", ["cheese"].into_iter()));
+        let plain = Multiselect::new("Toppings", ["cheese"].into_iter());
+
+        assert!(rendered(&themed).starts_with(">> Toppings: "));
+        assert!(rendered(&themed).contains("( ) cheese"));
+        assert!(rendered(&plain).starts_with("? Toppings: "));
+    }
+
+    #[test]
+    fn scope_restores_previous_theme() {
+        Theme::minimal().scope(|| {
+            Theme::high_contrast().scope(|| {
+                assert_eq!(Theme::current().highlighted_marker, "▶ ");
+            });
+            assert_eq!(Theme::current().expanded_icon, "- ");
+        });
+        assert_eq!(Theme::current().expanded_icon, "▾ ");
+    }
+
+    #[test]
+    fn multiselect_highlight_stays_dark_green_by_default() {
+        let style = crate::style::MultiselectionStyle::from_theme(&Theme::default());
+        assert_eq!(
+            style.highlighted_option_formatting.foreground_color,
+            Some(Color::DarkGreen)
+        );
+        assert!(style.highlighted_option_formatting.text_formatting.is_empty());
+    }
+
+    #[test]
+    fn minimal_theme_is_ascii() {
+        let theme = Theme::minimal();
+        let tree_style = TreeStyle::from_theme(&theme);
+        let symbols = [
+            &theme.prefix,
+            &theme.highlighted_marker,
+            &theme.grabbed_marker,
+            &theme.new_item_marker,
+            &theme.checkbox_selection,
+            &tree_style.expanded_icon,
+            &tree_style.collapsed_icon,
+            &theme.ascending_indicator,
+            &theme.descending_indicator,
+            &theme.progress_filled,
+            &theme.progress_empty,
+        ];
+        assert!(symbols.iter().all(|s| s.is_ascii()));
+        assert!(theme.spinner_frames.iter().all(|s| s.is_ascii()));
+        assert!(theme.error.foreground_color.is_none());
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+};
+
+use super::Theme;
+
+/// Environment variable with the name of the theme preset or the path to the theme file
+pub const THEME_ENV_VAR: &str = "CLI_PROMPTS_THEME";
+
+const CONFIG_DIR: &str = "cli-prompts";
+const THEME_FILE_NAMES: [&str; 2] = ["theme.toml", "theme.json"];
+
+/// Errors that can occur while loading a theme
+#[derive(Debug)]
+pub enum ThemeError {
+    /// The theme file couldn't be read
+    Io(std::io::Error),
+
+    /// The theme couldn't be parsed
+    Parse(String),
+}
+
+impl Theme {
+    /// Loads the theme from a file. The format is detected by the file extension:
+    /// `toml` or `json`. The fields that are missing in the file are taken from the default theme
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(ThemeError::Io)?;
+        let theme = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::from_toml(&contents),
+            Some("json") => Self::from_json(&contents),
+            _ => Err(ThemeError::Parse("unsupported file format".into())),
+        };
+        theme.map_err(|e| match e {
+            ThemeError::Parse(error) => ThemeError::Parse(format!("{}: {}", path.display(), error)),
+            e => e,
+        })
+    }
+
+    /// Parses the theme in TOML format
+    ///
+    /// ```rust
+    /// use cli_prompts::style::Theme;
+    ///
+    /// let theme = Theme::from_toml(r##"
+    ///     prefix = "›"
+    ///     highlighted_marker = "→ "
+    ///     accent = { fg = "#ff8800", options = ["bold"] }
+    ///     error = { fg = 203 }
+    /// "##).unwrap();
+    /// ```
+    pub fn from_toml(theme: &str) -> Result<Self, ThemeError> {
+        toml::from_str(theme).map_err(|e| ThemeError::Parse(e.to_string()))
+    }
+
+    /// Parses the theme in JSON format
+    pub fn from_json(theme: &str) -> Result<Self, ThemeError> {
+        serde_json::from_str(theme).map_err(|e| ThemeError::Parse(e.to_string()))
+    }
+
+    /// Loads the theme configured by the user. The `CLI_PROMPTS_THEME` environment variable
+    /// is checked first. It contains either the name of a preset or the path to the theme file.
+    /// Otherwise the theme is loaded from `theme.toml` or `theme.json` in the `cli-prompts`
+    /// directory of the user's config directory, e.g. `~/.config/cli-prompts/theme.toml`.
+    ///
+    /// Returns `Ok(None)` if no theme is configured
+    pub fn from_config() -> Result<Option<Self>, ThemeError> {
+        if let Some(value) = std::env::var_os(THEME_ENV_VAR).filter(|v| !v.is_empty()) {
+            if let Some(theme) = value.to_str().and_then(Theme::preset) {
+                return Ok(Some(theme));
+            }
+            return Self::from_file(PathBuf::from(value)).map(Some);
+        }
+
+        Self::config_file().map(Self::from_file).transpose()
+    }
+
+    /// Loads the theme configured by the user with `from_config` and installs it
+    pub fn install_from_config() -> Result<(), ThemeError> {
+        if let Some(theme) = Self::from_config()? {
+            theme.install();
+        }
+        Ok(())
+    }
+
+    fn config_file() -> Option<PathBuf> {
+        let env_path = |name| {
+            std::env::var_os(name)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        };
+        let config_dir = env_path("XDG_CONFIG_HOME")
+            .or_else(|| env_path("HOME").map(|home| home.join(".config")))
+            .or_else(|| env_path("APPDATA"))?
+            .join(CONFIG_DIR);
+
+        THEME_FILE_NAMES
+            .iter()
+            .map(|name| config_dir.join(name))
+            .find(|path| path.is_file())
+    }
+}
+
+impl Display for ThemeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ThemeError::Io(error) => write!(f, "I/O error: {}", error),
+            ThemeError::Parse(error) => write!(f, "parse error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{Color, FormattingOption, InputStyle};
+
+    #[test]
+    fn missing_fields_are_taken_from_default_theme() {
+        let theme = Theme::from_toml(
+            r##"
+            prefix = ">"
+            accent = { fg = "#ff8800", bg = "dark-grey", options = ["bold", "underline"] }
+            error = { foreground_color = 203 }
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(theme.prefix, ">");
+        assert!(matches!(
+            theme.accent.foreground_color,
+            Some(Color::Rgb {
+                r: 255,
+                g: 136,
+                b: 0
+            })
+        ));
+        assert!(matches!(
+            theme.accent.background_color,
+            Some(Color::DarkGrey)
+        ));
+        assert!(matches!(
+            theme.accent.text_formatting[..],
+            [FormattingOption::Bold, FormattingOption::Underline]
+        ));
+        assert!(matches!(
+            theme.error.foreground_color,
+            Some(Color::AnsiValue(203))
+        ));
+        assert_eq!(
+            theme.highlighted_marker,
+            Theme::default().highlighted_marker
+        );
+    }
+
+    #[test]
+    fn styles_roundtrip_through_json() {
+        let style = InputStyle::default();
+        let json = serde_json::to_string(&style).unwrap();
+        assert!(json.contains(r#""submitted_formatting":{"foreground_color":"green""#));
+
+        let style: InputStyle =
+            serde_json::from_str(r#"{ "error_formatting": { "fg": "yellow" } }"#).unwrap();
+        assert!(matches!(
+            style.error_formatting.foreground_color,
+            Some(Color::Yellow)
+        ));
+        assert!(matches!(
+            style.submitted_formatting.foreground_color,
+            Some(Color::Green)
+        ));
+    }
+
+    #[test]
+    fn reports_invalid_colors() {
+        let Err(error) = Theme::from_json(r#"{ "muted": { "fg": "purple" } }"#) else {
+            panic!("The color is expected to be invalid");
+        };
+        assert!(error.to_string().contains("Unknown color: purple"));
+    }
+}